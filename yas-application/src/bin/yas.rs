@@ -1,44 +1,358 @@
-use clap::{command, Command};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use clap::{arg, command, ArgMatches, Command, ValueEnum};
+use yas::common::cancel::CancellationToken;
+use yas::config::{ConfigFile, ResolvedOptions};
+use yas::export::ScanHistory;
+use yas::server::ScanServer;
 use yas::utils::press_any_key_to_continue;
-use yas_genshin::application::ArtifactScannerApplication;
-use yas_starrail::application::RelicScannerApplication;
+use yas_genshin::application::{ArtifactConverterApplication, ArtifactDiffApplication, ArtifactScannerApplication};
+use yas_genshin::export::artifact::{GenshinArtifactImportFormat, ARTIFACT_EXPORT_FORMATS};
+use yas_starrail::application::{CharacterScannerApplication, LightConeScannerApplication, RelicConverterApplication, RelicDiffApplication, RelicScannerApplication};
+use yas_starrail::export::{StarRailRelicImportFormat, RELIC_EXPORT_FORMATS};
+use yas_wutheringwaves::application::{WWEchoDiffApplication, WWEchoScannerApplication};
+
+/// The scanner subcommands, each is also the name of its section in the config file
+const SCANNER_NAMES: [&str; 5] = ["genshin", "starrail", "starrail-light-cone", "starrail-character", "wutheringwaves"];
+
+fn get_scanner_command(name: &'static str) -> Command {
+    let cmd = match name {
+        "genshin" => ArtifactScannerApplication::build_command(),
+        "starrail" => RelicScannerApplication::build_command(),
+        "starrail-light-cone" => LightConeScannerApplication::build_command(),
+        "starrail-character" => CharacterScannerApplication::build_command(),
+        "wutheringwaves" => WWEchoScannerApplication::build_command(),
+        _ => unreachable!(),
+    };
+    cmd.name(name)
+}
+
+fn run_scanner(name: &str, matches: &ArgMatches) -> Result<()> {
+    let matches = matches.clone();
+    match name {
+        "genshin" => ArtifactScannerApplication::new(matches).run(),
+        "starrail" => RelicScannerApplication::new(matches).run(),
+        "starrail-light-cone" => LightConeScannerApplication::new(matches).run(),
+        "starrail-character" => CharacterScannerApplication::new(matches).run(),
+        "wutheringwaves" => WWEchoScannerApplication::new(matches).run(),
+        _ => unreachable!(),
+    }
+}
+
+fn get_convert_command() -> Command {
+    let to_formats = [ARTIFACT_EXPORT_FORMATS.names(), RELIC_EXPORT_FORMATS.names()].concat().join(", ");
+    Command::new("convert")
+        .about("将之前导出的文件转换为其他格式，无需重新扫描")
+        .arg(arg!(--from <FORMAT> "输入格式：good, mona, mingyu-lab, hsr, march7th"))
+        .arg(arg!(--to <FORMAT>).help(format!("输出格式：{}, all", to_formats)))
+        .arg(arg!(-o --"output-dir" <DIR> "输出目录").default_value("."))
+        .arg(arg!(<INPUT> "输入文件"))
+}
+
+fn run_convert(matches: &ArgMatches) -> Result<()> {
+    let from = matches.get_one::<String>("from").unwrap();
+    let to = matches.get_one::<String>("to").unwrap();
+    let input = PathBuf::from(matches.get_one::<String>("INPUT").unwrap());
+    let output_dir = PathBuf::from(matches.get_one::<String>("output-dir").unwrap());
 
-fn get_genshin_command() -> Command {
-    let cmd = ArtifactScannerApplication::build_command();
-    cmd.name("genshin")
+    if let (Ok(from), true) = (
+        GenshinArtifactImportFormat::from_str(from, true),
+        ARTIFACT_EXPORT_FORMATS.contains(to)
+    ) {
+        let application = ArtifactConverterApplication { input, from, to: to.clone(), output_dir };
+        application.run()
+    } else if let (Ok(from), true) = (
+        StarRailRelicImportFormat::from_str(from, true),
+        RELIC_EXPORT_FORMATS.contains(to)
+    ) {
+        let application = RelicConverterApplication { input, from, to: to.clone(), output_dir };
+        application.run()
+    } else {
+        Err(anyhow!("不支持从 {} 转换到 {}", from, to))
+    }
+}
+
+fn get_diff_command() -> Command {
+    Command::new("diff")
+        .about("比较两次导出的结果，列出新增、移除、升级和换装的物品")
+        .arg(arg!(-g --game <GAME> "游戏")
+            .value_parser(["genshin", "starrail", "wutheringwaves"])
+            .default_value("genshin"))
+        .arg(arg!(-o --"output-dir" <DIR> "输出目录").default_value("."))
+        .arg(arg!(<OLD> "之前的导出文件"))
+        .arg(arg!(<NEW> "之后的导出文件"))
 }
 
-fn get_starrail_command() -> Command {
-    let cmd = RelicScannerApplication::build_command();
-    cmd.name("starrail")
+fn run_diff(matches: &ArgMatches) -> Result<()> {
+    let old = PathBuf::from(matches.get_one::<String>("OLD").unwrap());
+    let new = PathBuf::from(matches.get_one::<String>("NEW").unwrap());
+    let output_dir = PathBuf::from(matches.get_one::<String>("output-dir").unwrap());
+
+    match matches.get_one::<String>("game").unwrap().as_str() {
+        "genshin" => ArtifactDiffApplication { old, new, output_dir }.run(),
+        "starrail" => RelicDiffApplication { old, new, output_dir }.run(),
+        "wutheringwaves" => WWEchoDiffApplication { old, new, output_dir }.run(),
+        _ => unreachable!(),
+    }
+}
+
+fn get_history_command() -> Command {
+    let game = arg!(-g --game <GAME> "游戏")
+        .value_parser(["genshin", "starrail", "wutheringwaves"])
+        .default_value("genshin");
+    let uid = arg!(--uid <UID> "只查询该 UID 的记录");
+
+    Command::new("history")
+        .about("查询扫描历史数据库")
+        .arg(arg!(--db <PATH> "历史数据库").default_value("yas_history.db"))
+        .subcommand_required(true)
+        .subcommand(Command::new("runs").about("列出所有扫描记录"))
+        .subcommand(Command::new("items").about("列出扫描到过的物品").arg(game.clone()).arg(uid.clone()))
+        .subcommand(Command::new("removed").about("列出最近一次扫描中消失的物品").arg(game).arg(uid))
+        .subcommand(Command::new("item")
+            .about("列出一件物品的每次扫描记录，包括副词条的变化")
+            .arg(arg!(<ID> "物品编号").value_parser(clap::value_parser!(i64))))
+}
+
+fn run_history(matches: &ArgMatches) -> Result<()> {
+    let history = ScanHistory::open(matches.get_one::<String>("db").unwrap())?;
+
+    let table = match matches.subcommand() {
+        Some(("runs", _)) => history.get_runs_table()?,
+        Some(("item", sub_matches)) => history.get_sightings_table(*sub_matches.get_one::<i64>("ID").unwrap())?,
+        Some((name, sub_matches)) => {
+            let game = sub_matches.get_one::<String>("game").unwrap();
+            let uid = sub_matches.get_one::<String>("uid").map(|x| x.as_str());
+            let entries = if name == "removed" {
+                history.removed_items(game, uid)?
+            } else {
+                history.items(game, uid)?
+            };
+            ScanHistory::get_entries_table(&entries)
+        },
+        None => unreachable!(),
+    };
+
+    let table = format!("{}", table);
+    // print multiline
+    for line in table.lines() {
+        log::info!("{}", line);
+    }
+    Ok(())
+}
+
+/// The options a scan request of `yas serve` may set, options that name a file are left out on purpose
+fn get_serve_options(name: &str) -> &'static [&'static str] {
+    match name {
+        "genshin" => &[
+            "format", "min-star", "min-level", "ignore-dup", "verbose", "number",
+            "max-row", "scroll-delay", "max-wait-switch-item", "cloud-wait-switch-item", "output-failed-items",
+        ],
+        "starrail" => &[
+            "format", "min-star", "min-level", "ignore-dup", "verbose", "number",
+            "max-row", "scroll-delay", "max-wait-switch-item", "cloud-wait-switch-item", "output-failed-items",
+            "equipper-max-distance",
+        ],
+        "starrail-light-cone" => &[
+            "format", "verbose", "number",
+            "max-row", "scroll-delay", "max-wait-switch-item", "cloud-wait-switch-item",
+            "equipper-max-distance",
+        ],
+        "starrail-character" => &["verbose", "number", "wait-switch-character", "character-scroll-delay"],
+        "wutheringwaves" => &[
+            "format", "min-star", "min-level", "ignore-dup", "verbose", "number",
+            "max-row", "scroll-delay", "max-wait-switch-item", "cloud-wait-switch-item",
+        ],
+        _ => unreachable!(),
+    }
+}
+
+fn serve_scanner(name: &str, matches: ArgMatches, token: CancellationToken) -> Result<()> {
+    match name {
+        "genshin" => ArtifactScannerApplication::new(matches).with_cancellation_token(token).run(),
+        "starrail" => RelicScannerApplication::new(matches).with_cancellation_token(token).run(),
+        "starrail-light-cone" => LightConeScannerApplication::new(matches).with_cancellation_token(token).run(),
+        "starrail-character" => CharacterScannerApplication::new(matches).with_cancellation_token(token).run(),
+        "wutheringwaves" => WWEchoScannerApplication::new(matches).with_cancellation_token(token).run(),
+        _ => unreachable!(),
+    }
+}
+
+fn get_serve_command() -> Command {
+    Command::new("serve")
+        .about("启动本地 HTTP 服务，供网页端的配装工具控制扫描")
+        .arg(arg!(--port <PORT> "端口").value_parser(clap::value_parser!(u16)).default_value("8787"))
+        .arg(arg!(-o --"output-dir" <DIR> "每次扫描的输出目录所在的目录").default_value("yas_serve"))
+        .arg(arg!(--"allow-origin" <ORIGIN> "允许访问的网页来源，如 https://example.com，可以重复")
+            .action(clap::ArgAction::Append))
+}
+
+fn run_serve(matches: &ArgMatches, scanner_commands: &ScannerCommands) -> Result<()> {
+    let port = *matches.get_one::<u16>("port").unwrap();
+    let output_dir = PathBuf::from(matches.get_one::<String>("output-dir").unwrap());
+
+    let mut server = ScanServer::new(output_dir);
+    for scanner in scanner_commands.scanners.iter() {
+        let name = scanner.name;
+        let command = scanner.command.clone();
+        server = server.add_game(name, get_serve_options(name), Box::new(move |args, token| {
+            let matches = command.clone().try_get_matches_from(args)?;
+            serve_scanner(name, matches, token)
+        }));
+    }
+    for origin in matches.get_many::<String>("allow-origin").into_iter().flatten() {
+        server = server.allow_origin(origin);
+    }
+    server.serve(port)
+}
+
+fn get_config_command() -> Command {
+    Command::new("config")
+        .about("配置文件")
+        .subcommand_required(true)
+        .subcommand(Command::new("show")
+            .about("显示合并配置文件、环境变量后各选项的值")
+            .arg(arg!(-g --game <GAME> "游戏")
+                .value_parser(SCANNER_NAMES)
+                .default_value("genshin")))
+}
+
+fn run_config(matches: &ArgMatches, scanner_commands: &ScannerCommands) -> Result<()> {
+    let matches = match matches.subcommand() {
+        Some(("show", v)) => v,
+        _ => unreachable!(),
+    };
+    let game = matches.get_one::<String>("game").unwrap();
+    let scanner = scanner_commands.get(game);
+
+    match scanner_commands.config_path.as_ref() {
+        Some(path) => log::info!("配置文件：{:?}", path),
+        None => log::info!("未找到配置文件"),
+    }
+    if let Some(profile) = scanner_commands.profile.as_ref() {
+        log::info!("配置方案：{}", profile);
+    }
+
+    let game_matches = scanner.command.clone().try_get_matches_from([game.as_str()])?;
+    let table = format!("{}", scanner.options.get_table(&scanner.command, &game_matches));
+    // print multiline
+    for line in table.lines() {
+        log::info!("{}", line);
+    }
+    Ok(())
+}
+
+struct ScannerCommand {
+    name: &'static str,
+    command: Command,
+    options: ResolvedOptions,
+}
+
+/// The scanner commands, with the options of the config file and the environment as their defaults
+struct ScannerCommands {
+    config_path: Option<PathBuf>,
+    profile: Option<String>,
+    scanners: Vec<ScannerCommand>,
+}
+
+impl ScannerCommands {
+    fn new(config_path: Option<&str>, profile: Option<String>) -> Result<ScannerCommands> {
+        let config = match config_path {
+            Some(path) => Some(ConfigFile::load(path)?),
+            None => ConfigFile::find_default()?,
+        };
+        if profile.is_some() && config.is_none() {
+            return Err(anyhow!("使用配置方案需要配置文件"));
+        }
+
+        let mut scanners = Vec::new();
+        for name in SCANNER_NAMES {
+            let command = get_scanner_command(name);
+            let options = ResolvedOptions::resolve(&command, name, config.as_ref(), profile.as_deref())?;
+            scanners.push(ScannerCommand { name, command: options.apply(command), options });
+        }
+
+        Ok(ScannerCommands {
+            config_path: config.map(|x| x.path().to_path_buf()),
+            profile,
+            scanners,
+        })
+    }
+
+    fn get(&self, name: &str) -> &ScannerCommand {
+        self.scanners.iter().find(|x| x.name == name).unwrap()
+    }
+
+    fn commands(&self) -> Vec<Command> {
+        self.scanners.iter().map(|x| x.command.clone()).collect()
+    }
+}
+
+fn build_command(scanners: Vec<Command>) -> Command {
+    command!()
+        .arg(arg!(--config <FILE> "配置文件，默认为当前目录下的 yas.yaml、yas.yml 或 yas.toml").global(true))
+        .arg(arg!(--profile <NAME> "配置文件中的配置方案，也可以用环境变量 YAS_PROFILE 指定").global(true))
+        .subcommands(scanners)
+        .subcommand(get_convert_command())
+        .subcommand(get_diff_command())
+        .subcommand(get_history_command())
+        .subcommand(get_serve_command())
+        .subcommand(get_config_command())
+}
+
+/// A global option, given before or after the subcommand
+fn get_global_option(matches: &ArgMatches, id: &str) -> Option<String> {
+    let get = |m: &ArgMatches| m.try_get_one::<String>(id).ok().flatten().cloned();
+    get(matches).or_else(|| matches.subcommand().and_then(|(_, m)| get(m)))
+}
+
+fn run() -> Result<()> {
+    // the config file decides the defaults of the scanner commands, so it is read before they are built
+    let first_pass = build_command(SCANNER_NAMES.iter().map(|x| get_scanner_command(x)).collect())
+        .ignore_errors(true)
+        .try_get_matches()
+        .ok();
+    let config_path = first_pass.as_ref().and_then(|m| get_global_option(m, "config"));
+    let profile = first_pass.as_ref()
+        .and_then(|m| get_global_option(m, "profile"))
+        .or_else(|| std::env::var("YAS_PROFILE").ok());
+    let scanner_commands = ScannerCommands::new(config_path.as_deref(), profile)?;
+
+    let arg_matches = build_command(scanner_commands.commands()).get_matches();
+
+    if let Some((subcommand_name, matches)) = arg_matches.subcommand() {
+        if SCANNER_NAMES.contains(&subcommand_name) {
+            run_scanner(subcommand_name, matches)
+        } else if subcommand_name == "convert" {
+            run_convert(matches)
+        } else if subcommand_name == "diff" {
+            run_diff(matches)
+        } else if subcommand_name == "history" {
+            run_history(matches)
+        } else if subcommand_name == "serve" {
+            run_serve(matches, &scanner_commands)
+        } else if subcommand_name == "config" {
+            run_config(matches, &scanner_commands)
+        } else {
+            Ok(())
+        }
+    } else {
+        Ok(())
+    }
 }
 
 fn init() {
     env_logger::Builder::new()
         .filter_level(log::LevelFilter::Info)
+        // stdout is kept for --json-lines
+        .target(env_logger::Target::Stderr)
         .init();
 }
 
 pub fn main() {
     init();
-    let cmd = command!()
-        .subcommand(get_genshin_command())
-        .subcommand(get_starrail_command());
-    let arg_matches = cmd.get_matches();
-
-    let res = if let Some((subcommand_name, matches)) = arg_matches.subcommand() {
-        if subcommand_name == "genshin" {
-            let application = ArtifactScannerApplication::new(matches.clone());
-            application.run()
-        } else if subcommand_name == "starrail" {
-            let application = RelicScannerApplication::new(matches.clone());
-            application.run()
-        } else {
-            Ok(())
-        }
-    } else {
-        Ok(())
-    };
+    let res = run();
 
     match res {
         Ok(_) => {
@@ -5,6 +5,8 @@ use log::error;
 pub fn main() {
     env_logger::Builder::new()
         .filter_level(log::LevelFilter::Info)
+        // stdout is kept for --json-lines
+        .target(env_logger::Target::Stderr)
         .init();
 
     let command = ArtifactScannerApplication::build_command();
@@ -0,0 +1,23 @@
+use yas::utils::press_any_key_to_continue;
+use yas_starrail::application::CharacterScannerApplication;
+use log::error;
+
+pub fn main() {
+    env_logger::Builder::new()
+        .filter_level(log::LevelFilter::Info)
+        // stdout is kept for --json-lines
+        .target(env_logger::Target::Stderr)
+        .init();
+    let matches = CharacterScannerApplication::build_command().get_matches();
+
+    let application = CharacterScannerApplication::new(matches);
+    match application.run() {
+        Err(e) => {
+            error!("error: {}", e);
+            press_any_key_to_continue();
+        },
+        _ => {
+            press_any_key_to_continue();
+        }
+    }
+}
\ No newline at end of file
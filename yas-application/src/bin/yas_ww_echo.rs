@@ -5,6 +5,8 @@ use yas_wutheringwaves::application::WWEchoScannerApplication;
 pub fn main() {
     env_logger::Builder::new()
         .filter_level(log::LevelFilter::Info)
+        // stdout is kept for --json-lines
+        .target(env_logger::Target::Stderr)
         .init();
     let matches = WWEchoScannerApplication::build_command().get_matches();
 
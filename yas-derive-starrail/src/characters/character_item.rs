@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct CharacterDataItem {
+    /// The HSR-Scanner key
+    pub key: String,
+    pub name_chs: String,
+    pub id: String,
+    /// The center pixel of the equipper avatar, if known
+    pub equipper_color: Option<[u8; 3]>,
+}
@@ -0,0 +1,3 @@
+mod character_item;
+
+pub use character_item::CharacterDataItem;
@@ -0,0 +1,108 @@
+extern crate proc_macro2;
+
+use proc_macro::TokenStream;
+use crate::characters::CharacterDataItem;
+use crate::relic_sets::RelicSetDataItem;
+use quote::quote;
+
+mod characters;
+mod relic_sets;
+
+fn read_data<T: serde::de::DeserializeOwned>(input: TokenStream) -> T {
+    let ast: syn::LitStr = syn::parse(input).unwrap();
+
+    let filename = ast.value();
+
+    let content = std::fs::read_to_string(filename).unwrap();
+    serde_json::from_str(&content).unwrap()
+}
+
+fn relic_set_impl(data: &[RelicSetDataItem], set_names: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
+    let en_names: Vec<_> = data.iter().map(|x| x.name_en.clone()).collect();
+
+    let mut pieces = Vec::new();
+    for (item, set_name) in data.iter().zip(set_names.iter()) {
+        for piece in item.pieces.iter() {
+            let name = &piece.name_chs;
+            let slot: proc_macro2::TokenStream = piece.slot.parse().unwrap();
+            pieces.push(quote! {
+                (#name, Self:: #set_name, RelicSlot:: #slot)
+            });
+        }
+    }
+
+    quote! {
+        impl RelicSetName {
+            /// All relic piece names in zh-cn, with the set and slot they belong to
+            pub const ALL_PIECES_CHS: &'static [(&'static str, RelicSetName, RelicSlot)] = &[
+                #(#pieces),*
+            ];
+
+            /// The english name, e.g. `Passerby of Wandering Cloud`
+            pub fn name_en(&self) -> &'static str {
+                match *self {
+                    #(Self:: #set_names => #en_names),*
+                }
+            }
+        }
+    }
+}
+
+/// Generates `RelicSetName` and its piece names from a StarRailRes-style `relic_sets.json`.
+/// `RelicSlot` must be in scope
+#[proc_macro]
+pub fn yas_starrail_relic_sets(input: TokenStream) -> TokenStream {
+    let set_data: Vec<RelicSetDataItem> = read_data(input);
+
+    let set_names: Vec<proc_macro2::TokenStream> = set_data.iter()
+        .map(|x| x.name.parse().unwrap())
+        .collect();
+
+    let set_name_enum = quote! {
+        #[derive(Debug, Hash, Clone, PartialEq, Eq, strum_macros::Display, strum_macros::EnumIter)]
+        pub enum RelicSetName {
+            #(#set_names),*
+        }
+    };
+    let set_name_impl = relic_set_impl(&set_data, &set_names);
+
+    let result = quote! {
+        #set_name_enum
+        #set_name_impl
+    };
+
+    result.into()
+}
+
+/// Generates the `CHARACTERS` list from a StarRailRes-style `characters.json`.
+/// `CharacterData` must be in scope
+#[proc_macro]
+pub fn yas_starrail_characters(input: TokenStream) -> TokenStream {
+    let character_data: Vec<CharacterDataItem> = read_data(input);
+
+    let characters: Vec<_> = character_data.iter().map(|item| {
+        let key = &item.key;
+        let name_chs = &item.name_chs;
+        let id = &item.id;
+        let equipper_color = match item.equipper_color {
+            Some([r, g, b]) => quote! { Some(image::Rgb([#r, #g, #b])) },
+            None => quote! { None },
+        };
+        quote! {
+            CharacterData {
+                key: #key,
+                name_chs: #name_chs,
+                id: #id,
+                equipper_color: #equipper_color,
+            }
+        }
+    }).collect();
+
+    let result = quote! {
+        pub const CHARACTERS: &[CharacterData] = &[
+            #(#characters),*
+        ];
+    };
+
+    result.into()
+}
@@ -0,0 +1,3 @@
+mod relic_set_item;
+
+pub use relic_set_item::RelicSetDataItem;
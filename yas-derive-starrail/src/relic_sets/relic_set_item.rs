@@ -0,0 +1,17 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct RelicPieceDataItem {
+    pub name_chs: String,
+    /// A variant of `RelicSlot`
+    pub slot: String,
+}
+
+#[derive(Deserialize)]
+pub struct RelicSetDataItem {
+    /// The variant name, which is also the March7th name
+    pub name: String,
+    /// The HSR-Scanner name
+    pub name_en: String,
+    pub pieces: Vec<RelicPieceDataItem>,
+}
@@ -3,6 +3,8 @@ use serde::{Deserialize};
 #[derive(Deserialize)]
 pub struct EchoDataItem {
     pub name: String,
-    // pub cost: usize,
+    /// Missing in data files that predate it
+    #[serde(default)]
+    pub cost: Option<usize>,
     pub name_chs: String,
 }
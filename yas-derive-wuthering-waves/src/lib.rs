@@ -18,6 +18,12 @@ fn get_echo_names(data: &[EchoDataItem]) -> Vec<proc_macro2::TokenStream> {
 
 fn echo_name_from_chs(data: &[EchoDataItem], echo_names: &[proc_macro2::TokenStream]) -> proc_macro2::TokenStream {
     let chs_names: Vec<_> = data.iter().map(|x| x.name_chs.clone()).collect();
+    let costs: Vec<_> = data.iter()
+        .map(|x| match x.cost {
+            Some(cost) => quote! { Some(#cost) },
+            None => quote! { None },
+        })
+        .collect();
 
     let mut temp = Vec::new();
     for i in 0..echo_names.len() {
@@ -30,12 +36,36 @@ fn echo_name_from_chs(data: &[EchoDataItem], echo_names: &[proc_macro2::TokenStr
 
     quote! {
         impl WWEchoName {
+            /// All echo names in chs
+            pub const ALL_CHS: &'static [(&'static str, WWEchoName)] = &[
+                #((#chs_names, Self:: #echo_names)),*
+            ];
+
+            /// The cost of the echo, 1, 3 or 4, `None` if the echo data does not have it
+            pub fn cost(&self) -> Option<usize> {
+                match *self {
+                    #(Self:: #echo_names => #costs),*
+                }
+            }
+
             pub fn from_chs(chs: &str) -> Option<Self> {
-                match chs {
+                let exact = match chs {
                     #(#temp)*
                     // It's weird that this will not compile
                     // #(#chs_names => Some(Self::#echo_names)),*
-                    _ => return None,
+                    _ => None,
+                };
+                if exact.is_some() {
+                    return exact;
+                }
+
+                let matcher = yas::common::fuzzy_match::FuzzyMatcher::new(Self::ALL_CHS.iter().cloned());
+                match matcher.find(chs) {
+                    yas::common::fuzzy_match::FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                        log::warn!("echo name `{}` is ambiguous: {:?}", chs, candidates);
+                        None
+                    },
+                    result => result.value(),
                 }
             }
         }
@@ -54,7 +84,7 @@ pub fn yas_wuthering_waves_echoes(input: TokenStream) -> TokenStream {
     let echo_names = get_echo_names(&echo_data);
 
     let echo_name_enum = quote! {
-        #[derive(Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display)]
+        #[derive(Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display, strum_macros::EnumString)]
         pub enum WWEchoName {
             #(#echo_names),*
         }
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use log::info;
+
+use yas::export::{AssetEmitter, ExportAssets};
+
+use crate::export::artifact::{artifacts_from_str, GenshinArtifactExporter, GenshinArtifactImportFormat};
+
+/// Convert a previous export to another format, without scanning again
+pub struct ArtifactConverterApplication {
+    pub input: PathBuf,
+    pub from: GenshinArtifactImportFormat,
+    /// A name in `ARTIFACT_EXPORT_FORMATS`, or `all`
+    pub to: String,
+    pub output_dir: PathBuf,
+}
+
+impl ArtifactConverterApplication {
+    pub fn run(&self) -> Result<()> {
+        let contents = std::fs::read_to_string(&self.input)?;
+        let artifacts = artifacts_from_str(&contents, self.from)?;
+        info!("读取到 {} 件圣遗物", artifacts.len());
+
+        let exporter = GenshinArtifactExporter {
+            format: self.to.clone(),
+            results: Some(&artifacts),
+            failed_items: &[],
+            output_dir: self.output_dir.clone(),
+            output_failed_items: false,
+            merge_into: None,
+            template: None,
+        };
+        let mut export_assets = ExportAssets::new();
+        exporter.emit(&mut export_assets);
+
+        let stats = export_assets.save();
+        info!("保存结果：");
+        let table = format!("{}", stats);
+        // print multiline
+        for line in table.lines() {
+            info!("{}", line);
+        }
+
+        Ok(())
+    }
+}
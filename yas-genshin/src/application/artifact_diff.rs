@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use log::info;
+
+use yas::common::inventory_diff::InventoryDiff;
+use yas::export::ExportAssets;
+
+use crate::export::artifact::load_artifacts;
+
+/// Compare two exports of the artifacts, e.g. before and after a farming session
+pub struct ArtifactDiffApplication {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    pub output_dir: PathBuf,
+}
+
+impl ArtifactDiffApplication {
+    pub fn run(&self) -> Result<()> {
+        let old = load_artifacts(&self.old)?;
+        let new = load_artifacts(&self.new)?;
+        let diff = InventoryDiff::new(&old, &new);
+
+        let table = format!("{}", diff.get_table());
+        // print multiline
+        for line in table.lines() {
+            info!("{}", line);
+        }
+        info!("{}", diff);
+
+        let mut export_assets = ExportAssets::new();
+        export_assets.add_asset(
+            Some(String::from("diff")),
+            self.output_dir.join("diff.json"),
+            serde_json::to_string(&diff)?.into_bytes(),
+            Some(String::from("圣遗物变化")));
+        let stats = export_assets.save();
+        info!("保存结果：");
+        let table = format!("{}", stats);
+        for line in table.lines() {
+            info!("{}", line);
+        }
+
+        Ok(())
+    }
+}
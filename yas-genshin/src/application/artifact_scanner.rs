@@ -1,31 +1,45 @@
 use anyhow::Result;
 use anyhow::anyhow;
-use clap::{command, ArgMatches, Args};
-use log::info;
+use clap::{command, ArgMatches, Args, FromArgMatches};
+use log::{info, warn};
 
-use yas::export::{AssetEmitter, ExportAssets};
+use yas::common::cancel::CancellationToken;
+use yas::common::main_stat_check::MainStatCheckSummary;
+use yas::export::{emit_item, emit_removed, emit_status, AssetEmitter, ExportAssets, ExportSaveConfig, JsonLinesConfig, ScanHistoryConfig, TemplateExportConfig};
 use yas::game_info::{GameInfo, GameInfoBuilder};
 use yas::window_info::{load_window_info_repo, WindowInfoRepository};
 
-use crate::artifact::GenshinArtifact;
-use crate::export::artifact::{ExportArtifactConfig, GenshinArtifactExporter};
+use crate::artifact::{ArtifactField, GenshinArtifact, GenshinArtifactConversionError};
+use crate::export::artifact::{load_artifacts, ExportArtifactConfig, GenshinArtifactExporter};
 use crate::scanner::{GenshinArtifactScanner, GenshinArtifactScannerConfig};
 use crate::scanner_controller::repository_layout::GenshinRepositoryScannerLogicConfig;
 
 pub struct ArtifactScannerApplication {
     arg_matches: ArgMatches,
+    cancellation_token: CancellationToken,
 }
 
 impl ArtifactScannerApplication {
     pub fn new(matches: ArgMatches) -> Self {
         ArtifactScannerApplication {
-            arg_matches: matches
+            arg_matches: matches,
+            cancellation_token: CancellationToken::new(),
         }
     }
 
+    /// Cancel the scan through `token`, e.g. from `yas serve`
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = token;
+        self
+    }
+
     pub fn build_command() -> clap::Command {
         let mut cmd = command!();
         cmd = <ExportArtifactConfig as Args>::augment_args_for_update(cmd);
+        cmd = <TemplateExportConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ScanHistoryConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ExportSaveConfig as Args>::augment_args_for_update(cmd);
+        cmd = <JsonLinesConfig as Args>::augment_args_for_update(cmd);
         cmd = <GenshinArtifactScannerConfig as Args>::augment_args_for_update(cmd);
         cmd = <GenshinRepositoryScannerLogicConfig as Args>::augment_args_for_update(cmd);
         cmd
@@ -60,6 +74,9 @@ impl ArtifactScannerApplication {
 impl ArtifactScannerApplication {
     pub fn run(&self) -> Result<()> {
         let arg_matches = &self.arg_matches;
+        JsonLinesConfig::from_arg_matches(arg_matches)?.apply();
+        // fail on a bad template before spending a whole scan on it
+        TemplateExportConfig::from_arg_matches(arg_matches)?.build::<GenshinArtifact>()?;
         let window_info_repository = Self::get_window_info_repository();
         let game_info = Self::get_game_info()?;
 
@@ -81,25 +98,82 @@ impl ArtifactScannerApplication {
             arg_matches,
             game_info.clone()
         )?;
+        scanner.set_cancellation_token(self.cancellation_token.clone());
+
+        let scanner_config = GenshinArtifactScannerConfig::from_arg_matches(arg_matches)?;
+        let previous = match scanner_config.since.as_ref() {
+            Some(path) => {
+                let previous = load_artifacts(path)?;
+                info!("增量扫描，从 {} 读取到 {} 件圣遗物", path, previous.len());
+                scanner.set_known_artifacts(previous.iter().map(|x| x.identity()).collect());
+                Some(previous)
+            },
+            None => None,
+        };
 
         let result = scanner.scan()?;
-        let artifacts = result
-            .iter()
-            .flat_map(GenshinArtifact::try_from)
-            .collect::<Vec<_>>();
+        let mut main_stat_summary = MainStatCheckSummary::default();
+        let mut artifacts = Vec::new();
+        let mut failed_items = Vec::new();
+        for (index, item) in result.iter().enumerate() {
+            let mut artifact = match GenshinArtifact::try_from(item) {
+                Ok(v) => v,
+                Err(e) => {
+                    failed_items.push(e.with_index(index));
+                    continue;
+                },
+            };
+
+            let check = artifact.check_main_stat();
+            main_stat_summary.add(&check);
+            // the worker streamed the item before it was checked
+            if check.is_corrected() {
+                emit_item(index, &artifact);
+            }
+            if check.is_rejected() {
+                emit_removed(index);
+                let raw = format!("{}+{} Lv.{}", item.main_stat_name, item.main_stat_value, item.level);
+                failed_items.push(GenshinArtifactConversionError::new(ArtifactField::MainStatLevel, raw).with_index(index));
+                continue;
+            }
 
-        let exporter = GenshinArtifactExporter::new(arg_matches, &artifacts)?;
+            artifacts.push(artifact);
+        }
+
+        if let Some(previous) = previous {
+            info!("增量扫描到 {} 件圣遗物，与之前的 {} 件合并", artifacts.len(), previous.len());
+            artifacts = GenshinArtifact::merge_scans(artifacts, previous);
+        }
+
+        let exporter = GenshinArtifactExporter::new(arg_matches, &artifacts, &failed_items)?;
         let mut export_assets = ExportAssets::new();
         exporter.emit(&mut export_assets);
 
-        let stats = export_assets.save();
+        let stats = export_assets.save_with(&ExportSaveConfig::from_arg_matches(arg_matches)?);
         info!("保存结果：");
         let table = format!("{}", stats);
         // print multiline
         for line in table.lines() {
             info!("{}", line);
         }
+
+        let history_config = ScanHistoryConfig::from_arg_matches(arg_matches)?;
+        if let Some(run_id) = history_config.record(env!("CARGO_PKG_VERSION"), &artifacts)? {
+            info!("已追加到历史数据库，本次记录编号 {}", run_id);
+        }
+        if main_stat_summary.has_discrepancy() {
+            warn!("{}", main_stat_summary);
+        } else {
+            info!("{}", main_stat_summary);
+        }
+        if !failed_items.is_empty() {
+            warn!("{} 件圣遗物转换失败：", failed_items.len());
+            for e in failed_items.iter() {
+                warn!("{}", e);
+            }
+        }
         info!("Yas 识别结束，共识别到 {} 件圣遗物。", result.len());
+        emit_status("finished");
 
         Ok(())
     }
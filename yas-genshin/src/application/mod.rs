@@ -1,4 +1,7 @@
+pub use artifact_converter::ArtifactConverterApplication;
+pub use artifact_diff::ArtifactDiffApplication;
 pub use artifact_scanner::ArtifactScannerApplication;
 
+mod artifact_converter;
+mod artifact_diff;
 mod artifact_scanner;
-
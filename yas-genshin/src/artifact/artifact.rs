@@ -1,13 +1,19 @@
+use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 
-use log::error;
+use lazy_static::lazy_static;
+use log::{error, warn};
 use regex::Regex;
-use strum_macros::Display;
+use strum_macros::{Display, EnumIter};
 
-use crate::character::CHARACTER_NAMES;
+use yas::common::fuzzy_match::{FuzzyMatcher, FuzzyMatchResult};
+use yas::common::sub_stat_roll::RollItem;
+
+use crate::artifact::{ArtifactField, GenshinArtifactConversionError};
+use crate::character::CHARACTER_NAME_MATCHER;
 use crate::scanner::GenshinArtifactScanResult;
 
-#[derive(Debug, Hash, Clone, PartialEq, Eq, Display)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Display, EnumIter)]
 pub enum ArtifactStatName {
     HealingBonus,
     CriticalDamage,
@@ -30,7 +36,7 @@ pub enum ArtifactStatName {
     DendroBonus,
 }
 
-#[derive(Debug, Hash, Clone, PartialEq, Eq, Display)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Display, EnumIter)]
 pub enum ArtifactSlot {
     Flower,
     Feather,
@@ -39,7 +45,7 @@ pub enum ArtifactSlot {
     Head,
 }
 
-#[derive(Debug, Hash, Clone, PartialEq, Eq, Display)]
+#[derive(Debug, Hash, Clone, PartialEq, Eq, Display, EnumIter)]
 pub enum ArtifactSetName {
     ArchaicPetra,
     HeartOfDepth,
@@ -102,6 +108,10 @@ pub enum ArtifactSetName {
 pub struct ArtifactStat {
     pub name: ArtifactStatName,
     pub value: f64,
+    /// How many rolls make up this sub stat, inferred from the roll table
+    pub roll_count: Option<usize>,
+    /// Set when the value cannot be made up of legal rolls, most likely because it was misread
+    pub impossible: bool,
 }
 
 #[derive(Debug, Hash, Clone, PartialEq, Eq)]
@@ -122,7 +132,7 @@ pub struct GenshinArtifact {
 impl Hash for ArtifactStat {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.name.hash(state);
-        let v = (self.value * 1000.0) as i32;
+        let v = (self.value * 1000.0).round() as i32;
         v.hash(state);
     }
 }
@@ -133,8 +143,8 @@ impl PartialEq for ArtifactStat {
             return false;
         }
 
-        let v1 = (self.value * 1000.0) as i32;
-        let v2 = (other.value * 1000.0) as i32;
+        let v1 = (self.value * 1000.0).round() as i32;
+        let v2 = (other.value * 1000.0).round() as i32;
 
         v1 == v2
     }
@@ -196,23 +206,25 @@ impl ArtifactStat {
         Some(ArtifactStat {
             name: stat_name,
             value,
+            roll_count: None,
+            impossible: false,
         })
     }
 }
 
 impl TryFrom<&GenshinArtifactScanResult> for GenshinArtifact {
-    type Error = ();
+    type Error = GenshinArtifactConversionError;
 
     fn try_from(value: &GenshinArtifactScanResult) -> Result<Self, Self::Error> {
-        let set_name = ArtifactSetName::from_zh_cn(&value.name).ok_or(())?;
-        let slot = ArtifactSlot::from_zh_cn(&value.name).ok_or(())?;
+        let name_error = || GenshinArtifactConversionError::new(ArtifactField::Name, &value.name);
+        let set_name = ArtifactSetName::from_zh_cn(&value.name).ok_or_else(name_error)?;
+        let slot = ArtifactSlot::from_zh_cn(&value.name).ok_or_else(name_error)?;
         let star = value.star;
         let lock = value.lock;
 
-        let main_stat = ArtifactStat::from_zh_cn_raw(
-            (value.main_stat_name.clone() + "+" + value.main_stat_value.as_str()).as_str(),
-        )
-        .ok_or(())?;
+        let main_stat_raw = value.main_stat_name.clone() + "+" + value.main_stat_value.as_str();
+        let main_stat = ArtifactStat::from_zh_cn_raw(&main_stat_raw)
+            .ok_or_else(|| GenshinArtifactConversionError::new(ArtifactField::MainStat, &main_stat_raw))?;
 
         let sub1 = ArtifactStat::from_zh_cn_raw(&value.sub_stat[0]);
         let sub2 = ArtifactStat::from_zh_cn_raw(&value.sub_stat[1]);
@@ -223,16 +235,19 @@ impl TryFrom<&GenshinArtifactScanResult> for GenshinArtifact {
             let chars = value.equip.chars().collect::<Vec<_>>();
             let equip_name = chars[..chars.len() - 3].iter().collect::<String>();
 
-            if CHARACTER_NAMES.contains(equip_name.as_str()) {
-                Some(equip_name)
-            } else {
-                None
+            match CHARACTER_NAME_MATCHER.find(&equip_name) {
+                FuzzyMatchResult::Matched { value, .. } => Some(String::from(value)),
+                FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                    warn!("equip `{}` is ambiguous: {:?}", equip_name, candidates);
+                    None
+                },
+                FuzzyMatchResult::NotFound => None,
             }
         } else {
             None
         };
 
-        Ok(GenshinArtifact {
+        let mut artifact = GenshinArtifact {
             set_name,
             slot,
             star,
@@ -244,474 +259,338 @@ impl TryFrom<&GenshinArtifactScanResult> for GenshinArtifact {
             sub_stat_3: sub3,
             sub_stat_4: sub4,
             equip,
-        })
+        };
+        artifact.check_sub_stat_rolls();
+
+        Ok(artifact)
+    }
+}
+
+impl GenshinArtifact {
+    /// A copy without lock and equip, which can change while the artifact stays the same.
+    /// The main stat value is left out too, since it is determined by the level and some formats do not store it.
+    /// Used to match an artifact across scans
+    pub fn identity(&self) -> GenshinArtifact {
+        let mut main_stat = self.main_stat.clone();
+        main_stat.value = 0.0;
+
+        GenshinArtifact {
+            lock: false,
+            equip: None,
+            main_stat,
+            ..self.clone()
+        }
+    }
+
+    /// Merge the artifacts of an incremental scan into the complete result of a previous scan.
+    /// Previous artifacts that are scanned again are replaced by the new ones
+    pub fn merge_scans(scanned: Vec<GenshinArtifact>, previous: Vec<GenshinArtifact>) -> Vec<GenshinArtifact> {
+        let scanned_identities = scanned.iter()
+            .map(|x| x.identity())
+            .collect::<HashSet<_>>();
+
+        let mut result = scanned;
+        result.extend(previous.into_iter().filter(|x| !scanned_identities.contains(&x.identity())));
+        result
     }
 }
 
+/// All artifact names in zh-cn, with the set and slot they belong to
+#[rustfmt::skip]
+const ARTIFACT_NAMES_ZH_CN: &[(&str, ArtifactSetName, ArtifactSlot)] = &[
+    ("磐陀裂生之花", ArtifactSetName::ArchaicPetra, ArtifactSlot::Flower),
+    ("嵯峨群峰之翼", ArtifactSetName::ArchaicPetra, ArtifactSlot::Feather),
+    ("星罗圭壁之晷", ArtifactSetName::ArchaicPetra, ArtifactSlot::Sand),
+    // "壁" is different
+    ("星罗圭璧之晷", ArtifactSetName::ArchaicPetra, ArtifactSlot::Sand),
+    ("巉岩琢塑之樽", ArtifactSetName::ArchaicPetra, ArtifactSlot::Goblet),
+    ("不动玄石之相", ArtifactSetName::ArchaicPetra, ArtifactSlot::Head),
+    ("历经风雪的思念", ArtifactSetName::BlizzardStrayer, ArtifactSlot::Flower),
+    ("摧冰而行的执望", ArtifactSetName::BlizzardStrayer, ArtifactSlot::Feather),
+    ("冰雪故园的终期", ArtifactSetName::BlizzardStrayer, ArtifactSlot::Sand),
+    ("遍结寒霜的傲骨", ArtifactSetName::BlizzardStrayer, ArtifactSlot::Goblet),
+    ("破冰踏雪的回音", ArtifactSetName::BlizzardStrayer, ArtifactSlot::Head),
+    ("染血的铁之心", ArtifactSetName::BloodstainedChivalry, ArtifactSlot::Flower),
+    ("染血的黑之羽", ArtifactSetName::BloodstainedChivalry, ArtifactSlot::Feather),
+    ("骑士染血之时", ArtifactSetName::BloodstainedChivalry, ArtifactSlot::Sand),
+    ("染血骑士之杯", ArtifactSetName::BloodstainedChivalry, ArtifactSlot::Goblet),
+    ("染血的铁假面", ArtifactSetName::BloodstainedChivalry, ArtifactSlot::Head),
+    ("魔女的炎之花", ArtifactSetName::CrimsonWitch, ArtifactSlot::Flower),
+    ("魔女常燃之羽", ArtifactSetName::CrimsonWitch, ArtifactSlot::Feather),
+    ("魔女破灭之时", ArtifactSetName::CrimsonWitch, ArtifactSlot::Sand),
+    ("魔女的心之火", ArtifactSetName::CrimsonWitch, ArtifactSlot::Goblet),
+    ("焦灼的魔女帽", ArtifactSetName::CrimsonWitch, ArtifactSlot::Head),
+    ("角斗士的留恋", ArtifactSetName::GladiatorFinale, ArtifactSlot::Flower),
+    ("角斗士的归宿", ArtifactSetName::GladiatorFinale, ArtifactSlot::Feather),
+    ("角斗士的希冀", ArtifactSetName::GladiatorFinale, ArtifactSlot::Sand),
+    ("角斗士的酣醉", ArtifactSetName::GladiatorFinale, ArtifactSlot::Goblet),
+    ("角斗士的凯旋", ArtifactSetName::GladiatorFinale, ArtifactSlot::Head),
+    ("饰金胸花", ArtifactSetName::HeartOfDepth, ArtifactSlot::Flower),
+    ("追忆之风", ArtifactSetName::HeartOfDepth, ArtifactSlot::Feather),
+    ("坚铜罗盘", ArtifactSetName::HeartOfDepth, ArtifactSlot::Sand),
+    ("沉波之盏", ArtifactSetName::HeartOfDepth, ArtifactSlot::Goblet),
+    ("酒渍船帽", ArtifactSetName::HeartOfDepth, ArtifactSlot::Head),
+    ("渡火者的决绝", ArtifactSetName::LavaWalker, ArtifactSlot::Flower),
+    ("渡火者的解脱", ArtifactSetName::LavaWalker, ArtifactSlot::Feather),
+    ("渡火者的煎熬", ArtifactSetName::LavaWalker, ArtifactSlot::Sand),
+    ("渡火者的醒悟", ArtifactSetName::LavaWalker, ArtifactSlot::Goblet),
+    ("渡火者的智慧", ArtifactSetName::LavaWalker, ArtifactSlot::Head),
+    ("远方的少女之心", ArtifactSetName::MaidenBeloved, ArtifactSlot::Flower),
+    ("少女飘摇的思念", ArtifactSetName::MaidenBeloved, ArtifactSlot::Feather),
+    ("少女苦短的良辰", ArtifactSetName::MaidenBeloved, ArtifactSlot::Sand),
+    ("少女片刻的闲暇", ArtifactSetName::MaidenBeloved, ArtifactSlot::Goblet),
+    ("少女易逝的芳颜", ArtifactSetName::MaidenBeloved, ArtifactSlot::Head),
+    ("宗室之花", ArtifactSetName::NoblesseOblige, ArtifactSlot::Flower),
+    ("宗室之翎", ArtifactSetName::NoblesseOblige, ArtifactSlot::Feather),
+    ("宗室时计", ArtifactSetName::NoblesseOblige, ArtifactSlot::Sand),
+    ("宗室银瓮", ArtifactSetName::NoblesseOblige, ArtifactSlot::Goblet),
+    ("宗室面具", ArtifactSetName::NoblesseOblige, ArtifactSlot::Head),
+    ("夏祭之花", ArtifactSetName::RetracingBolide, ArtifactSlot::Flower),
+    ("夏祭终末", ArtifactSetName::RetracingBolide, ArtifactSlot::Feather),
+    ("夏祭之刻", ArtifactSetName::RetracingBolide, ArtifactSlot::Sand),
+    ("夏祭水玉", ArtifactSetName::RetracingBolide, ArtifactSlot::Goblet),
+    ("夏祭之面", ArtifactSetName::RetracingBolide, ArtifactSlot::Head),
+    ("平雷之心", ArtifactSetName::ThunderSmoother, ArtifactSlot::Flower),
+    ("平雷之羽", ArtifactSetName::ThunderSmoother, ArtifactSlot::Feather),
+    ("平雷之刻", ArtifactSetName::ThunderSmoother, ArtifactSlot::Sand),
+    ("平雷之器", ArtifactSetName::ThunderSmoother, ArtifactSlot::Goblet),
+    ("平雷之冠", ArtifactSetName::ThunderSmoother, ArtifactSlot::Head),
+    ("雷鸟的怜悯", ArtifactSetName::ThunderingFury, ArtifactSlot::Flower),
+    ("雷灾的孑遗", ArtifactSetName::ThunderingFury, ArtifactSlot::Feather),
+    ("雷霆的时计", ArtifactSetName::ThunderingFury, ArtifactSlot::Sand),
+    ("降雷的凶兆", ArtifactSetName::ThunderingFury, ArtifactSlot::Goblet),
+    ("唤雷的头冠", ArtifactSetName::ThunderingFury, ArtifactSlot::Head),
+    ("野花记忆的绿野", ArtifactSetName::ViridescentVenerer, ArtifactSlot::Flower),
+    ("猎人青翠的箭羽", ArtifactSetName::ViridescentVenerer, ArtifactSlot::Feather),
+    ("翠绿猎人的笃定", ArtifactSetName::ViridescentVenerer, ArtifactSlot::Sand),
+    ("翠绿猎人的容器", ArtifactSetName::ViridescentVenerer, ArtifactSlot::Goblet),
+    ("翠绿的猎人之冠", ArtifactSetName::ViridescentVenerer, ArtifactSlot::Head),
+    ("乐团的晨光", ArtifactSetName::WandererTroupe, ArtifactSlot::Flower),
+    ("琴师的箭羽", ArtifactSetName::WandererTroupe, ArtifactSlot::Feather),
+    ("终幕的时计", ArtifactSetName::WandererTroupe, ArtifactSlot::Sand),
+    ("终末的时计", ArtifactSetName::WandererTroupe, ArtifactSlot::Sand),
+    ("吟游者之壶", ArtifactSetName::WandererTroupe, ArtifactSlot::Goblet),
+    ("指挥的礼帽", ArtifactSetName::WandererTroupe, ArtifactSlot::Head),
+    ("战狂的蔷薇", ArtifactSetName::Berserker, ArtifactSlot::Flower),
+    ("战狂的翎羽", ArtifactSetName::Berserker, ArtifactSlot::Feather),
+    ("战狂的时计", ArtifactSetName::Berserker, ArtifactSlot::Sand),
+    ("战狂的骨杯", ArtifactSetName::Berserker, ArtifactSlot::Goblet),
+    ("战狂的鬼面", ArtifactSetName::Berserker, ArtifactSlot::Head),
+    ("勇士的勋章", ArtifactSetName::BraveHeart, ArtifactSlot::Flower),
+    ("勇士的期许", ArtifactSetName::BraveHeart, ArtifactSlot::Feather),
+    ("勇士的坚毅", ArtifactSetName::BraveHeart, ArtifactSlot::Sand),
+    ("勇士的壮行", ArtifactSetName::BraveHeart, ArtifactSlot::Goblet),
+    ("勇士的冠冕", ArtifactSetName::BraveHeart, ArtifactSlot::Head),
+    ("守护之花", ArtifactSetName::DefenderWill, ArtifactSlot::Flower),
+    ("守护徽印", ArtifactSetName::DefenderWill, ArtifactSlot::Feather),
+    ("守护座钟", ArtifactSetName::DefenderWill, ArtifactSlot::Sand),
+    ("守护之皿", ArtifactSetName::DefenderWill, ArtifactSlot::Goblet),
+    ("守护束带", ArtifactSetName::DefenderWill, ArtifactSlot::Head),
+    ("流放者之花", ArtifactSetName::Exile, ArtifactSlot::Flower),
+    ("流放者之羽", ArtifactSetName::Exile, ArtifactSlot::Feather),
+    ("流放者怀表", ArtifactSetName::Exile, ArtifactSlot::Sand),
+    ("流放者之杯", ArtifactSetName::Exile, ArtifactSlot::Goblet),
+    ("流放者头冠", ArtifactSetName::Exile, ArtifactSlot::Head),
+    ("赌徒的胸花", ArtifactSetName::Gambler, ArtifactSlot::Flower),
+    ("赌徒的羽饰", ArtifactSetName::Gambler, ArtifactSlot::Feather),
+    ("赌徒的怀表", ArtifactSetName::Gambler, ArtifactSlot::Sand),
+    ("赌徒的骰盅", ArtifactSetName::Gambler, ArtifactSlot::Goblet),
+    ("赌徒的耳环", ArtifactSetName::Gambler, ArtifactSlot::Head),
+    ("教官的胸花", ArtifactSetName::Instructor, ArtifactSlot::Flower),
+    ("教官的羽饰", ArtifactSetName::Instructor, ArtifactSlot::Feather),
+    ("教官的怀表", ArtifactSetName::Instructor, ArtifactSlot::Sand),
+    ("教官的茶杯", ArtifactSetName::Instructor, ArtifactSlot::Goblet),
+    ("教官的帽子", ArtifactSetName::Instructor, ArtifactSlot::Head),
+    ("武人的红花", ArtifactSetName::MartialArtist, ArtifactSlot::Flower),
+    ("武人的羽饰", ArtifactSetName::MartialArtist, ArtifactSlot::Feather),
+    ("武人的水漏", ArtifactSetName::MartialArtist, ArtifactSlot::Sand),
+    ("武人的酒杯", ArtifactSetName::MartialArtist, ArtifactSlot::Goblet),
+    ("武人的头巾", ArtifactSetName::MartialArtist, ArtifactSlot::Head),
+    ("祭水礼冠", ArtifactSetName::PrayersForDestiny, ArtifactSlot::Head),
+    ("祭火礼冠", ArtifactSetName::PrayersForIllumination, ArtifactSlot::Head),
+    ("祭雷礼冠", ArtifactSetName::PrayersForWisdom, ArtifactSlot::Head),
+    ("祭冰礼冠", ArtifactSetName::PrayersToSpringtime, ArtifactSlot::Head),
+    ("故人之心", ArtifactSetName::ResolutionOfSojourner, ArtifactSlot::Flower),
+    ("归乡之羽", ArtifactSetName::ResolutionOfSojourner, ArtifactSlot::Feather),
+    ("逐光之石", ArtifactSetName::ResolutionOfSojourner, ArtifactSlot::Sand),
+    ("异国之盏", ArtifactSetName::ResolutionOfSojourner, ArtifactSlot::Goblet),
+    ("感别之冠", ArtifactSetName::ResolutionOfSojourner, ArtifactSlot::Head),
+    ("学士的书签", ArtifactSetName::Scholar, ArtifactSlot::Flower),
+    ("学士的羽笔", ArtifactSetName::Scholar, ArtifactSlot::Feather),
+    ("学士的时钟", ArtifactSetName::Scholar, ArtifactSlot::Sand),
+    ("学士的墨杯", ArtifactSetName::Scholar, ArtifactSlot::Goblet),
+    ("学士的镜片", ArtifactSetName::Scholar, ArtifactSlot::Head),
+    ("奇迹之花", ArtifactSetName::TinyMiracle, ArtifactSlot::Flower),
+    ("奇迹之羽", ArtifactSetName::TinyMiracle, ArtifactSlot::Feather),
+    ("奇迹之沙", ArtifactSetName::TinyMiracle, ArtifactSlot::Sand),
+    ("奇迹之杯", ArtifactSetName::TinyMiracle, ArtifactSlot::Goblet),
+    ("奇迹耳坠", ArtifactSetName::TinyMiracle, ArtifactSlot::Head),
+    ("冒险家之花", ArtifactSetName::Adventurer, ArtifactSlot::Flower),
+    ("冒险家尾羽", ArtifactSetName::Adventurer, ArtifactSlot::Feather),
+    ("冒险家怀表", ArtifactSetName::Adventurer, ArtifactSlot::Sand),
+    ("冒险家金杯", ArtifactSetName::Adventurer, ArtifactSlot::Goblet),
+    ("冒险家头带", ArtifactSetName::Adventurer, ArtifactSlot::Head),
+    ("幸运儿绿花", ArtifactSetName::LuckyDog, ArtifactSlot::Flower),
+    ("幸运儿鹰羽", ArtifactSetName::LuckyDog, ArtifactSlot::Feather),
+    ("幸运儿沙漏", ArtifactSetName::LuckyDog, ArtifactSlot::Sand),
+    ("幸运儿之杯", ArtifactSetName::LuckyDog, ArtifactSlot::Goblet),
+    ("幸运儿银冠", ArtifactSetName::LuckyDog, ArtifactSlot::Head),
+    ("游医的银莲", ArtifactSetName::TravelingDoctor, ArtifactSlot::Flower),
+    ("游医的枭羽", ArtifactSetName::TravelingDoctor, ArtifactSlot::Feather),
+    ("游医的怀钟", ArtifactSetName::TravelingDoctor, ArtifactSlot::Sand),
+    ("游医的药壶", ArtifactSetName::TravelingDoctor, ArtifactSlot::Goblet),
+    ("游医的方巾", ArtifactSetName::TravelingDoctor, ArtifactSlot::Head),
+    ("勋绩之花", ArtifactSetName::TenacityOfTheMillelith, ArtifactSlot::Flower),
+    ("昭武翎羽", ArtifactSetName::TenacityOfTheMillelith, ArtifactSlot::Feather),
+    ("金铜时晷", ArtifactSetName::TenacityOfTheMillelith, ArtifactSlot::Sand),
+    ("盟誓金爵", ArtifactSetName::TenacityOfTheMillelith, ArtifactSlot::Goblet),
+    ("将帅兜鍪", ArtifactSetName::TenacityOfTheMillelith, ArtifactSlot::Head),
+    ("无垢之花", ArtifactSetName::PaleFlame, ArtifactSlot::Flower),
+    ("贤医之羽", ArtifactSetName::PaleFlame, ArtifactSlot::Feather),
+    ("停摆之刻", ArtifactSetName::PaleFlame, ArtifactSlot::Sand),
+    ("超越之盏", ArtifactSetName::PaleFlame, ArtifactSlot::Goblet),
+    ("嗤笑之面", ArtifactSetName::PaleFlame, ArtifactSlot::Head),
+    ("明威之镡", ArtifactSetName::EmblemOfSeveredFate, ArtifactSlot::Flower),
+    ("切落之羽", ArtifactSetName::EmblemOfSeveredFate, ArtifactSlot::Feather),
+    ("雷云之笼", ArtifactSetName::EmblemOfSeveredFate, ArtifactSlot::Sand),
+    ("绯花之壶", ArtifactSetName::EmblemOfSeveredFate, ArtifactSlot::Goblet),
+    ("华饰之兜", ArtifactSetName::EmblemOfSeveredFate, ArtifactSlot::Head),
+    ("羁缠之花", ArtifactSetName::ShimenawaReminiscence, ArtifactSlot::Flower),
+    ("思忆之矢", ArtifactSetName::ShimenawaReminiscence, ArtifactSlot::Feather),
+    ("朝露之时", ArtifactSetName::ShimenawaReminiscence, ArtifactSlot::Sand),
+    ("祈望之心", ArtifactSetName::ShimenawaReminiscence, ArtifactSlot::Goblet),
+    ("无常之面", ArtifactSetName::ShimenawaReminiscence, ArtifactSlot::Head),
+    ("荣花之期", ArtifactSetName::HuskOfOpulentDreams, ArtifactSlot::Flower),
+    ("华馆之羽", ArtifactSetName::HuskOfOpulentDreams, ArtifactSlot::Feather),
+    ("众生之谣", ArtifactSetName::HuskOfOpulentDreams, ArtifactSlot::Sand),
+    ("梦醒之瓢", ArtifactSetName::HuskOfOpulentDreams, ArtifactSlot::Goblet),
+    ("形骸之笠", ArtifactSetName::HuskOfOpulentDreams, ArtifactSlot::Head),
+    ("海染之花", ArtifactSetName::OceanHuedClam, ArtifactSlot::Flower),
+    ("渊宫之羽", ArtifactSetName::OceanHuedClam, ArtifactSlot::Feather),
+    ("离别之贝", ArtifactSetName::OceanHuedClam, ArtifactSlot::Sand),
+    ("真珠之笼", ArtifactSetName::OceanHuedClam, ArtifactSlot::Goblet),
+    ("海祇之冠", ArtifactSetName::OceanHuedClam, ArtifactSlot::Head),
+    ("生灵之华", ArtifactSetName::VermillionHereafter, ArtifactSlot::Flower),
+    ("阳辔之遗", ArtifactSetName::VermillionHereafter, ArtifactSlot::Sand),
+    ("潜光片羽", ArtifactSetName::VermillionHereafter, ArtifactSlot::Feather),
+    ("结契之刻", ArtifactSetName::VermillionHereafter, ArtifactSlot::Goblet),
+    ("虺雷之姿", ArtifactSetName::VermillionHereafter, ArtifactSlot::Head),
+    ("魂香之花", ArtifactSetName::EchoesOfAnOffering, ArtifactSlot::Flower),
+    ("祝祀之凭", ArtifactSetName::EchoesOfAnOffering, ArtifactSlot::Sand),
+    ("垂玉之叶", ArtifactSetName::EchoesOfAnOffering, ArtifactSlot::Feather),
+    ("涌泉之盏", ArtifactSetName::EchoesOfAnOffering, ArtifactSlot::Goblet),
+    ("浮溯之珏", ArtifactSetName::EchoesOfAnOffering, ArtifactSlot::Head),
+    ("迷宫的游人", ArtifactSetName::DeepwoodMemories, ArtifactSlot::Flower),
+    ("翠蔓的智者", ArtifactSetName::DeepwoodMemories, ArtifactSlot::Feather),
+    ("贤智的定期", ArtifactSetName::DeepwoodMemories, ArtifactSlot::Sand),
+    ("迷误者之灯", ArtifactSetName::DeepwoodMemories, ArtifactSlot::Goblet),
+    ("月桂的宝冠", ArtifactSetName::DeepwoodMemories, ArtifactSlot::Head),
+    ("梦中的铁花", ArtifactSetName::GildedDreams, ArtifactSlot::Flower),
+    ("裁断的翎羽", ArtifactSetName::GildedDreams, ArtifactSlot::Feather),
+    ("沉金的岁月", ArtifactSetName::GildedDreams, ArtifactSlot::Sand),
+    ("如蜜的终宴", ArtifactSetName::GildedDreams, ArtifactSlot::Goblet),
+    ("沙王的投影", ArtifactSetName::GildedDreams, ArtifactSlot::Head),
+    ("月女的华彩", ArtifactSetName::FlowerOfParadiseLost, ArtifactSlot::Flower),
+    ("谢落的筵席", ArtifactSetName::FlowerOfParadiseLost, ArtifactSlot::Feather),
+    ("凝结的时刻", ArtifactSetName::FlowerOfParadiseLost, ArtifactSlot::Sand),
+    ("守秘的魔瓶", ArtifactSetName::FlowerOfParadiseLost, ArtifactSlot::Goblet),
+    ("紫晶的花冠", ArtifactSetName::FlowerOfParadiseLost, ArtifactSlot::Head),
+    ("众王之都的开端", ArtifactSetName::DesertPavilionChronicle, ArtifactSlot::Flower),
+    ("黄金邦国的结末", ArtifactSetName::DesertPavilionChronicle, ArtifactSlot::Feather),
+    ("失落迷途的机芯", ArtifactSetName::DesertPavilionChronicle, ArtifactSlot::Sand),
+    ("迷醉长梦的守护", ArtifactSetName::DesertPavilionChronicle, ArtifactSlot::Goblet),
+    ("流沙贵嗣的遗宝", ArtifactSetName::DesertPavilionChronicle, ArtifactSlot::Head),
+    ("恶龙的单片镜", ArtifactSetName::NymphsDream, ArtifactSlot::Head),
+    ("坏巫师的羽杖", ArtifactSetName::NymphsDream, ArtifactSlot::Feather),
+    ("旅途中的鲜花", ArtifactSetName::NymphsDream, ArtifactSlot::Flower),
+    ("水仙的时时刻刻", ArtifactSetName::NymphsDream, ArtifactSlot::Sand),
+    ("勇者们的茶会", ArtifactSetName::NymphsDream, ArtifactSlot::Goblet),
+    ("灵光明烁之心", ArtifactSetName::VourukashasGlow, ArtifactSlot::Head),
+    ("琦色灵彩之羽", ArtifactSetName::VourukashasGlow, ArtifactSlot::Feather),
+    ("灵光源起之蕊", ArtifactSetName::VourukashasGlow, ArtifactSlot::Flower),
+    ("久远花落之时", ArtifactSetName::VourukashasGlow, ArtifactSlot::Sand),
+    ("无边酣乐之筵", ArtifactSetName::VourukashasGlow, ArtifactSlot::Goblet),
+    ("猎人的胸花", ArtifactSetName::MarechausseeHunter, ArtifactSlot::Flower),
+    ("杰作的序曲", ArtifactSetName::MarechausseeHunter, ArtifactSlot::Feather),
+    ("裁判的时刻", ArtifactSetName::MarechausseeHunter, ArtifactSlot::Sand),
+    ("遗忘的容器", ArtifactSetName::MarechausseeHunter, ArtifactSlot::Goblet),
+    ("老兵的容颜", ArtifactSetName::MarechausseeHunter, ArtifactSlot::Head),
+    ("黄金乐曲的变奏", ArtifactSetName::GoldenTroupe, ArtifactSlot::Flower),
+    ("黄金飞鸟的落羽", ArtifactSetName::GoldenTroupe, ArtifactSlot::Feather),
+    ("黄金时代的先声", ArtifactSetName::GoldenTroupe, ArtifactSlot::Sand),
+    ("黄金之夜的喧嚣", ArtifactSetName::GoldenTroupe, ArtifactSlot::Goblet),
+    ("黄金剧团的奖赏", ArtifactSetName::GoldenTroupe, ArtifactSlot::Head),
+    ("昔时传奏之诗", ArtifactSetName::SongOfDaysPast, ArtifactSlot::Head),
+    ("昔时浮想之思", ArtifactSetName::SongOfDaysPast, ArtifactSlot::Feather),
+    ("昔时遗落之誓", ArtifactSetName::SongOfDaysPast, ArtifactSlot::Flower),
+    ("昔时回映之音", ArtifactSetName::SongOfDaysPast, ArtifactSlot::Sand),
+    ("昔时应许之梦", ArtifactSetName::SongOfDaysPast, ArtifactSlot::Goblet),
+    ("慈爱的淑女帽", ArtifactSetName::NighttimeWhispersInTheEchoingWoods, ArtifactSlot::Head),
+    ("诚恳的蘸水笔", ArtifactSetName::NighttimeWhispersInTheEchoingWoods, ArtifactSlot::Feather),
+    ("无私的妆饰花", ArtifactSetName::NighttimeWhispersInTheEchoingWoods, ArtifactSlot::Flower),
+    ("忠实的砂时计", ArtifactSetName::NighttimeWhispersInTheEchoingWoods, ArtifactSlot::Sand),
+    ("慷慨的墨水瓶", ArtifactSetName::NighttimeWhispersInTheEchoingWoods, ArtifactSlot::Goblet),
+    ("异想零落的圆舞", ArtifactSetName::FragmentOfHarmonicWhimsy, ArtifactSlot::Head),
+    ("古海玄幽的夜想", ArtifactSetName::FragmentOfHarmonicWhimsy, ArtifactSlot::Feather),
+    ("谐律交响的前奏", ArtifactSetName::FragmentOfHarmonicWhimsy, ArtifactSlot::Flower),
+    ("命途轮转的谐谑", ArtifactSetName::FragmentOfHarmonicWhimsy, ArtifactSlot::Sand),
+    ("灵露倾洒的狂诗", ArtifactSetName::FragmentOfHarmonicWhimsy, ArtifactSlot::Goblet),
+    ("失冕的宝冠", ArtifactSetName::UnfinishedReverie, ArtifactSlot::Head),
+    ("褪光的翠尾", ArtifactSetName::UnfinishedReverie, ArtifactSlot::Feather),
+    ("暗结的明花", ArtifactSetName::UnfinishedReverie, ArtifactSlot::Flower),
+    ("举业的识刻", ArtifactSetName::UnfinishedReverie, ArtifactSlot::Sand),
+    ("筹谋的共樽", ArtifactSetName::UnfinishedReverie, ArtifactSlot::Goblet),
+    ("魔战士的羽面", ArtifactSetName::ScrollOfTheHeroOfCinderCity, ArtifactSlot::Head),
+    ("巡山客的信标", ArtifactSetName::ScrollOfTheHeroOfCinderCity, ArtifactSlot::Feather),
+    ("驯兽师的护符", ArtifactSetName::ScrollOfTheHeroOfCinderCity, ArtifactSlot::Flower),
+    ("秘术家的金盘", ArtifactSetName::ScrollOfTheHeroOfCinderCity, ArtifactSlot::Sand),
+    ("游学者的爪杯", ArtifactSetName::ScrollOfTheHeroOfCinderCity, ArtifactSlot::Goblet),
+    ("诸圣的礼冠", ArtifactSetName::ObsidianCodex, ArtifactSlot::Head),
+    ("灵髓的根脉", ArtifactSetName::ObsidianCodex, ArtifactSlot::Feather),
+    ("异种的期许", ArtifactSetName::ObsidianCodex, ArtifactSlot::Flower),
+    ("夜域的迷思", ArtifactSetName::ObsidianCodex, ArtifactSlot::Sand),
+    ("纷争的前宴", ArtifactSetName::ObsidianCodex, ArtifactSlot::Goblet),
+    ("深廊的遂失之冕", ArtifactSetName::LongNightsOath, ArtifactSlot::Head),
+    ("深廊的漫远之约", ArtifactSetName::LongNightsOath, ArtifactSlot::Feather),
+    ("深廊的回奏之歌", ArtifactSetName::LongNightsOath, ArtifactSlot::Flower),
+    ("深廊的湮落之刻", ArtifactSetName::LongNightsOath, ArtifactSlot::Sand),
+    ("深廊的饫赐之宴", ArtifactSetName::LongNightsOath, ArtifactSlot::Goblet),
+    ("被浸染的缨盔", ArtifactSetName::FinaleOfTheDeepGalleries, ArtifactSlot::Head),
+    ("夜鸣莺的尾羽", ArtifactSetName::FinaleOfTheDeepGalleries, ArtifactSlot::Feather),
+    ("执灯人的誓词", ArtifactSetName::FinaleOfTheDeepGalleries, ArtifactSlot::Flower),
+    ("不死者的哀铃", ArtifactSetName::FinaleOfTheDeepGalleries, ArtifactSlot::Sand),
+    ("未吹响的号角", ArtifactSetName::FinaleOfTheDeepGalleries, ArtifactSlot::Goblet),
+];
+
+lazy_static! {
+    static ref ARTIFACT_SET_NAME_MATCHER: FuzzyMatcher<'static, ArtifactSetName> = FuzzyMatcher::new(
+        ARTIFACT_NAMES_ZH_CN.iter().map(|(name, set_name, _)| (*name, set_name.clone()))
+    );
+    static ref ARTIFACT_SLOT_MATCHER: FuzzyMatcher<'static, ArtifactSlot> = FuzzyMatcher::new(
+        ARTIFACT_NAMES_ZH_CN.iter().map(|(name, _, slot)| (*name, slot.clone()))
+    );
+}
+
 impl ArtifactSetName {
-    #[rustfmt::skip]
     pub fn from_zh_cn(s: &str) -> Option<ArtifactSetName> {
-        match s {
-            "磐陀裂生之花" => Some(ArtifactSetName::ArchaicPetra),
-            "嵯峨群峰之翼" => Some(ArtifactSetName::ArchaicPetra),
-            "星罗圭壁之晷" => Some(ArtifactSetName::ArchaicPetra),
-            // "壁" is different
-            "星罗圭璧之晷" => Some(ArtifactSetName::ArchaicPetra),
-            "巉岩琢塑之樽" => Some(ArtifactSetName::ArchaicPetra),
-            "不动玄石之相" => Some(ArtifactSetName::ArchaicPetra),
-            "历经风雪的思念" => Some(ArtifactSetName::BlizzardStrayer),
-            "摧冰而行的执望" => Some(ArtifactSetName::BlizzardStrayer),
-            "冰雪故园的终期" => Some(ArtifactSetName::BlizzardStrayer),
-            "遍结寒霜的傲骨" => Some(ArtifactSetName::BlizzardStrayer),
-            "破冰踏雪的回音" => Some(ArtifactSetName::BlizzardStrayer),
-            "染血的铁之心" => Some(ArtifactSetName::BloodstainedChivalry),
-            "染血的黑之羽" => Some(ArtifactSetName::BloodstainedChivalry),
-            "骑士染血之时" => Some(ArtifactSetName::BloodstainedChivalry),
-            "染血骑士之杯" => Some(ArtifactSetName::BloodstainedChivalry),
-            "染血的铁假面" => Some(ArtifactSetName::BloodstainedChivalry),
-            "魔女的炎之花" => Some(ArtifactSetName::CrimsonWitch),
-            "魔女常燃之羽" => Some(ArtifactSetName::CrimsonWitch),
-            "魔女破灭之时" => Some(ArtifactSetName::CrimsonWitch),
-            "魔女的心之火" => Some(ArtifactSetName::CrimsonWitch),
-            "焦灼的魔女帽" => Some(ArtifactSetName::CrimsonWitch),
-            "角斗士的留恋" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的归宿" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的希冀" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的酣醉" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的凯旋" => Some(ArtifactSetName::GladiatorFinale),
-            "饰金胸花" => Some(ArtifactSetName::HeartOfDepth),
-            "追忆之风" => Some(ArtifactSetName::HeartOfDepth),
-            "坚铜罗盘" => Some(ArtifactSetName::HeartOfDepth),
-            "沉波之盏" => Some(ArtifactSetName::HeartOfDepth),
-            "酒渍船帽" => Some(ArtifactSetName::HeartOfDepth),
-            "渡火者的决绝" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的解脱" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的煎熬" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的醒悟" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的智慧" => Some(ArtifactSetName::LavaWalker),
-            "远方的少女之心" => Some(ArtifactSetName::MaidenBeloved),
-            "少女飘摇的思念" => Some(ArtifactSetName::MaidenBeloved),
-            "少女苦短的良辰" => Some(ArtifactSetName::MaidenBeloved),
-            "少女片刻的闲暇" => Some(ArtifactSetName::MaidenBeloved),
-            "少女易逝的芳颜" => Some(ArtifactSetName::MaidenBeloved),
-            "宗室之花" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室之翎" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室时计" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室银瓮" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室面具" => Some(ArtifactSetName::NoblesseOblige),
-            "夏祭之花" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭终末" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭之刻" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭水玉" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭之面" => Some(ArtifactSetName::RetracingBolide),
-            "平雷之心" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之羽" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之刻" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之器" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之冠" => Some(ArtifactSetName::ThunderSmoother),
-            "雷鸟的怜悯" => Some(ArtifactSetName::ThunderingFury),
-            "雷灾的孑遗" => Some(ArtifactSetName::ThunderingFury),
-            "雷霆的时计" => Some(ArtifactSetName::ThunderingFury),
-            "降雷的凶兆" => Some(ArtifactSetName::ThunderingFury),
-            "唤雷的头冠" => Some(ArtifactSetName::ThunderingFury),
-            "野花记忆的绿野" => Some(ArtifactSetName::ViridescentVenerer),
-            "猎人青翠的箭羽" => Some(ArtifactSetName::ViridescentVenerer),
-            "翠绿猎人的笃定" => Some(ArtifactSetName::ViridescentVenerer),
-            "翠绿猎人的容器" => Some(ArtifactSetName::ViridescentVenerer),
-            "翠绿的猎人之冠" => Some(ArtifactSetName::ViridescentVenerer),
-            "乐团的晨光" => Some(ArtifactSetName::WandererTroupe),
-            "琴师的箭羽" => Some(ArtifactSetName::WandererTroupe),
-            "终幕的时计" => Some(ArtifactSetName::WandererTroupe),
-            "终末的时计" => Some(ArtifactSetName::WandererTroupe),
-            "吟游者之壶" => Some(ArtifactSetName::WandererTroupe),
-            "指挥的礼帽" => Some(ArtifactSetName::WandererTroupe),
-            "战狂的蔷薇" => Some(ArtifactSetName::Berserker),
-            "战狂的翎羽" => Some(ArtifactSetName::Berserker),
-            "战狂的时计" => Some(ArtifactSetName::Berserker),
-            "战狂的骨杯" => Some(ArtifactSetName::Berserker),
-            "战狂的鬼面" => Some(ArtifactSetName::Berserker),
-            "勇士的勋章" => Some(ArtifactSetName::BraveHeart),
-            "勇士的期许" => Some(ArtifactSetName::BraveHeart),
-            "勇士的坚毅" => Some(ArtifactSetName::BraveHeart),
-            "勇士的壮行" => Some(ArtifactSetName::BraveHeart),
-            "勇士的冠冕" => Some(ArtifactSetName::BraveHeart),
-            "守护之花" => Some(ArtifactSetName::DefenderWill),
-            "守护徽印" => Some(ArtifactSetName::DefenderWill),
-            "守护座钟" => Some(ArtifactSetName::DefenderWill),
-            "守护之皿" => Some(ArtifactSetName::DefenderWill),
-            "守护束带" => Some(ArtifactSetName::DefenderWill),
-            "流放者之花" => Some(ArtifactSetName::Exile),
-            "流放者之羽" => Some(ArtifactSetName::Exile),
-            "流放者怀表" => Some(ArtifactSetName::Exile),
-            "流放者之杯" => Some(ArtifactSetName::Exile),
-            "流放者头冠" => Some(ArtifactSetName::Exile),
-            "赌徒的胸花" => Some(ArtifactSetName::Gambler),
-            "赌徒的羽饰" => Some(ArtifactSetName::Gambler),
-            "赌徒的怀表" => Some(ArtifactSetName::Gambler),
-            "赌徒的骰盅" => Some(ArtifactSetName::Gambler),
-            "赌徒的耳环" => Some(ArtifactSetName::Gambler),
-            "教官的胸花" => Some(ArtifactSetName::Instructor),
-            "教官的羽饰" => Some(ArtifactSetName::Instructor),
-            "教官的怀表" => Some(ArtifactSetName::Instructor),
-            "教官的茶杯" => Some(ArtifactSetName::Instructor),
-            "教官的帽子" => Some(ArtifactSetName::Instructor),
-            "武人的红花" => Some(ArtifactSetName::MartialArtist),
-            "武人的羽饰" => Some(ArtifactSetName::MartialArtist),
-            "武人的水漏" => Some(ArtifactSetName::MartialArtist),
-            "武人的酒杯" => Some(ArtifactSetName::MartialArtist),
-            "武人的头巾" => Some(ArtifactSetName::MartialArtist),
-            "祭水礼冠" => Some(ArtifactSetName::PrayersForDestiny),
-            "祭火礼冠" => Some(ArtifactSetName::PrayersForIllumination),
-            "祭雷礼冠" => Some(ArtifactSetName::PrayersForWisdom),
-            "祭冰礼冠" => Some(ArtifactSetName::PrayersToSpringtime),
-            "故人之心" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "归乡之羽" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "逐光之石" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "异国之盏" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "感别之冠" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "学士的书签" => Some(ArtifactSetName::Scholar),
-            "学士的羽笔" => Some(ArtifactSetName::Scholar),
-            "学士的时钟" => Some(ArtifactSetName::Scholar),
-            "学士的墨杯" => Some(ArtifactSetName::Scholar),
-            "学士的镜片" => Some(ArtifactSetName::Scholar),
-            "奇迹之花" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹之羽" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹之沙" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹之杯" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹耳坠" => Some(ArtifactSetName::TinyMiracle),
-            "冒险家之花" => Some(ArtifactSetName::Adventurer),
-            "冒险家尾羽" => Some(ArtifactSetName::Adventurer),
-            "冒险家怀表" => Some(ArtifactSetName::Adventurer),
-            "冒险家金杯" => Some(ArtifactSetName::Adventurer),
-            "冒险家头带" => Some(ArtifactSetName::Adventurer),
-            "幸运儿绿花" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿鹰羽" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿沙漏" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿之杯" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿银冠" => Some(ArtifactSetName::LuckyDog),
-            "游医的银莲" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的枭羽" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的怀钟" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的药壶" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的方巾" => Some(ArtifactSetName::TravelingDoctor),
-            "勋绩之花" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "昭武翎羽" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "金铜时晷" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "盟誓金爵" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "将帅兜鍪" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "无垢之花" => Some(ArtifactSetName::PaleFlame),
-            "贤医之羽" => Some(ArtifactSetName::PaleFlame),
-            "停摆之刻" => Some(ArtifactSetName::PaleFlame),
-            "超越之盏" => Some(ArtifactSetName::PaleFlame),
-            "嗤笑之面" => Some(ArtifactSetName::PaleFlame),
-            "明威之镡" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "切落之羽" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "雷云之笼" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "绯花之壶" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "华饰之兜" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "羁缠之花" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "思忆之矢" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "朝露之时" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "祈望之心" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "无常之面" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "荣花之期" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "华馆之羽" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "众生之谣" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "梦醒之瓢" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "形骸之笠" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "海染之花" => Some(ArtifactSetName::OceanHuedClam),
-            "渊宫之羽" => Some(ArtifactSetName::OceanHuedClam),
-            "离别之贝" => Some(ArtifactSetName::OceanHuedClam),
-            "真珠之笼" => Some(ArtifactSetName::OceanHuedClam),
-            "海祇之冠" => Some(ArtifactSetName::OceanHuedClam),
-            "生灵之华" | "阳辔之遗" | "潜光片羽" | "结契之刻" | "虺雷之姿" => Some(ArtifactSetName::VermillionHereafter),
-            "魂香之花" | "祝祀之凭" | "垂玉之叶" | "涌泉之盏" | "浮溯之珏" => Some(ArtifactSetName::EchoesOfAnOffering),
-            "迷宫的游人" | "翠蔓的智者" | "贤智的定期" | "迷误者之灯" | "月桂的宝冠" => Some(ArtifactSetName::DeepwoodMemories),
-            "梦中的铁花" | "裁断的翎羽" | "沉金的岁月" | "如蜜的终宴" | "沙王的投影" => Some(ArtifactSetName::GildedDreams),
-            "月女的华彩" | "谢落的筵席" | "凝结的时刻" | "守秘的魔瓶" | "紫晶的花冠" => Some(ArtifactSetName::FlowerOfParadiseLost),
-            "众王之都的开端" | "黄金邦国的结末" | "失落迷途的机芯" | "迷醉长梦的守护" | "流沙贵嗣的遗宝" => Some(ArtifactSetName::DesertPavilionChronicle),
-            "恶龙的单片镜" | "坏巫师的羽杖" | "旅途中的鲜花" | "水仙的时时刻刻" | "勇者们的茶会" => Some(ArtifactSetName::NymphsDream),
-            "灵光明烁之心" | "琦色灵彩之羽" | "灵光源起之蕊" | "久远花落之时" | "无边酣乐之筵" => Some(ArtifactSetName::VourukashasGlow),
-            "猎人的胸花" | "杰作的序曲" | "裁判的时刻" | "遗忘的容器" | "老兵的容颜" => Some(ArtifactSetName::MarechausseeHunter),
-            "黄金乐曲的变奏" | "黄金飞鸟的落羽" | "黄金时代的先声" | "黄金之夜的喧嚣" | "黄金剧团的奖赏" => Some(ArtifactSetName::GoldenTroupe),
-            "昔时传奏之诗" | "昔时浮想之思" | "昔时遗落之誓" | "昔时回映之音" | "昔时应许之梦" => Some(ArtifactSetName::SongOfDaysPast),
-            "慈爱的淑女帽" | "诚恳的蘸水笔" | "无私的妆饰花" | "忠实的砂时计" | "慷慨的墨水瓶" => Some(ArtifactSetName::NighttimeWhispersInTheEchoingWoods),
-            "异想零落的圆舞" | "古海玄幽的夜想" | "谐律交响的前奏" | "命途轮转的谐谑" | "灵露倾洒的狂诗" => Some(ArtifactSetName::FragmentOfHarmonicWhimsy),
-            "失冕的宝冠" | "褪光的翠尾" | "暗结的明花" | "举业的识刻" | "筹谋的共樽" => Some(ArtifactSetName::UnfinishedReverie),
-            "魔战士的羽面" | "巡山客的信标" | "驯兽师的护符" | "秘术家的金盘" | "游学者的爪杯" => Some(ArtifactSetName::ScrollOfTheHeroOfCinderCity),
-            "诸圣的礼冠" | "灵髓的根脉" | "异种的期许" | "夜域的迷思" | "纷争的前宴" => Some(ArtifactSetName::ObsidianCodex),
-            "深廊的遂失之冕" | "深廊的漫远之约" | "深廊的回奏之歌" | "深廊的湮落之刻" | "深廊的饫赐之宴" => Some(ArtifactSetName::LongNightsOath),
-            "被浸染的缨盔" | "夜鸣莺的尾羽" | "执灯人的誓词" | "不死者的哀铃" | "未吹响的号角" => Some(ArtifactSetName::FinaleOfTheDeepGalleries),
-            _ => None,
+        match ARTIFACT_SET_NAME_MATCHER.find(s) {
+            FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                warn!("artifact name `{}` is ambiguous: {:?}", s, candidates);
+                None
+            },
+            result => result.value(),
         }
     }
 }
 
 impl ArtifactSlot {
     pub fn from_zh_cn(s: &str) -> Option<ArtifactSlot> {
-        match s {
-            "磐陀裂生之花" => Some(ArtifactSlot::Flower),
-            "嵯峨群峰之翼" => Some(ArtifactSlot::Feather),
-            "星罗圭壁之晷" => Some(ArtifactSlot::Sand),
-            "星罗圭璧之晷" => Some(ArtifactSlot::Sand),
-            "巉岩琢塑之樽" => Some(ArtifactSlot::Goblet),
-            "不动玄石之相" => Some(ArtifactSlot::Head),
-            "历经风雪的思念" => Some(ArtifactSlot::Flower),
-            "摧冰而行的执望" => Some(ArtifactSlot::Feather),
-            "冰雪故园的终期" => Some(ArtifactSlot::Sand),
-            "遍结寒霜的傲骨" => Some(ArtifactSlot::Goblet),
-            "破冰踏雪的回音" => Some(ArtifactSlot::Head),
-            "染血的铁之心" => Some(ArtifactSlot::Flower),
-            "染血的黑之羽" => Some(ArtifactSlot::Feather),
-            "骑士染血之时" => Some(ArtifactSlot::Sand),
-            "染血骑士之杯" => Some(ArtifactSlot::Goblet),
-            "染血的铁假面" => Some(ArtifactSlot::Head),
-            "魔女的炎之花" => Some(ArtifactSlot::Flower),
-            "魔女常燃之羽" => Some(ArtifactSlot::Feather),
-            "魔女破灭之时" => Some(ArtifactSlot::Sand),
-            "魔女的心之火" => Some(ArtifactSlot::Goblet),
-            "焦灼的魔女帽" => Some(ArtifactSlot::Head),
-            "角斗士的留恋" => Some(ArtifactSlot::Flower),
-            "角斗士的归宿" => Some(ArtifactSlot::Feather),
-            "角斗士的希冀" => Some(ArtifactSlot::Sand),
-            "角斗士的酣醉" => Some(ArtifactSlot::Goblet),
-            "角斗士的凯旋" => Some(ArtifactSlot::Head),
-            "饰金胸花" => Some(ArtifactSlot::Flower),
-            "追忆之风" => Some(ArtifactSlot::Feather),
-            "坚铜罗盘" => Some(ArtifactSlot::Sand),
-            "沉波之盏" => Some(ArtifactSlot::Goblet),
-            "酒渍船帽" => Some(ArtifactSlot::Head),
-            "渡火者的决绝" => Some(ArtifactSlot::Flower),
-            "渡火者的解脱" => Some(ArtifactSlot::Feather),
-            "渡火者的煎熬" => Some(ArtifactSlot::Sand),
-            "渡火者的醒悟" => Some(ArtifactSlot::Goblet),
-            "渡火者的智慧" => Some(ArtifactSlot::Head),
-            "远方的少女之心" => Some(ArtifactSlot::Flower),
-            "少女飘摇的思念" => Some(ArtifactSlot::Feather),
-            "少女苦短的良辰" => Some(ArtifactSlot::Sand),
-            "少女片刻的闲暇" => Some(ArtifactSlot::Goblet),
-            "少女易逝的芳颜" => Some(ArtifactSlot::Head),
-            "宗室之花" => Some(ArtifactSlot::Flower),
-            "宗室之翎" => Some(ArtifactSlot::Feather),
-            "宗室时计" => Some(ArtifactSlot::Sand),
-            "宗室银瓮" => Some(ArtifactSlot::Goblet),
-            "宗室面具" => Some(ArtifactSlot::Head),
-            "夏祭之花" => Some(ArtifactSlot::Flower),
-            "夏祭终末" => Some(ArtifactSlot::Feather),
-            "夏祭之刻" => Some(ArtifactSlot::Sand),
-            "夏祭水玉" => Some(ArtifactSlot::Goblet),
-            "夏祭之面" => Some(ArtifactSlot::Head),
-            "平雷之心" => Some(ArtifactSlot::Flower),
-            "平雷之羽" => Some(ArtifactSlot::Feather),
-            "平雷之刻" => Some(ArtifactSlot::Sand),
-            "平雷之器" => Some(ArtifactSlot::Goblet),
-            "平雷之冠" => Some(ArtifactSlot::Head),
-            "雷鸟的怜悯" => Some(ArtifactSlot::Flower),
-            "雷灾的孑遗" => Some(ArtifactSlot::Feather),
-            "雷霆的时计" => Some(ArtifactSlot::Sand),
-            "降雷的凶兆" => Some(ArtifactSlot::Goblet),
-            "唤雷的头冠" => Some(ArtifactSlot::Head),
-            "野花记忆的绿野" => Some(ArtifactSlot::Flower),
-            "猎人青翠的箭羽" => Some(ArtifactSlot::Feather),
-            "翠绿猎人的笃定" => Some(ArtifactSlot::Sand),
-            "翠绿猎人的容器" => Some(ArtifactSlot::Goblet),
-            "翠绿的猎人之冠" => Some(ArtifactSlot::Head),
-            "乐团的晨光" => Some(ArtifactSlot::Flower),
-            "琴师的箭羽" => Some(ArtifactSlot::Feather),
-            "终幕的时计" => Some(ArtifactSlot::Sand),
-            "终末的时计" => Some(ArtifactSlot::Sand),
-            "吟游者之壶" => Some(ArtifactSlot::Goblet),
-            "指挥的礼帽" => Some(ArtifactSlot::Head),
-            "战狂的蔷薇" => Some(ArtifactSlot::Flower),
-            "战狂的翎羽" => Some(ArtifactSlot::Feather),
-            "战狂的时计" => Some(ArtifactSlot::Sand),
-            "战狂的骨杯" => Some(ArtifactSlot::Goblet),
-            "战狂的鬼面" => Some(ArtifactSlot::Head),
-            "勇士的勋章" => Some(ArtifactSlot::Flower),
-            "勇士的期许" => Some(ArtifactSlot::Feather),
-            "勇士的坚毅" => Some(ArtifactSlot::Sand),
-            "勇士的壮行" => Some(ArtifactSlot::Goblet),
-            "勇士的冠冕" => Some(ArtifactSlot::Head),
-            "守护之花" => Some(ArtifactSlot::Flower),
-            "守护徽印" => Some(ArtifactSlot::Feather),
-            "守护座钟" => Some(ArtifactSlot::Sand),
-            "守护之皿" => Some(ArtifactSlot::Goblet),
-            "守护束带" => Some(ArtifactSlot::Head),
-            "流放者之花" => Some(ArtifactSlot::Flower),
-            "流放者之羽" => Some(ArtifactSlot::Feather),
-            "流放者怀表" => Some(ArtifactSlot::Sand),
-            "流放者之杯" => Some(ArtifactSlot::Goblet),
-            "流放者头冠" => Some(ArtifactSlot::Head),
-            "赌徒的胸花" => Some(ArtifactSlot::Flower),
-            "赌徒的羽饰" => Some(ArtifactSlot::Feather),
-            "赌徒的怀表" => Some(ArtifactSlot::Sand),
-            "赌徒的骰盅" => Some(ArtifactSlot::Goblet),
-            "赌徒的耳环" => Some(ArtifactSlot::Head),
-            "教官的胸花" => Some(ArtifactSlot::Flower),
-            "教官的羽饰" => Some(ArtifactSlot::Feather),
-            "教官的怀表" => Some(ArtifactSlot::Sand),
-            "教官的茶杯" => Some(ArtifactSlot::Goblet),
-            "教官的帽子" => Some(ArtifactSlot::Head),
-            "武人的红花" => Some(ArtifactSlot::Flower),
-            "武人的羽饰" => Some(ArtifactSlot::Feather),
-            "武人的水漏" => Some(ArtifactSlot::Sand),
-            "武人的酒杯" => Some(ArtifactSlot::Goblet),
-            "武人的头巾" => Some(ArtifactSlot::Head),
-            "祭水礼冠" => Some(ArtifactSlot::Head),
-            "祭火礼冠" => Some(ArtifactSlot::Head),
-            "祭雷礼冠" => Some(ArtifactSlot::Head),
-            "祭冰礼冠" => Some(ArtifactSlot::Head),
-            "故人之心" => Some(ArtifactSlot::Flower),
-            "归乡之羽" => Some(ArtifactSlot::Feather),
-            "逐光之石" => Some(ArtifactSlot::Sand),
-            "异国之盏" => Some(ArtifactSlot::Goblet),
-            "感别之冠" => Some(ArtifactSlot::Head),
-            "学士的书签" => Some(ArtifactSlot::Flower),
-            "学士的羽笔" => Some(ArtifactSlot::Feather),
-            "学士的时钟" => Some(ArtifactSlot::Sand),
-            "学士的墨杯" => Some(ArtifactSlot::Goblet),
-            "学士的镜片" => Some(ArtifactSlot::Head),
-            "奇迹之花" => Some(ArtifactSlot::Flower),
-            "奇迹之羽" => Some(ArtifactSlot::Feather),
-            "奇迹之沙" => Some(ArtifactSlot::Sand),
-            "奇迹之杯" => Some(ArtifactSlot::Goblet),
-            "奇迹耳坠" => Some(ArtifactSlot::Head),
-            "冒险家之花" => Some(ArtifactSlot::Flower),
-            "冒险家尾羽" => Some(ArtifactSlot::Feather),
-            "冒险家怀表" => Some(ArtifactSlot::Sand),
-            "冒险家金杯" => Some(ArtifactSlot::Goblet),
-            "冒险家头带" => Some(ArtifactSlot::Head),
-            "幸运儿绿花" => Some(ArtifactSlot::Flower),
-            "幸运儿鹰羽" => Some(ArtifactSlot::Feather),
-            "幸运儿沙漏" => Some(ArtifactSlot::Sand),
-            "幸运儿之杯" => Some(ArtifactSlot::Goblet),
-            "幸运儿银冠" => Some(ArtifactSlot::Head),
-            "游医的银莲" => Some(ArtifactSlot::Flower),
-            "游医的枭羽" => Some(ArtifactSlot::Feather),
-            "游医的怀钟" => Some(ArtifactSlot::Sand),
-            "游医的药壶" => Some(ArtifactSlot::Goblet),
-            "游医的方巾" => Some(ArtifactSlot::Head),
-            "勋绩之花" => Some(ArtifactSlot::Flower),
-            "昭武翎羽" => Some(ArtifactSlot::Feather),
-            "金铜时晷" => Some(ArtifactSlot::Sand),
-            "盟誓金爵" => Some(ArtifactSlot::Goblet),
-            "将帅兜鍪" => Some(ArtifactSlot::Head),
-            "无垢之花" => Some(ArtifactSlot::Flower),
-            "贤医之羽" => Some(ArtifactSlot::Feather),
-            "停摆之刻" => Some(ArtifactSlot::Sand),
-            "超越之盏" => Some(ArtifactSlot::Goblet),
-            "嗤笑之面" => Some(ArtifactSlot::Head),
-            "明威之镡" => Some(ArtifactSlot::Flower),
-            "切落之羽" => Some(ArtifactSlot::Feather),
-            "雷云之笼" => Some(ArtifactSlot::Sand),
-            "绯花之壶" => Some(ArtifactSlot::Goblet),
-            "华饰之兜" => Some(ArtifactSlot::Head),
-            "羁缠之花" => Some(ArtifactSlot::Flower),
-            "思忆之矢" => Some(ArtifactSlot::Feather),
-            "朝露之时" => Some(ArtifactSlot::Sand),
-            "祈望之心" => Some(ArtifactSlot::Goblet),
-            "无常之面" => Some(ArtifactSlot::Head),
-            "荣花之期" => Some(ArtifactSlot::Flower),
-            "华馆之羽" => Some(ArtifactSlot::Feather),
-            "众生之谣" => Some(ArtifactSlot::Sand),
-            "梦醒之瓢" => Some(ArtifactSlot::Goblet),
-            "形骸之笠" => Some(ArtifactSlot::Head),
-            "海染之花" => Some(ArtifactSlot::Flower),
-            "渊宫之羽" => Some(ArtifactSlot::Feather),
-            "离别之贝" => Some(ArtifactSlot::Sand),
-            "真珠之笼" => Some(ArtifactSlot::Goblet),
-            "海祇之冠" => Some(ArtifactSlot::Head),
-            "生灵之华" => Some(ArtifactSlot::Flower),
-            "阳辔之遗" => Some(ArtifactSlot::Sand),
-            "潜光片羽" => Some(ArtifactSlot::Feather),
-            "结契之刻" => Some(ArtifactSlot::Goblet),
-            "虺雷之姿" => Some(ArtifactSlot::Head),
-            "魂香之花" => Some(ArtifactSlot::Flower),
-            "祝祀之凭" => Some(ArtifactSlot::Sand),
-            "垂玉之叶" => Some(ArtifactSlot::Feather),
-            "涌泉之盏" => Some(ArtifactSlot::Goblet),
-            "浮溯之珏" => Some(ArtifactSlot::Head),
-            "迷宫的游人" => Some(ArtifactSlot::Flower),
-            "翠蔓的智者" => Some(ArtifactSlot::Feather),
-            "贤智的定期" => Some(ArtifactSlot::Sand),
-            "迷误者之灯" => Some(ArtifactSlot::Goblet),
-            "月桂的宝冠" => Some(ArtifactSlot::Head),
-            "梦中的铁花" => Some(ArtifactSlot::Flower),
-            "裁断的翎羽" => Some(ArtifactSlot::Feather),
-            "沉金的岁月" => Some(ArtifactSlot::Sand),
-            "如蜜的终宴" => Some(ArtifactSlot::Goblet),
-            "沙王的投影" => Some(ArtifactSlot::Head),
-            "月女的华彩" => Some(ArtifactSlot::Flower),
-            "谢落的筵席" => Some(ArtifactSlot::Feather),
-            "凝结的时刻" => Some(ArtifactSlot::Sand),
-            "守秘的魔瓶" => Some(ArtifactSlot::Goblet),
-            "紫晶的花冠" => Some(ArtifactSlot::Head),
-            "众王之都的开端" => Some(ArtifactSlot::Flower),
-            "黄金邦国的结末" => Some(ArtifactSlot::Feather),
-            "失落迷途的机芯" => Some(ArtifactSlot::Sand),
-            "迷醉长梦的守护" => Some(ArtifactSlot::Goblet),
-            "流沙贵嗣的遗宝" => Some(ArtifactSlot::Head),
-            "旅途中的鲜花" => Some(ArtifactSlot::Flower),
-            "坏巫师的羽杖" => Some(ArtifactSlot::Feather),
-            "水仙的时时刻刻" => Some(ArtifactSlot::Sand),
-            "勇者们的茶会" => Some(ArtifactSlot::Goblet),
-            "恶龙的单片镜" => Some(ArtifactSlot::Head),
-            "灵光源起之蕊" => Some(ArtifactSlot::Flower),
-            "琦色灵彩之羽" => Some(ArtifactSlot::Feather),
-            "久远花落之时" => Some(ArtifactSlot::Sand),
-            "无边酣乐之筵" => Some(ArtifactSlot::Goblet),
-            "灵光明烁之心" => Some(ArtifactSlot::Head),
-            "猎人的胸花" => Some(ArtifactSlot::Flower),
-            "杰作的序曲" => Some(ArtifactSlot::Feather),
-            "裁判的时刻" => Some(ArtifactSlot::Sand),
-            "遗忘的容器" => Some(ArtifactSlot::Goblet),
-            "老兵的容颜" => Some(ArtifactSlot::Head),
-            "黄金乐曲的变奏" => Some(ArtifactSlot::Flower),
-            "黄金飞鸟的落羽" => Some(ArtifactSlot::Feather),
-            "黄金时代的先声" => Some(ArtifactSlot::Sand),
-            "黄金之夜的喧嚣" => Some(ArtifactSlot::Goblet),
-            "黄金剧团的奖赏" => Some(ArtifactSlot::Head),
-            "昔时遗落之誓" => Some(ArtifactSlot::Flower),
-            "昔时浮想之思" => Some(ArtifactSlot::Feather),
-            "昔时回映之音" => Some(ArtifactSlot::Sand),
-            "昔时应许之梦" => Some(ArtifactSlot::Goblet),
-            "昔时传奏之诗" => Some(ArtifactSlot::Head),
-            "无私的妆饰花" => Some(ArtifactSlot::Flower),
-            "诚恳的蘸水笔" => Some(ArtifactSlot::Feather),
-            "忠实的砂时计" => Some(ArtifactSlot::Sand),
-            "慷慨的墨水瓶" => Some(ArtifactSlot::Goblet),
-            "慈爱的淑女帽" => Some(ArtifactSlot::Head),
-            "异想零落的圆舞" => Some(ArtifactSlot::Head),
-            "古海玄幽的夜想" => Some(ArtifactSlot::Feather),
-            "谐律交响的前奏" => Some(ArtifactSlot::Flower),
-            "命途轮转的谐谑" => Some(ArtifactSlot::Sand),
-            "灵露倾洒的狂诗" => Some(ArtifactSlot::Goblet),
-            "失冕的宝冠" => Some(ArtifactSlot::Head),
-            "褪光的翠尾" => Some(ArtifactSlot::Feather),
-            "暗结的明花" => Some(ArtifactSlot::Flower),
-            "举业的识刻" => Some(ArtifactSlot::Sand),
-            "筹谋的共樽" => Some(ArtifactSlot::Goblet),
-            "魔战士的羽面" => Some(ArtifactSlot::Head),
-            "巡山客的信标" => Some(ArtifactSlot::Feather),
-            "驯兽师的护符" => Some(ArtifactSlot::Flower),
-            "秘术家的金盘" => Some(ArtifactSlot::Sand),
-            "游学者的爪杯" => Some(ArtifactSlot::Goblet),
-            "诸圣的礼冠" => Some(ArtifactSlot::Head),
-            "灵髓的根脉" => Some(ArtifactSlot::Feather),
-            "异种的期许" => Some(ArtifactSlot::Flower),
-            "夜域的迷思" => Some(ArtifactSlot::Sand),
-            "纷争的前宴" => Some(ArtifactSlot::Goblet),
-            "被浸染的缨盔" => Some(ArtifactSlot::Head),
-            "夜鸣莺的尾羽" => Some(ArtifactSlot::Feather),
-            "执灯人的誓词" => Some(ArtifactSlot::Flower),
-            "不死者的哀铃" => Some(ArtifactSlot::Sand),
-            "未吹响的号角" => Some(ArtifactSlot::Goblet),
-            "深廊的遂失之冕" => Some(ArtifactSlot::Head),
-            "深廊的漫远之约" => Some(ArtifactSlot::Feather),
-            "深廊的回奏之歌" => Some(ArtifactSlot::Flower),
-            "深廊的湮落之刻" => Some(ArtifactSlot::Sand),
-            "深廊的饫赐之宴" => Some(ArtifactSlot::Goblet),
-            _ => None,
+        match ARTIFACT_SLOT_MATCHER.find(s) {
+            FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                warn!("artifact name `{}` is ambiguous: {:?}", s, candidates);
+                None
+            },
+            result => result.value(),
         }
     }
 }
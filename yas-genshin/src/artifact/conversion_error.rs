@@ -0,0 +1,60 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// The field of a scan result that fails to convert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArtifactField {
+    Name,
+    MainStat,
+    /// The main stat value does not fit the level, and neither can be corrected
+    MainStatLevel,
+}
+
+impl ArtifactField {
+    pub fn to_zh_cn(&self) -> &'static str {
+        match *self {
+            ArtifactField::Name => "名称",
+            ArtifactField::MainStat => "主词条",
+            ArtifactField::MainStatLevel => "主词条与等级",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenshinArtifactConversionError {
+    /// Index of the item in the scan, `None` if it is not known
+    pub index: Option<usize>,
+    pub field: ArtifactField,
+    /// The OCR text of the field
+    pub raw: String,
+}
+
+impl GenshinArtifactConversionError {
+    pub fn new(field: ArtifactField, raw: impl Into<String>) -> Self {
+        GenshinArtifactConversionError {
+            index: None,
+            field,
+            raw: raw.into(),
+        }
+    }
+
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+}
+
+impl fmt::Display for GenshinArtifactConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "第 {} 件圣遗物", index + 1)?,
+            None => write!(f, "圣遗物")?,
+        }
+        write!(f, "的{}无法识别：`{}`", self.field.to_zh_cn(), self.raw)
+    }
+}
+
+impl std::error::Error for GenshinArtifactConversionError {}
@@ -0,0 +1,110 @@
+use log::warn;
+
+use yas::common::main_stat_check::MainStatCheck;
+use yas::common::sub_stat_roll::RollItem;
+
+use crate::artifact::{ArtifactStatName, GenshinArtifact};
+
+// https://genshin-impact.fandom.com/wiki/Artifact/Scaling
+// values are in displayed unit, indexed by level
+#[rustfmt::skip]
+mod curves {
+    pub const STAR5_HP: [f64; 21] = [717.0, 920.0, 1123.0, 1326.0, 1530.0, 1733.0, 1936.0, 2139.0, 2342.0, 2545.0, 2749.0, 2952.0, 3155.0, 3358.0, 3561.0, 3764.0, 3967.0, 4171.0, 4374.0, 4577.0, 4780.0];
+    pub const STAR5_ATK: [f64; 21] = [47.0, 60.0, 73.0, 86.0, 100.0, 113.0, 126.0, 139.0, 152.0, 166.0, 179.0, 192.0, 205.0, 219.0, 232.0, 245.0, 258.0, 272.0, 285.0, 298.0, 311.0];
+    pub const STAR5_HP_ATK_ELEMENTAL: [f64; 21] = [7.0, 9.0, 11.0, 12.9, 14.9, 16.9, 18.9, 20.9, 22.8, 24.8, 26.8, 28.8, 30.8, 32.8, 34.7, 36.7, 38.7, 40.7, 42.7, 44.6, 46.6];
+    pub const STAR5_DEF_PHYSICAL: [f64; 21] = [8.7, 11.2, 13.7, 16.2, 18.6, 21.1, 23.6, 26.1, 28.6, 31.0, 33.5, 36.0, 38.5, 40.9, 43.4, 45.9, 48.4, 50.8, 53.3, 55.8, 58.3];
+    pub const STAR5_EM: [f64; 21] = [28.0, 36.0, 44.0, 52.0, 60.0, 68.0, 76.0, 84.0, 91.0, 99.0, 107.0, 115.0, 123.0, 131.0, 139.0, 147.0, 155.0, 163.0, 171.0, 179.0, 187.0];
+    pub const STAR5_ER: [f64; 21] = [7.8, 10.0, 12.2, 14.4, 16.6, 18.8, 21.0, 23.2, 25.4, 27.6, 29.8, 32.0, 34.2, 36.4, 38.6, 40.8, 43.0, 45.2, 47.4, 49.6, 51.8];
+    pub const STAR5_CRIT_RATE: [f64; 21] = [4.7, 6.0, 7.4, 8.7, 10.0, 11.4, 12.7, 14.0, 15.4, 16.7, 18.0, 19.3, 20.7, 22.0, 23.3, 24.7, 26.0, 27.3, 28.7, 30.0, 31.1];
+    pub const STAR5_CRIT_DMG: [f64; 21] = [9.3, 12.0, 14.7, 17.4, 20.0, 22.7, 25.4, 28.0, 30.7, 33.4, 36.1, 38.7, 41.4, 44.1, 46.7, 49.4, 52.1, 54.7, 57.4, 60.1, 62.2];
+    pub const STAR5_HEALING: [f64; 21] = [5.4, 6.9, 8.4, 10.0, 11.5, 13.0, 14.5, 16.1, 17.6, 19.1, 20.7, 22.2, 23.7, 25.2, 26.8, 28.3, 29.8, 31.4, 32.9, 34.4, 35.9];
+
+    pub const STAR4_HP: [f64; 17] = [645.0, 828.0, 1011.0, 1194.0, 1377.0, 1559.0, 1742.0, 1925.0, 2108.0, 2291.0, 2474.0, 2657.0, 2839.0, 3022.0, 3205.0, 3388.0, 3571.0];
+    pub const STAR4_ATK: [f64; 17] = [42.0, 54.0, 66.0, 78.0, 90.0, 102.0, 113.0, 125.0, 137.0, 149.0, 161.0, 173.0, 185.0, 197.0, 209.0, 221.0, 232.0];
+    pub const STAR4_HP_ATK_ELEMENTAL: [f64; 17] = [6.3, 8.1, 9.9, 11.6, 13.4, 15.2, 17.0, 18.8, 20.6, 22.3, 24.1, 25.9, 27.7, 29.5, 31.3, 33.0, 34.8];
+    pub const STAR4_DEF_PHYSICAL: [f64; 17] = [7.9, 10.1, 12.3, 14.6, 16.8, 19.0, 21.2, 23.5, 25.7, 27.9, 30.2, 32.4, 34.6, 36.8, 39.1, 41.3, 43.5];
+    pub const STAR4_EM: [f64; 17] = [25.0, 32.0, 40.0, 47.0, 54.0, 61.0, 68.0, 75.0, 82.0, 89.0, 97.0, 104.0, 111.0, 118.0, 125.0, 132.0, 139.0];
+    pub const STAR4_ER: [f64; 17] = [7.0, 9.0, 11.0, 12.9, 14.9, 16.9, 18.9, 20.9, 22.8, 24.8, 26.8, 28.8, 30.8, 32.8, 34.7, 36.7, 38.7];
+    pub const STAR4_CRIT_RATE: [f64; 17] = [4.2, 5.4, 6.6, 7.8, 9.0, 10.1, 11.3, 12.5, 13.7, 14.9, 16.1, 17.3, 18.5, 19.7, 20.8, 22.0, 23.2];
+    pub const STAR4_CRIT_DMG: [f64; 17] = [8.4, 10.8, 13.2, 15.6, 17.9, 20.3, 22.7, 25.1, 27.5, 29.8, 32.2, 34.6, 37.0, 39.4, 41.7, 44.1, 46.5];
+    pub const STAR4_HEALING: [f64; 17] = [4.8, 6.2, 7.6, 9.0, 10.3, 11.7, 13.1, 14.4, 15.8, 17.2, 18.6, 19.9, 21.3, 22.7, 24.0, 25.4, 26.8];
+}
+
+impl ArtifactStatName {
+    /// The main stat value of every level, in displayed unit.
+    /// Only 4 and 5 star artifacts have a curve
+    pub fn main_stat_curve(&self, star: i32) -> Option<&'static [f64]> {
+        use ArtifactStatName::*;
+
+        let curve: &'static [f64] = match (self, star) {
+            (Hp, 5) => &curves::STAR5_HP,
+            (Atk, 5) => &curves::STAR5_ATK,
+            (HpPercentage | AtkPercentage, 5) => &curves::STAR5_HP_ATK_ELEMENTAL,
+            (DefPercentage | PhysicalBonus, 5) => &curves::STAR5_DEF_PHYSICAL,
+            (ElementalMastery, 5) => &curves::STAR5_EM,
+            (Recharge, 5) => &curves::STAR5_ER,
+            (Critical, 5) => &curves::STAR5_CRIT_RATE,
+            (CriticalDamage, 5) => &curves::STAR5_CRIT_DMG,
+            (HealingBonus, 5) => &curves::STAR5_HEALING,
+            (ElectroBonus | PyroBonus | HydroBonus | CryoBonus | AnemoBonus | GeoBonus | DendroBonus, 5) => &curves::STAR5_HP_ATK_ELEMENTAL,
+
+            (Hp, 4) => &curves::STAR4_HP,
+            (Atk, 4) => &curves::STAR4_ATK,
+            (HpPercentage | AtkPercentage, 4) => &curves::STAR4_HP_ATK_ELEMENTAL,
+            (DefPercentage | PhysicalBonus, 4) => &curves::STAR4_DEF_PHYSICAL,
+            (ElementalMastery, 4) => &curves::STAR4_EM,
+            (Recharge, 4) => &curves::STAR4_ER,
+            (Critical, 4) => &curves::STAR4_CRIT_RATE,
+            (CriticalDamage, 4) => &curves::STAR4_CRIT_DMG,
+            (HealingBonus, 4) => &curves::STAR4_HEALING,
+            (ElectroBonus | PyroBonus | HydroBonus | CryoBonus | AnemoBonus | GeoBonus | DendroBonus, 4) => &curves::STAR4_HP_ATK_ELEMENTAL,
+
+            _ => return None,
+        };
+        Some(curve)
+    }
+
+    /// The main stat value at `level`, in the unit stored in `ArtifactStat`
+    pub fn main_stat_value(&self, star: i32, level: i32) -> Option<f64> {
+        let value = *self.main_stat_curve(star)?.get(usize::try_from(level).ok()?)?;
+        Some(if self.is_percentage() { value / 100.0 } else { value })
+    }
+}
+
+impl GenshinArtifact {
+    /// Cross-check the main stat value and the level, and correct whichever is misread.
+    ///
+    /// Sub stat rolls are checked again if the level changes
+    pub fn check_main_stat(&mut self) -> MainStatCheck {
+        let curve = match self.main_stat.name.main_stat_curve(self.star) {
+            Some(v) => v,
+            None => return MainStatCheck::Unchecked,
+        };
+
+        let is_percentage = self.main_stat.name.is_percentage();
+        let (value, tolerance) = if is_percentage {
+            (self.main_stat.value * 100.0, 0.15)
+        } else {
+            (self.main_stat.value, 1.5)
+        };
+
+        let check = MainStatCheck::check(curve, value, self.level, tolerance);
+        match &check {
+            MainStatCheck::LevelCorrected { read, inferred } => {
+                warn!("{} 主词条 {}+{} 与等级 {} 不符，修正等级为 {}", self.set_name, self.main_stat.name, value, read, inferred);
+                self.level = *inferred;
+                self.check_sub_stat_rolls();
+            },
+            MainStatCheck::ValueCorrected { read, expected } => {
+                warn!("{} 主词条 {}+{} 与等级 {} 不符，修正为 {}", self.set_name, self.main_stat.name, read, self.level, expected);
+                self.main_stat.value = if is_percentage { expected / 100.0 } else { *expected };
+            },
+            MainStatCheck::Rejected => {
+                warn!("{} 主词条 {}+{} 与等级 {} 均不可信", self.set_name, self.main_stat.name, value, self.level);
+            },
+            _ => {},
+        }
+
+        check
+    }
+}
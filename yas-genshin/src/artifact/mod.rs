@@ -3,6 +3,12 @@ pub use artifact::ArtifactSlot;
 pub use artifact::ArtifactStat;
 pub use artifact::ArtifactStatName;
 pub use artifact::GenshinArtifact;
+pub use conversion_error::{ArtifactField, GenshinArtifactConversionError};
 
 mod artifact;
+mod conversion_error;
+mod inventory_diff;
+mod main_stat_curve;
+mod template_item;
+mod sub_stat_roll;
 mod zh_cn;
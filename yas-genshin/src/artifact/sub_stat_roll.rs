@@ -0,0 +1,131 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use yas::common::sub_stat_roll::{RollItem, RollStat, RollTable};
+
+use crate::artifact::{ArtifactStat, ArtifactStatName, GenshinArtifact};
+
+impl ArtifactStatName {
+    pub fn is_percentage(&self) -> bool {
+        !matches!(
+            self,
+            ArtifactStatName::Hp
+                | ArtifactStatName::Atk
+                | ArtifactStatName::Def
+                | ArtifactStatName::ElementalMastery
+        )
+    }
+
+    // https://genshin-impact.fandom.com/wiki/Artifact/Distribution#Sub_Stats
+    /// The values a single roll of this sub stat can take, in displayed unit.
+    /// Returns `None` if it cannot be a sub stat of an artifact of this star
+    #[rustfmt::skip]
+    pub fn roll_table(&self, star: i32) -> Option<RollTable> {
+        // tier values are rounded to 2 decimals
+        const fn t(tiers: &'static [f64]) -> Option<RollTable> {
+            Some(RollTable::new(tiers, 0.005))
+        }
+
+        match (self, star) {
+            (ArtifactStatName::Hp, 5) => t(&[209.13, 239.00, 268.88, 298.75]),
+            (ArtifactStatName::Atk, 5) => t(&[13.62, 15.56, 17.51, 19.45]),
+            (ArtifactStatName::Def, 5) => t(&[16.20, 18.52, 20.83, 23.15]),
+            (ArtifactStatName::HpPercentage, 5) => t(&[4.08, 4.66, 5.25, 5.83]),
+            (ArtifactStatName::AtkPercentage, 5) => t(&[4.08, 4.66, 5.25, 5.83]),
+            (ArtifactStatName::DefPercentage, 5) => t(&[5.10, 5.83, 6.56, 7.29]),
+            (ArtifactStatName::ElementalMastery, 5) => t(&[16.32, 18.65, 20.98, 23.31]),
+            (ArtifactStatName::Recharge, 5) => t(&[4.53, 5.18, 5.83, 6.48]),
+            (ArtifactStatName::Critical, 5) => t(&[2.72, 3.11, 3.50, 3.89]),
+            (ArtifactStatName::CriticalDamage, 5) => t(&[5.44, 6.22, 6.99, 7.77]),
+
+            (ArtifactStatName::Hp, 4) => t(&[167.30, 191.20, 215.10, 239.00]),
+            (ArtifactStatName::Atk, 4) => t(&[10.89, 12.45, 14.00, 15.56]),
+            (ArtifactStatName::Def, 4) => t(&[12.96, 14.82, 16.67, 18.52]),
+            (ArtifactStatName::HpPercentage, 4) => t(&[3.26, 3.73, 4.20, 4.66]),
+            (ArtifactStatName::AtkPercentage, 4) => t(&[3.26, 3.73, 4.20, 4.66]),
+            (ArtifactStatName::DefPercentage, 4) => t(&[4.08, 4.66, 5.25, 5.83]),
+            (ArtifactStatName::ElementalMastery, 4) => t(&[13.06, 14.92, 16.79, 18.65]),
+            (ArtifactStatName::Recharge, 4) => t(&[3.63, 4.14, 4.66, 5.18]),
+            (ArtifactStatName::Critical, 4) => t(&[2.18, 2.49, 2.80, 3.11]),
+            (ArtifactStatName::CriticalDamage, 4) => t(&[4.35, 4.97, 5.60, 6.22]),
+
+            (ArtifactStatName::Hp, 3) => t(&[100.38, 114.72, 129.06, 143.40]),
+            (ArtifactStatName::Atk, 3) => t(&[6.54, 7.47, 8.40, 9.34]),
+            (ArtifactStatName::Def, 3) => t(&[7.78, 8.89, 10.00, 11.11]),
+            (ArtifactStatName::HpPercentage, 3) => t(&[2.45, 2.80, 3.15, 3.50]),
+            (ArtifactStatName::AtkPercentage, 3) => t(&[2.45, 2.80, 3.15, 3.50]),
+            (ArtifactStatName::DefPercentage, 3) => t(&[3.06, 3.50, 3.93, 4.37]),
+            (ArtifactStatName::ElementalMastery, 3) => t(&[9.79, 11.19, 12.59, 13.99]),
+            (ArtifactStatName::Recharge, 3) => t(&[2.72, 3.11, 3.50, 3.89]),
+            (ArtifactStatName::Critical, 3) => t(&[1.63, 1.86, 2.10, 2.33]),
+            (ArtifactStatName::CriticalDamage, 3) => t(&[3.26, 3.73, 4.20, 4.66]),
+
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ArtifactStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}+{}", self.name, self.value)
+    }
+}
+
+impl RollStat for ArtifactStat {
+    fn roll_table(&self, star: i32) -> Option<RollTable> {
+        self.name.roll_table(star)
+    }
+
+    /// The game rounds the displayed value, to 1 decimal for percentages and to integer otherwise
+    fn displayed_value(&self) -> (f64, f64) {
+        if self.name.is_percentage() {
+            (self.value * 100.0, 0.05)
+        } else {
+            (self.value, 0.5)
+        }
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    fn set_roll_count(&mut self, roll_count: Option<usize>) {
+        self.roll_count = roll_count;
+    }
+
+    fn set_impossible(&mut self, impossible: bool) {
+        self.impossible = impossible;
+    }
+}
+
+impl RollItem for GenshinArtifact {
+    type Stat = ArtifactStat;
+
+    const ITEM_NAME: &'static str = "圣遗物";
+    const LEVELS_PER_UPGRADE: i32 = 4;
+    const ROLL_TABLE_STARS: RangeInclusive<i32> = 3..=5;
+
+    fn star(&self) -> i32 {
+        self.star
+    }
+
+    fn level(&self) -> i32 {
+        self.level
+    }
+
+    fn sub_stats_mut(&mut self) -> Vec<&mut ArtifactStat> {
+        [
+            &mut self.sub_stat_1,
+            &mut self.sub_stat_2,
+            &mut self.sub_stat_3,
+            &mut self.sub_stat_4,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
@@ -0,0 +1,47 @@
+use yas::export::{split_indexed_field, TemplateItem};
+
+use crate::artifact::{ArtifactStat, GenshinArtifact};
+
+impl ArtifactStat {
+    fn template_field(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "name" => self.name.to_string(),
+            "value" => self.value.to_string(),
+            "rolls" => self.roll_count?.to_string(),
+            "impossible" => self.impossible.to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl TemplateItem for GenshinArtifact {
+    const FIELDS: &'static [&'static str] = &[
+        "set", "slot", "star", "level", "lock", "equip",
+        "main.name", "main.value",
+        "sub[].name", "sub[].value", "sub[].rolls", "sub[].impossible",
+    ];
+
+    fn field(&self, name: &str) -> Option<String> {
+        if let Some((index, field)) = split_indexed_field(name, "sub") {
+            let stat = [&self.sub_stat_1, &self.sub_stat_2, &self.sub_stat_3, &self.sub_stat_4]
+                .get(index)?
+                .as_ref()?;
+            return stat.template_field(field);
+        }
+        if let Some(field) = name.strip_prefix("main.") {
+            return self.main_stat.template_field(field);
+        }
+
+        let value = match name {
+            "set" => self.set_name.to_string(),
+            "slot" => self.slot.to_string(),
+            "star" => self.star.to_string(),
+            "level" => self.level.to_string(),
+            "lock" => self.lock.to_string(),
+            "equip" => self.equip.clone()?,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
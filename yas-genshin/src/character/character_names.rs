@@ -2,6 +2,8 @@ use std::collections::HashSet;
 
 use lazy_static::lazy_static;
 
+use yas::common::fuzzy_match::FuzzyMatcher;
+
 lazy_static! {
     pub static ref CHARACTER_NAMES: HashSet<&'static str> = HashSet::from([
         "迪卢克",
@@ -94,4 +96,9 @@ lazy_static! {
         "基尼奇",
         "希诺宁",
     ]);
+
+    /// Character names are short, so only one misread glyph is tolerated
+    pub static ref CHARACTER_NAME_MATCHER: FuzzyMatcher<'static, &'static str> = FuzzyMatcher::new(
+        CHARACTER_NAMES.iter().map(|name| (*name, *name))
+    ).with_max_distance(1);
 }
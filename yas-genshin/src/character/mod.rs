@@ -1,4 +1,4 @@
-pub use character_names::CHARACTER_NAMES;
+pub use character_names::{CHARACTER_NAMES, CHARACTER_NAME_MATCHER};
 
 mod character_names;
 
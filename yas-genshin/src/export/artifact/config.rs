@@ -1,11 +1,19 @@
-use crate::export::artifact::GenshinArtifactExportFormat;
+use crate::export::artifact::ARTIFACT_EXPORT_FORMATS;
 
 #[derive(clap::Args)]
 pub struct ExportArtifactConfig {
-    #[arg(id = "format", long = "format", short = 'f', default_value_t = GenshinArtifactExportFormat::Mona, help = "输出格式")]
-    #[arg(value_enum)]
-    pub format: GenshinArtifactExportFormat,
+    #[arg(id = "format", long = "format", short = 'f', default_value = "mona", help = "输出格式")]
+    #[arg(value_parser = ARTIFACT_EXPORT_FORMATS.value_parser())]
+    pub format: String,
 
     #[arg(id = "output-dir", long = "output-dir", short, default_value_t = String::from("."), help = "输出目录")]
     pub output_dir: String,
+
+    #[arg(id = "output-failed-items", long = "output-failed-items", help = "将转换失败的圣遗物输出到 failed_items.json")]
+    pub output_failed_items: bool,
+
+    /// An existing GOOD database, e.g. with characters and weapons from other tools.
+    /// Its artifacts are replaced with the scanned ones, everything else is kept
+    #[arg(id = "merge-into", long = "merge-into", help = "将结果合并到已有的 GOOD 文件，替换其中的圣遗物并保留其余内容", value_name = "FILE")]
+    pub merge_into: Option<String>,
 }
@@ -1,12 +1,22 @@
 use serde::{Serialize, Serializer};
-use crate::artifact::GenshinArtifact;
+use crate::artifact::{ArtifactStat, GenshinArtifact};
+use yas::export::ExportFormat;
 
 pub struct GenshinArtifactCSVFormat<'a> {
     artifacts: &'a [GenshinArtifact],
 }
 
+/// The inferred roll count, or `!` if the value cannot be made up of legal rolls
+fn roll_count_to_string(stat: &ArtifactStat) -> String {
+    if stat.impossible {
+        String::from("!")
+    } else {
+        stat.roll_count.map(|x| x.to_string()).unwrap_or_default()
+    }
+}
+
 /// CSV format:
-/// set name, slot, star, level, main stat name, main stat value, [sub state name, sub state value]*4, equip
+/// set name, slot, star, level, main stat name, main stat value, [sub state name, sub state value, sub stat roll count]*4, equip
 fn single_artifact_to_string(artifact: &GenshinArtifact) -> String {
     let mut s = String::new();
     s = s + &artifact.set_name.to_string();
@@ -18,26 +28,30 @@ fn single_artifact_to_string(artifact: &GenshinArtifact) -> String {
     if let Some(sub) = &artifact.sub_stat_1 {
         s = s + "," + &sub.name.to_string();
         s = s + "," + &format!("{}", sub.value);
+        s = s + "," + &roll_count_to_string(sub);
     } else {
-        s += ",,";
+        s += ",,,";
     }
     if let Some(sub) = &artifact.sub_stat_2 {
         s = s + "," + &sub.name.to_string();
         s = s + "," + &format!("{}", sub.value);
+        s = s + "," + &roll_count_to_string(sub);
     } else {
-        s += ",,";
+        s += ",,,";
     }
     if let Some(sub) = &artifact.sub_stat_3 {
         s = s + "," + &sub.name.to_string();
         s = s + "," + &format!("{}", sub.value);
+        s = s + "," + &roll_count_to_string(sub);
     } else {
-        s += ",,";
+        s += ",,,";
     }
     if let Some(sub) = &artifact.sub_stat_4 {
         s = s + "," + &sub.name.to_string();
         s = s + "," + &format!("{}", sub.value);
+        s = s + "," + &roll_count_to_string(sub);
     } else {
-        s += ",,";
+        s += ",,,";
     }
     if let Some(e) = &artifact.equip {
         s = s + "," + e;
@@ -56,7 +70,7 @@ impl<'a> GenshinArtifactCSVFormat<'a> {
     }
 
     pub fn to_csv_string(&self) -> String {
-        let header = "套装,部位,星级,等级,主词条名,主词条值,副词条名1,副词条值1,副词条名2,副词条值2,副词条名3,副词条值3,副词条名4,副词条值4,装备";
+        let header = "套装,部位,星级,等级,主词条名,主词条值,副词条名1,副词条值1,副词条次数1,副词条名2,副词条值2,副词条次数2,副词条名3,副词条值3,副词条次数3,副词条名4,副词条值4,副词条次数4,装备";
         let mut result = String::from(header) + "\n";
 
         for artifact in self.artifacts.iter() {
@@ -74,3 +88,23 @@ impl<'a> Serialize for GenshinArtifactCSVFormat<'a> {
         serializer.serialize_str(&s)
     }
 }
+
+pub struct CSVExportFormat;
+
+impl ExportFormat<GenshinArtifact> for CSVExportFormat {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "artifacts.csv"
+    }
+
+    fn description(&self) -> &'static str {
+        "CSV格式圣遗物"
+    }
+
+    fn serialize(&self, items: &[GenshinArtifact]) -> anyhow::Result<Vec<u8>> {
+        Ok(GenshinArtifactCSVFormat::new(items).to_csv_string().into_bytes())
+    }
+}
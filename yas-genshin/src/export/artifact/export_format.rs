@@ -1,17 +1,22 @@
-use clap::ValueEnum;
+use lazy_static::lazy_static;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-pub enum GenshinArtifactExportFormat {
-    Mona,
-    MingyuLab,
-    Good,
-    CSV,
-    /// Export all formats
-    All,
-}
+use yas::export::ExportFormatRegistry;
+
+use crate::artifact::GenshinArtifact;
+
+use super::csv::CSVExportFormat;
+use super::good::GOODExportFormat;
+use super::mingyu_lab::MingyuLabExportFormat;
+use super::mona_uranai::MonaExportFormat;
 
-impl Default for GenshinArtifactExportFormat {
-    fn default() -> Self {
-        Self::Mona
-    }
+lazy_static! {
+    /// The formats of `--format`. Other crates can register more before the command line is built
+    pub static ref ARTIFACT_EXPORT_FORMATS: ExportFormatRegistry<GenshinArtifact> = {
+        let registry = ExportFormatRegistry::new();
+        registry.register(MonaExportFormat);
+        registry.register(MingyuLabExportFormat);
+        registry.register(GOODExportFormat);
+        registry.register(CSVExportFormat);
+        registry
+    };
 }
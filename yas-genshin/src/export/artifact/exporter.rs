@@ -1,33 +1,68 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::FromArgMatches;
+use log::error;
 
-use yas::export::{AssetEmitter, ExportAssets};
+use yas::export::{AssetEmitter, ExportAssets, ExportFormat, TemplateExport, TemplateExportConfig};
 
-use crate::artifact::GenshinArtifact;
-use crate::export::artifact::{ExportArtifactConfig, GenshinArtifactExportFormat};
-use crate::export::artifact::csv::GenshinArtifactCSVFormat;
+use crate::artifact::{GenshinArtifact, GenshinArtifactConversionError};
+use crate::export::artifact::{ExportArtifactConfig, ARTIFACT_EXPORT_FORMATS};
 
-use super::good::GOODFormat;
-use super::mingyu_lab::MingyuLabFormat;
-use super::mona_uranai::MonaFormat;
+use super::good::{merge_into_good, GOODExportFormat};
 
 pub struct GenshinArtifactExporter<'a> {
-    pub format: GenshinArtifactExportFormat,
+    /// A name in `ARTIFACT_EXPORT_FORMATS`, or `all`
+    pub format: String,
     pub results: Option<&'a [GenshinArtifact]>,
+    pub failed_items: &'a [GenshinArtifactConversionError],
     pub output_dir: PathBuf,
+    pub output_failed_items: bool,
+    /// Merge the GOOD format into this file instead of writing a new one
+    pub merge_into: Option<PathBuf>,
+    /// A user defined template, exported alongside `format`
+    pub template: Option<TemplateExport>,
 }
 
 impl <'a> GenshinArtifactExporter<'a> {
-    pub fn new(arg_matches: &clap::ArgMatches, results: &'a [GenshinArtifact]) -> Result<Self> {
+    pub fn new(
+        arg_matches: &clap::ArgMatches,
+        results: &'a [GenshinArtifact],
+        failed_items: &'a [GenshinArtifactConversionError]
+    ) -> Result<Self> {
         let config = ExportArtifactConfig::from_arg_matches(arg_matches)?;
+        let writes_good = ARTIFACT_EXPORT_FORMATS.select(&config.format)
+            .iter()
+            .any(|format| format.name() == GOODExportFormat.name());
+        if config.merge_into.is_some() && !writes_good {
+            return Err(anyhow!("--merge-into 只能与包含 good 的 --format 一起使用"));
+        }
+
         Ok(Self {
             format: config.format,
             results: Some(results),
-            output_dir: PathBuf::from(&config.output_dir)
+            failed_items,
+            output_dir: PathBuf::from(&config.output_dir),
+            output_failed_items: config.output_failed_items,
+            merge_into: config.merge_into.map(PathBuf::from),
+            template: TemplateExportConfig::from_arg_matches(arg_matches)?.build::<GenshinArtifact>()?,
         })
     }
+
+    fn emit_merged_good(&self, path: &Path, results: &[GenshinArtifact], export_assets: &mut ExportAssets) {
+        let merged = std::fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|existing| merge_into_good(&existing, results));
+        match merged {
+            Ok(contents) => export_assets.add_in_place_asset(
+                Some(String::from(GOODExportFormat.name())),
+                path.to_path_buf(),
+                contents.into_bytes(),
+                Some(String::from("合并到已有的 GOOD 文件"))),
+            // do not overwrite a database that cannot be read
+            Err(e) => error!("无法合并到 {:?}: {}", path, e),
+        }
+    }
 }
 
 impl<'a> AssetEmitter for GenshinArtifactExporter<'a> {
@@ -38,101 +73,28 @@ impl<'a> AssetEmitter for GenshinArtifactExporter<'a> {
 
         let results = self.results.unwrap();
 
-        match self.format {
-            GenshinArtifactExportFormat::Mona => {
-                let path = self.output_dir.join("mona.json");
-                let value = MonaFormat::new(results);
-                let contents = serde_json::to_string(&value).unwrap();
-
-                export_assets.add_asset(
-                    Some(String::from("artifacts")),
-                    path,
-                    contents.into_bytes(),
-                    Some(String::from("莫娜圣遗物格式")));
-            },
-            GenshinArtifactExportFormat::MingyuLab => {
-                let path = self.output_dir.join("mingyulab.json");
-                let value = MingyuLabFormat::new(results);
-                let contents = serde_json::to_string(&value).unwrap();
-
-                export_assets.add_asset(
-                    Some(String::from("artifacts")),
-                    path,
-                    contents.into_bytes(),
-                    Some(String::from("原魔计算器圣遗物格式")));
-            },
-            GenshinArtifactExportFormat::Good => {
-                let path = self.output_dir.join("good.json");
-                let value = GOODFormat::new(results);
-                let contents = serde_json::to_string(&value).unwrap();
-
-                export_assets.add_asset(
-                    Some(String::from("artifacts")),
-                    path,
-                    contents.into_bytes(),
-                    Some(String::from("GOOD圣遗物格式")));
-            },
-            GenshinArtifactExportFormat::CSV => {
-                let path = self.output_dir.join("artifacts.csv");
-                let value = GenshinArtifactCSVFormat::new(results);
-                let contents = value.to_csv_string();
-                export_assets.add_asset(
-                    Some(String::from("artifacts csv format")),
-                    path,
-                    contents.into_bytes(),
-                    Some(String::from("CSV格式圣遗物"))
-                );
-            },
-            GenshinArtifactExportFormat::All => {
-                // mona
-                {
-                    let path = self.output_dir.join("mona.json");
-                    let value = MonaFormat::new(results);
-                    let contents = serde_json::to_string(&value).unwrap();
-
-                    export_assets.add_asset(
-                        Some(String::from("mona")),
-                        path,
-                        contents.into_bytes(),
-                        Some(String::from("莫娜圣遗物格式")));
-                }
-                // mingyulab
-                {
-                    let path = self.output_dir.join("mingyulab.json");
-                    let value = MingyuLabFormat::new(results);
-                    let contents = serde_json::to_string(&value).unwrap();
+        for format in ARTIFACT_EXPORT_FORMATS.select(&self.format) {
+            match &self.merge_into {
+                Some(path) if format.name() == GOODExportFormat.name() => {
+                    self.emit_merged_good(path, results, export_assets);
+                },
+                _ => format.emit(results, &self.output_dir, export_assets),
+            }
+        }
 
-                    export_assets.add_asset(
-                        Some(String::from("mingyulab")),
-                        path,
-                        contents.into_bytes(),
-                        Some(String::from("原魔计算器圣遗物格式")));
-                }
-                // good
-                {
-                    let path = self.output_dir.join("good.json");
-                    let value = GOODFormat::new(results);
-                    let contents = serde_json::to_string(&value).unwrap();
+        if let Some(template) = &self.template {
+            template.emit(results, &self.output_dir, export_assets);
+        }
 
-                    export_assets.add_asset(
-                        Some(String::from("GOOD")),
-                        path,
-                        contents.into_bytes(),
-                        Some(String::from("GOOD圣遗物格式")));
-                }
-                // csv
-                {
-                    let path = self.output_dir.join("artifacts.csv");
-                    let value = GenshinArtifactCSVFormat::new(results);
-                    let contents = value.to_csv_string();
-                    export_assets.add_asset(
-                        Some(String::from("csv")),
-                        path,
-                        contents.into_bytes(),
-                        Some(String::from("CSV格式圣遗物"))
-                    );
-                }
-            }
-        };
+        if self.output_failed_items && !self.failed_items.is_empty() {
+            let path = self.output_dir.join("failed_items.json");
+            let contents = serde_json::to_string(&self.failed_items).unwrap();
+            export_assets.add_asset(
+                Some(String::from("failed items")),
+                path,
+                contents.into_bytes(),
+                Some(String::from("转换失败的圣遗物"))
+            );
+        }
     }
 }
@@ -1,5 +1,12 @@
+use anyhow::{anyhow, Result};
+use log::warn;
 use serde::ser::{SerializeMap, Serializer};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::IntoEnumIterator;
+
+use yas::common::inventory_diff::match_items;
+use yas::export::ExportFormat;
 
 use crate::artifact::{
     ArtifactSetName, ArtifactSlot, ArtifactStat, ArtifactStatName, GenshinArtifact,
@@ -171,95 +178,106 @@ impl ArtifactSetName {
     }
 }
 
+/// Character names in zh-cn and their GOOD keys
+#[rustfmt::skip]
+const GOOD_CHARACTER_KEYS: &[(&str, &str)] = &[
+    ("旅行者", "Traveler"),
+    ("神里绫华", "KamisatoAyaka"),
+    ("琴", "Jean"),
+    ("丽莎", "Lisa"),
+    ("芭芭拉", "Barbara"),
+    ("凯亚", "Kaeya"),
+    ("迪卢克", "Diluc"),
+    ("雷泽", "Razor"),
+    ("安柏", "Amber"),
+    ("温迪", "Venti"),
+    ("香菱", "Xiangling"),
+    ("北斗", "Beidou"),
+    ("行秋", "Xingqiu"),
+    ("魈", "Xiao"),
+    ("凝光", "Ningguang"),
+    ("可莉", "Klee"),
+    ("钟离", "Zhongli"),
+    ("菲谢尔", "Fischl"),
+    ("班尼特", "Bennett"),
+    ("达达利亚", "Tartaglia"),
+    ("诺艾尔", "Noelle"),
+    ("七七", "Qiqi"),
+    ("重云", "Chongyun"),
+    ("甘雨", "Ganyu"),
+    ("阿贝多", "Albedo"),
+    ("迪奥娜", "Diona"),
+    ("莫娜", "Mona"),
+    ("刻晴", "Keqing"),
+    ("砂糖", "Sucrose"),
+    ("辛焱", "Xinyan"),
+    ("罗莎莉亚", "Rosaria"),
+    ("胡桃", "HuTao"),
+    ("枫原万叶", "KaedeharaKazuha"),
+    ("烟绯", "Yanfei"),
+    ("宵宫", "Yoimiya"),
+    ("托马", "Thoma"),
+    ("优菈", "Eula"),
+    ("雷电将军", "RaidenShogun"),
+    ("早柚", "Sayu"),
+    ("珊瑚宫心海", "SangonomiyaKokomi"),
+    ("五郎", "Gorou"),
+    ("九条裟罗", "KujouSara"),
+    ("荒泷一斗", "AratakiItto"),
+    ("八重神子", "YaeMiko"),
+    ("鹿野院平藏", "ShikanoinHeizou"),
+    ("夜兰", "Yelan"),
+    ("绮良良", "Kirara"),
+    ("埃洛伊", "Aloy"),
+    ("申鹤", "Shenhe"),
+    ("云堇", "YunJin"),
+    ("久岐忍", "KukiShinobu"),
+    ("神里绫人", "KamisatoAyato"),
+    ("柯莱", "Collei"),
+    ("多莉", "Dori"),
+    ("提纳里", "Tighnari"),
+    ("妮露", "Nilou"),
+    ("赛诺", "Cyno"),
+    ("坎蒂丝", "Candace"),
+    ("纳西妲", "Nahida"),
+    ("莱依拉", "Layla"),
+    ("流浪者", "Wanderer"),
+    ("珐露珊", "Faruzan"),
+    ("瑶瑶", "Yaoyao"),
+    ("艾尔海森", "Alhaitham"),
+    ("迪希雅", "Dehya"),
+    ("米卡", "Mika"),
+    ("卡维", "Kaveh"),
+    ("白术", "Baizhu"),
+    ("琳妮特", "Lynette"),
+    ("林尼", "Lyney"),
+    ("菲米尼", "Freminet"),
+    ("那维莱特", "Neuvillette"),
+    ("莱欧斯利", "Wriothesley"),
+    ("夏洛蒂", "Charlotte"),
+    ("芙宁娜", "Furina"),
+    ("夏沃蕾", "Chevreuse"),
+    ("娜维娅", "Navia"),
+    ("嘉明", "Gaming"),
+    ("闲云", "Xianyun"),
+    ("千织", "Chiori"),
+    ("阿蕾奇诺", "Arlecchino"),
+    ("希格雯", "Sigewinne"),
+    ("赛索斯", "Sethos"),
+    ("克洛琳德", "Clorinde"),
+    ("艾梅莉埃", "Emilie"),
+];
+
 fn equip_from_zh_cn(equip: Option<&str>) -> &'static str {
-    match equip {
-        Some("旅行者") => "Traveler",
-        Some("神里绫华") => "KamisatoAyaka",
-        Some("琴") => "Jean",
-        Some("丽莎") => "Lisa",
-        Some("芭芭拉") => "Barbara",
-        Some("凯亚") => "Kaeya",
-        Some("迪卢克") => "Diluc",
-        Some("雷泽") => "Razor",
-        Some("安柏") => "Amber",
-        Some("温迪") => "Venti",
-        Some("香菱") => "Xiangling",
-        Some("北斗") => "Beidou",
-        Some("行秋") => "Xingqiu",
-        Some("魈") => "Xiao",
-        Some("凝光") => "Ningguang",
-        Some("可莉") => "Klee",
-        Some("钟离") => "Zhongli",
-        Some("菲谢尔") => "Fischl",
-        Some("班尼特") => "Bennett",
-        Some("达达利亚") => "Tartaglia",
-        Some("诺艾尔") => "Noelle",
-        Some("七七") => "Qiqi",
-        Some("重云") => "Chongyun",
-        Some("甘雨") => "Ganyu",
-        Some("阿贝多") => "Albedo",
-        Some("迪奥娜") => "Diona",
-        Some("莫娜") => "Mona",
-        Some("刻晴") => "Keqing",
-        Some("砂糖") => "Sucrose",
-        Some("辛焱") => "Xinyan",
-        Some("罗莎莉亚") => "Rosaria",
-        Some("胡桃") => "HuTao",
-        Some("枫原万叶") => "KaedeharaKazuha",
-        Some("烟绯") => "Yanfei",
-        Some("宵宫") => "Yoimiya",
-        Some("托马") => "Thoma",
-        Some("优菈") => "Eula",
-        Some("雷电将军") => "RaidenShogun",
-        Some("早柚") => "Sayu",
-        Some("珊瑚宫心海") => "SangonomiyaKokomi",
-        Some("五郎") => "Gorou",
-        Some("九条裟罗") => "KujouSara",
-        Some("荒泷一斗") => "AratakiItto",
-        Some("八重神子") => "YaeMiko",
-        Some("鹿野院平藏") => "ShikanoinHeizou",
-        Some("夜兰") => "Yelan",
-        Some("绮良良") => "Kirara",
-        Some("埃洛伊") => "Aloy",
-        Some("申鹤") => "Shenhe",
-        Some("云堇") => "YunJin",
-        Some("久岐忍") => "KukiShinobu",
-        Some("神里绫人") => "KamisatoAyato",
-        Some("柯莱") => "Collei",
-        Some("多莉") => "Dori",
-        Some("提纳里") => "Tighnari",
-        Some("妮露") => "Nilou",
-        Some("赛诺") => "Cyno",
-        Some("坎蒂丝") => "Candace",
-        Some("纳西妲") => "Nahida",
-        Some("莱依拉") => "Layla",
-        Some("流浪者") => "Wanderer",
-        Some("珐露珊") => "Faruzan",
-        Some("瑶瑶") => "Yaoyao",
-        Some("艾尔海森") => "Alhaitham",
-        Some("迪希雅") => "Dehya",
-        Some("米卡") => "Mika",
-        Some("卡维") => "Kaveh",
-        Some("白术") => "Baizhu",
-        Some("琳妮特") => "Lynette",
-        Some("林尼") => "Lyney",
-        Some("菲米尼") => "Freminet",
-        Some("那维莱特") => "Neuvillette",
-        Some("莱欧斯利") => "Wriothesley",
-        Some("夏洛蒂") => "Charlotte",
-        Some("芙宁娜") => "Furina",
-        Some("夏沃蕾") => "Chevreuse",
-        Some("娜维娅") => "Navia",
-        Some("嘉明") => "Gaming",
-        Some("闲云") => "Xianyun",
-        Some("千织") => "Chiori",
-        Some("阿蕾奇诺") => "Arlecchino",
-        Some("希格雯") => "Sigewinne",
-        Some("赛索斯") => "Sethos",
-        Some("克洛琳德") => "Clorinde",
-        Some("艾梅莉埃") => "Emilie",
-        _ => "",
-    }
+    equip.and_then(|name| GOOD_CHARACTER_KEYS.iter().find(|(zh_cn, _)| *zh_cn == name))
+        .map(|(_, key)| *key)
+        .unwrap_or("")
+}
+
+fn equip_to_zh_cn(key: &str) -> Option<&'static str> {
+    GOOD_CHARACTER_KEYS.iter()
+        .find(|(_, good_key)| *good_key == key)
+        .map(|(zh_cn, _)| *zh_cn)
 }
 
 #[derive(Serialize)]
@@ -284,3 +302,176 @@ impl<'a> GOODFormat<'a> {
         }
     }
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GOODArtifactInput {
+    set_key: String,
+    slot_key: String,
+    level: i32,
+    rarity: i32,
+    main_stat_key: String,
+    #[serde(default)]
+    location: String,
+    #[serde(default)]
+    lock: bool,
+    #[serde(default)]
+    substats: Vec<GOODStatInput>,
+}
+
+#[derive(Deserialize)]
+struct GOODStatInput {
+    key: String,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+struct GOODFormatInput {
+    #[serde(default)]
+    artifacts: Vec<GOODArtifactInput>,
+}
+
+impl ArtifactStatName {
+    pub fn from_good(key: &str) -> Option<ArtifactStatName> {
+        ArtifactStatName::iter().find(|x| x.to_good() == key)
+    }
+}
+
+impl ArtifactSlot {
+    pub fn from_good(key: &str) -> Option<ArtifactSlot> {
+        ArtifactSlot::iter().find(|x| x.to_good() == key)
+    }
+}
+
+impl ArtifactSetName {
+    pub fn from_good(key: &str) -> Option<ArtifactSetName> {
+        ArtifactSetName::iter().find(|x| x.to_good() == key)
+    }
+}
+
+impl GOODStatInput {
+    fn to_stat(&self) -> Option<ArtifactStat> {
+        let name = ArtifactStatName::from_good(&self.key)?;
+        let value = if name.is_percentage() { self.value / 100.0 } else { self.value };
+        Some(ArtifactStat {
+            name,
+            value,
+            roll_count: None,
+            impossible: false,
+        })
+    }
+}
+
+impl GOODArtifactInput {
+    fn to_artifact(&self) -> Option<GenshinArtifact> {
+        let main_stat_name = ArtifactStatName::from_good(&self.main_stat_key)?;
+        // GOOD does not store the main stat value, it is determined by the level
+        let main_stat_value = main_stat_name.main_stat_value(self.rarity, self.level).unwrap_or(0.0);
+
+        let mut sub_stats = self.substats.iter()
+            .filter(|x| !x.key.is_empty())
+            .map(|x| x.to_stat());
+
+        Some(GenshinArtifact {
+            set_name: ArtifactSetName::from_good(&self.set_key)?,
+            slot: ArtifactSlot::from_good(&self.slot_key)?,
+            star: self.rarity,
+            lock: self.lock,
+            level: self.level,
+            main_stat: ArtifactStat {
+                name: main_stat_name,
+                value: main_stat_value,
+                roll_count: None,
+                impossible: false,
+            },
+            sub_stat_1: sub_stats.next().flatten(),
+            sub_stat_2: sub_stats.next().flatten(),
+            sub_stat_3: sub_stats.next().flatten(),
+            sub_stat_4: sub_stats.next().flatten(),
+            equip: equip_to_zh_cn(&self.location).map(String::from),
+        })
+    }
+}
+
+/// Read the artifacts of a GOOD file, unrecognized artifacts are skipped
+pub fn artifacts_from_good(contents: &str) -> Result<Vec<GenshinArtifact>> {
+    let input: GOODFormatInput = serde_json::from_str(contents)?;
+
+    let mut result = Vec::new();
+    for artifact in input.artifacts.iter() {
+        match artifact.to_artifact() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别 GOOD 圣遗物 {}/{}/{}", artifact.set_key, artifact.slot_key, artifact.main_stat_key),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Replace the artifacts of an existing GOOD database with `results`, and keep everything else.
+///
+/// An artifact matched to one in the database keeps the fields yas does not write, such as its `id`
+pub fn merge_into_good(existing: &str, results: &[GenshinArtifact]) -> Result<String> {
+    let mut root: Value = serde_json::from_str(existing)?;
+    let root_map = root.as_object_mut().ok_or_else(|| anyhow!("不是 GOOD 格式的文件"))?;
+
+    let mut known_entries = Vec::new();
+    let mut known = Vec::new();
+    if let Some(Value::Array(entries)) = root_map.remove("artifacts") {
+        for entry in entries {
+            let artifact = serde_json::from_value::<GOODArtifactInput>(entry.clone())
+                .ok()
+                .and_then(|x| x.to_artifact());
+            if let Some(artifact) = artifact {
+                known_entries.push(entry);
+                known.push(artifact);
+            }
+        }
+    }
+
+    let mut artifacts = Vec::new();
+    for (artifact, old_index) in results.iter().zip(match_items(&known, results)) {
+        let value = serde_json::to_value(GOODArtifact { artifact })?;
+        let entry = match (old_index, value) {
+            (Some(i), Value::Object(fields)) => {
+                let mut entry = std::mem::take(&mut known_entries[i]);
+                if let Some(entry_map) = entry.as_object_mut() {
+                    entry_map.extend(fields);
+                }
+                entry
+            },
+            (_, value) => value,
+        };
+        artifacts.push(entry);
+    }
+
+    // `version` is the GOOD schema version, so keep the one of the database.
+    // `source` records that yas wrote the artifacts last
+    let version = root_map.get("version").and_then(|x| x.as_u64()).unwrap_or(0).max(1);
+    root_map.insert(String::from("format"), Value::from("GOOD"));
+    root_map.insert(String::from("version"), Value::from(version));
+    root_map.insert(String::from("source"), Value::from("yas"));
+    root_map.insert(String::from("artifacts"), Value::Array(artifacts));
+
+    Ok(serde_json::to_string(&root)?)
+}
+
+pub struct GOODExportFormat;
+
+impl ExportFormat<GenshinArtifact> for GOODExportFormat {
+    fn name(&self) -> &'static str {
+        "good"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "good.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "GOOD圣遗物格式"
+    }
+
+    fn serialize(&self, items: &[GenshinArtifact]) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&GOODFormat::new(items))?)
+    }
+}
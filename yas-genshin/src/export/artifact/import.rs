@@ -0,0 +1,136 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::artifact::GenshinArtifact;
+
+use super::good::artifacts_from_good;
+use super::import_format::GenshinArtifactImportFormat;
+use super::mingyu_lab::artifacts_from_mingyu_lab;
+use super::mona_uranai::artifacts_from_mona;
+
+/// Detect the format of a previous export from its contents
+pub fn detect_artifact_format(contents: &str) -> Result<GenshinArtifactImportFormat> {
+    let value: serde_json::Value = serde_json::from_str(contents)?;
+
+    let format = if value.is_array() {
+        GenshinArtifactImportFormat::MingyuLab
+    } else if value.get("format").and_then(|x| x.as_str()) == Some("GOOD") {
+        GenshinArtifactImportFormat::Good
+    } else {
+        GenshinArtifactImportFormat::Mona
+    };
+    Ok(format)
+}
+
+/// Read artifacts from the contents of a previous export in `format`
+pub fn artifacts_from_str(contents: &str, format: GenshinArtifactImportFormat) -> Result<Vec<GenshinArtifact>> {
+    match format {
+        GenshinArtifactImportFormat::Mona => artifacts_from_mona(contents),
+        GenshinArtifactImportFormat::MingyuLab => artifacts_from_mingyu_lab(contents),
+        GenshinArtifactImportFormat::Good => artifacts_from_good(contents),
+    }
+}
+
+/// Read artifacts from a previous GOOD, mona or mingyulab export, the format is detected from the contents
+pub fn load_artifacts<P: AsRef<Path>>(path: P) -> Result<Vec<GenshinArtifact>> {
+    let contents = std::fs::read_to_string(path)?;
+    let format = detect_artifact_format(&contents)?;
+    artifacts_from_str(&contents, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use yas::export::ExportFormat;
+
+    use crate::artifact::{ArtifactSetName, ArtifactSlot, ArtifactStat, ArtifactStatName};
+    use crate::export::artifact::good::{merge_into_good, GOODExportFormat};
+    use crate::export::artifact::mingyu_lab::MingyuLabExportFormat;
+    use crate::export::artifact::mona_uranai::MonaExportFormat;
+
+    use super::*;
+
+    fn stat(name: ArtifactStatName, value: f64) -> ArtifactStat {
+        ArtifactStat { name, value, roll_count: None, impossible: false }
+    }
+
+    fn artifacts() -> Vec<GenshinArtifact> {
+        let flower = GenshinArtifact {
+            set_name: ArtifactSetName::GladiatorFinale,
+            slot: ArtifactSlot::Flower,
+            star: 5,
+            lock: true,
+            level: 20,
+            main_stat: stat(ArtifactStatName::Hp, ArtifactStatName::Hp.main_stat_value(5, 20).unwrap()),
+            sub_stat_1: Some(stat(ArtifactStatName::Critical, 0.035)),
+            sub_stat_2: Some(stat(ArtifactStatName::CriticalDamage, 0.14)),
+            sub_stat_3: Some(stat(ArtifactStatName::AtkPercentage, 0.099)),
+            sub_stat_4: Some(stat(ArtifactStatName::ElementalMastery, 23.0)),
+            equip: Some(String::from("芙宁娜")),
+        };
+        let goblet = GenshinArtifact {
+            set_name: ArtifactSetName::NoblesseOblige,
+            slot: ArtifactSlot::Goblet,
+            star: 4,
+            lock: false,
+            level: 4,
+            main_stat: stat(ArtifactStatName::PyroBonus, ArtifactStatName::PyroBonus.main_stat_value(4, 4).unwrap()),
+            sub_stat_1: Some(stat(ArtifactStatName::Def, 19.0)),
+            sub_stat_2: Some(stat(ArtifactStatName::Recharge, 0.045)),
+            sub_stat_3: Some(stat(ArtifactStatName::HpPercentage, 0.047)),
+            sub_stat_4: None,
+            equip: None,
+        };
+        vec![flower, goblet]
+    }
+
+    fn round_trip<F: ExportFormat<GenshinArtifact>>(format: F, expected: GenshinArtifactImportFormat) -> Vec<GenshinArtifact> {
+        let contents = String::from_utf8(format.serialize(&artifacts()).unwrap()).unwrap();
+        let detected = detect_artifact_format(&contents).unwrap();
+        assert_eq!(detected, expected);
+        artifacts_from_str(&contents, detected).unwrap()
+    }
+
+    #[test]
+    fn test_good_round_trip() {
+        let result = round_trip(GOODExportFormat, GenshinArtifactImportFormat::Good);
+        assert_eq!(result, artifacts());
+    }
+
+    #[test]
+    fn test_mona_round_trip() {
+        let result = round_trip(MonaExportFormat, GenshinArtifactImportFormat::Mona);
+        // mona groups artifacts by slot, flowers come first
+        assert_eq!(result, artifacts());
+        assert!(result[0].lock);
+        assert!(!result[1].lock);
+    }
+
+    #[test]
+    fn test_mingyu_lab_round_trip() {
+        let result = round_trip(MingyuLabExportFormat, GenshinArtifactImportFormat::MingyuLab);
+        // mingyulab does not store the lock state or the equipping character
+        let expected = artifacts().into_iter()
+            .map(|x| GenshinArtifact { lock: false, equip: None, ..x })
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_merge_into_good_keeps_database() {
+        let existing = r#"{"format":"GOOD","version":2,"source":"GO","characters":[{"key":"Furina"}],"artifacts":[]}"#;
+        let merged: serde_json::Value = serde_json::from_str(&merge_into_good(existing, &artifacts()).unwrap()).unwrap();
+        assert_eq!(merged["version"], 2);
+        assert_eq!(merged["source"], "yas");
+        assert_eq!(merged["characters"][0]["key"], "Furina");
+        assert_eq!(merged["artifacts"].as_array().unwrap().len(), 2);
+
+        // merging again does not change the schema version
+        let merged = merge_into_good(&merged.to_string(), &artifacts()).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&merged).unwrap()["version"], 2);
+
+        let without_version = r#"{"format":"GOOD","artifacts":[]}"#;
+        let merged = merge_into_good(without_version, &artifacts()).unwrap();
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&merged).unwrap()["version"], 1);
+    }
+}
@@ -0,0 +1,8 @@
+use clap::ValueEnum;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum GenshinArtifactImportFormat {
+    Mona,
+    MingyuLab,
+    Good,
+}
@@ -1,4 +1,10 @@
+use anyhow::Result;
+use log::warn;
+use serde::Deserialize;
 use serde::ser::{Serialize, SerializeMap, Serializer};
+use strum::IntoEnumIterator;
+
+use yas::export::ExportFormat;
 
 use crate::artifact::{
     ArtifactSetName, ArtifactSlot, ArtifactStat, ArtifactStatName, GenshinArtifact,
@@ -177,3 +183,112 @@ impl Serialize for MingyuLabFormat<'_> {
         self.artifacts.serialize(serializer)
     }
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MingyuLabArtifactInput {
+    as_key: String,
+    rarity: i32,
+    slot: String,
+    level: i32,
+    main_stat: String,
+    sub_stat1_type: String,
+    sub_stat1_value: f64,
+    sub_stat2_type: String,
+    sub_stat2_value: f64,
+    sub_stat3_type: String,
+    sub_stat3_value: f64,
+    sub_stat4_type: String,
+    sub_stat4_value: f64,
+}
+
+impl ArtifactStatName {
+    pub fn from_mingyu_lab(name: &str) -> Option<ArtifactStatName> {
+        ArtifactStatName::iter().find(|x| x.to_mingyu_lab() == name)
+    }
+}
+
+impl ArtifactSlot {
+    pub fn from_mingyu_lab(name: &str) -> Option<ArtifactSlot> {
+        ArtifactSlot::iter().find(|x| x.to_mingyu_lab() == name)
+    }
+}
+
+impl ArtifactSetName {
+    pub fn from_mingyu_lab(name: &str) -> Option<ArtifactSetName> {
+        ArtifactSetName::iter()
+            .filter(|x| !matches!(x, ArtifactSetName::Adventurer | ArtifactSetName::LuckyDog | ArtifactSetName::TravelingDoctor))
+            .find(|x| x.to_mingyu_lab() == name)
+    }
+}
+
+fn stat_from_mingyu_lab(name: &str, value: f64) -> Option<ArtifactStat> {
+    let name = ArtifactStatName::from_mingyu_lab(name)?;
+    let value = if name.is_percentage() { value / 100.0 } else { value };
+    Some(ArtifactStat { name, value, roll_count: None, impossible: false })
+}
+
+impl MingyuLabArtifactInput {
+    fn to_artifact(&self) -> Option<GenshinArtifact> {
+        // missing sub stats are written as a zero valued placeholder
+        let sub_stat = |name: &str, value: f64| if value == 0.0 {
+            Some(None)
+        } else {
+            stat_from_mingyu_lab(name, value).map(Some)
+        };
+
+        let main_stat_name = ArtifactStatName::from_mingyu_lab(&self.main_stat)?;
+        // mingyulab does not store the main stat value, so take it from the curve
+        let main_stat_value = main_stat_name.main_stat_value(self.rarity, self.level).unwrap_or(0.0);
+
+        Some(GenshinArtifact {
+            set_name: ArtifactSetName::from_mingyu_lab(&self.as_key)?,
+            slot: ArtifactSlot::from_mingyu_lab(&self.slot)?,
+            star: self.rarity,
+            // mingyulab does not store the lock state or the equipping character
+            lock: false,
+            level: self.level,
+            main_stat: ArtifactStat { name: main_stat_name, value: main_stat_value, roll_count: None, impossible: false },
+            sub_stat_1: sub_stat(&self.sub_stat1_type, self.sub_stat1_value)?,
+            sub_stat_2: sub_stat(&self.sub_stat2_type, self.sub_stat2_value)?,
+            sub_stat_3: sub_stat(&self.sub_stat3_type, self.sub_stat3_value)?,
+            sub_stat_4: sub_stat(&self.sub_stat4_type, self.sub_stat4_value)?,
+            equip: None,
+        })
+    }
+}
+
+/// Read the artifacts of a mingyulab file, unrecognized artifacts are skipped
+pub fn artifacts_from_mingyu_lab(contents: &str) -> Result<Vec<GenshinArtifact>> {
+    let input: Vec<MingyuLabArtifactInput> = serde_json::from_str(contents)?;
+
+    let mut result = Vec::new();
+    for artifact in input.iter() {
+        match artifact.to_artifact() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别 MingyuLab 圣遗物 {}/{}", artifact.as_key, artifact.slot),
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct MingyuLabExportFormat;
+
+impl ExportFormat<GenshinArtifact> for MingyuLabExportFormat {
+    fn name(&self) -> &'static str {
+        "mingyu-lab"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "mingyulab.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "原魔计算器圣遗物格式"
+    }
+
+    fn serialize(&self, items: &[GenshinArtifact]) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&MingyuLabFormat::new(items))?)
+    }
+}
@@ -1,6 +1,8 @@
 pub use config::ExportArtifactConfig;
-pub use export_format::GenshinArtifactExportFormat;
+pub use export_format::ARTIFACT_EXPORT_FORMATS;
 pub use exporter::GenshinArtifactExporter;
+pub use import::{artifacts_from_str, detect_artifact_format, load_artifacts};
+pub use import_format::GenshinArtifactImportFormat;
 
 mod good;
 mod mingyu_lab;
@@ -9,3 +11,5 @@ mod exporter;
 mod export_format;
 mod config;
 mod csv;
+mod import;
+mod import_format;
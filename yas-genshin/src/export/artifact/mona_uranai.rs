@@ -1,6 +1,12 @@
 use std::convert::From;
 
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::Deserialize;
 use serde::ser::{Serialize, SerializeMap, Serializer};
+use strum::IntoEnumIterator;
+
+use yas::export::{ExportFormat, HistoryItem};
 
 use crate::artifact::{
     ArtifactSetName, ArtifactSlot, ArtifactStat, ArtifactStatName, GenshinArtifact,
@@ -100,9 +106,15 @@ impl Serialize for ArtifactStat {
     where
         S: Serializer,
     {
-        let mut root = serializer.serialize_map(Some(2))?;
+        let mut root = serializer.serialize_map(None)?;
         root.serialize_entry("name", &self.name.to_mona()).unwrap();
         root.serialize_entry("value", &self.value).unwrap();
+        if let Some(roll_count) = self.roll_count {
+            root.serialize_entry("rollCount", &roll_count)?;
+        }
+        if self.impossible {
+            root.serialize_entry("impossible", &true)?;
+        }
         root.end()
     }
 }
@@ -146,6 +158,7 @@ impl Serialize for MonaArtifact {
         root.serialize_entry("level", &self.level)?;
         root.serialize_entry("star", &self.star)?;
         root.serialize_entry("equip", &self.equip)?;
+        root.serialize_entry("lock", &self.lock)?;
         // let random_id = thread_rng().gen::<u64>();
         // root.serialize_entry("id", &random_id);
 
@@ -206,3 +219,142 @@ impl<'a> MonaFormat<'a> {
         }
     }
 }
+
+#[derive(Deserialize)]
+struct MonaStatInput {
+    name: String,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MonaArtifactInput {
+    set_name: String,
+    position: String,
+    main_tag: MonaStatInput,
+    #[serde(default)]
+    normal_tags: Vec<MonaStatInput>,
+    level: i32,
+    star: i32,
+    #[serde(default)]
+    equip: Option<String>,
+    #[serde(default)]
+    lock: bool,
+}
+
+#[derive(Deserialize)]
+struct MonaFormatInput {
+    #[serde(default)]
+    flower: Vec<MonaArtifactInput>,
+    #[serde(default)]
+    feather: Vec<MonaArtifactInput>,
+    #[serde(default)]
+    sand: Vec<MonaArtifactInput>,
+    #[serde(default)]
+    cup: Vec<MonaArtifactInput>,
+    #[serde(default)]
+    head: Vec<MonaArtifactInput>,
+}
+
+impl ArtifactStatName {
+    pub fn from_mona(name: &str) -> Option<ArtifactStatName> {
+        ArtifactStatName::iter().find(|x| x.to_mona() == name)
+    }
+}
+
+impl ArtifactSetName {
+    pub fn from_mona(name: &str) -> Option<ArtifactSetName> {
+        ArtifactSetName::iter().find(|x| x.to_mona() == name)
+    }
+}
+
+impl ArtifactSlot {
+    pub fn from_mona(name: &str) -> Option<ArtifactSlot> {
+        ArtifactSlot::iter().find(|x| x.to_mona() == name)
+    }
+}
+
+impl MonaStatInput {
+    fn to_stat(&self) -> Option<ArtifactStat> {
+        Some(ArtifactStat {
+            name: ArtifactStatName::from_mona(&self.name)?,
+            value: self.value,
+            roll_count: None,
+            impossible: false,
+        })
+    }
+}
+
+impl MonaArtifactInput {
+    fn to_artifact(&self) -> Option<GenshinArtifact> {
+        let mut sub_stats = self.normal_tags.iter().map(|x| x.to_stat());
+
+        Some(GenshinArtifact {
+            set_name: ArtifactSetName::from_mona(&self.set_name)?,
+            slot: ArtifactSlot::from_mona(&self.position)?,
+            star: self.star,
+            lock: self.lock,
+            level: self.level,
+            main_stat: self.main_tag.to_stat()?,
+            sub_stat_1: sub_stats.next().flatten(),
+            sub_stat_2: sub_stats.next().flatten(),
+            sub_stat_3: sub_stats.next().flatten(),
+            sub_stat_4: sub_stats.next().flatten(),
+            equip: self.equip.clone(),
+        })
+    }
+}
+
+/// Read the artifacts of a mona file, unrecognized artifacts are skipped
+pub fn artifacts_from_mona(contents: &str) -> Result<Vec<GenshinArtifact>> {
+    let input: MonaFormatInput = serde_json::from_str(contents)?;
+
+    let mut result = Vec::new();
+    for artifact in input.flower.iter()
+        .chain(input.feather.iter())
+        .chain(input.sand.iter())
+        .chain(input.cup.iter())
+        .chain(input.head.iter())
+    {
+        match artifact.to_artifact() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别莫娜圣遗物 {}/{}", artifact.set_name, artifact.position),
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct MonaExportFormat;
+
+impl ExportFormat<GenshinArtifact> for MonaExportFormat {
+    fn name(&self) -> &'static str {
+        "mona"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "mona.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "莫娜圣遗物格式"
+    }
+
+    fn serialize(&self, items: &[GenshinArtifact]) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&MonaFormat::new(items))?)
+    }
+}
+
+/// Artifacts are stored in the history database as mona artifacts
+impl HistoryItem for GenshinArtifact {
+    const GAME: &'static str = "genshin";
+
+    fn to_history_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    fn from_history_json(json: &str) -> Result<Self> {
+        let input: MonaArtifactInput = serde_json::from_str(json)?;
+        input.to_artifact().ok_or_else(|| anyhow!("无法识别莫娜圣遗物 {}/{}", input.set_name, input.position))
+    }
+}
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, ops::{Coroutine, CoroutineState}, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
+use std::{cell::RefCell, collections::HashSet, ops::{Coroutine, CoroutineState}, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
 
 use anyhow::Result;
 use clap::FromArgMatches;
@@ -6,6 +6,8 @@ use image::RgbImage;
 use log::{error, info};
 
 use yas::capture::{Capturer, GenericCapturer};
+use yas::common::cancel::CancellationToken;
+use yas::export::{emit_progress, emit_status};
 use yas::game_info::GameInfo;
 use yas::ocr::{ImageToText, yas_ocr_model};
 use yas::positioning::Pos;
@@ -13,6 +15,7 @@ use yas::window_info::FromWindowInfoRepository;
 use yas::window_info::WindowInfoRepository;
 
 use crate::{scanner::artifact_scanner::artifact_scanner_worker::ArtifactScannerWorker};
+use crate::artifact::GenshinArtifact;
 use crate::scanner::artifact_scanner::message_items::SendItem;
 use crate::scanner::artifact_scanner::scan_result::GenshinArtifactScanResult;
 use crate::scanner_controller::repository_layout::{
@@ -38,6 +41,8 @@ pub struct GenshinArtifactScanner {
     image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
     controller: Rc<RefCell<GenshinRepositoryScanController>>,
     capturer: Rc<dyn Capturer<RgbImage>>,
+    cancellation_token: CancellationToken,
+    known_artifacts: HashSet<GenshinArtifact>,
 }
 
 impl GenshinArtifactScanner {
@@ -78,6 +83,8 @@ impl GenshinArtifactScanner {
             image_to_text: Self::get_image_to_text()?,
             // item count will be set later, once the scan starts
             capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
+            known_artifacts: HashSet::new(),
         })
     }
 
@@ -101,11 +108,23 @@ impl GenshinArtifactScanner {
             game_info,
             image_to_text: Self::get_image_to_text()?,
             capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
+            known_artifacts: HashSet::new(),
         })
     }
 }
 
 impl GenshinArtifactScanner {
+    /// Stop capturing once `token` is cancelled, the items captured so far are still recognized
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
+    /// Stop scanning at the first artifact whose identity is in `known_artifacts`
+    pub fn set_known_artifacts(&mut self, known_artifacts: HashSet<GenshinArtifact>) {
+        self.known_artifacts = known_artifacts;
+    }
+
     pub fn capture_panel(&self) -> Result<RgbImage> {
         self.capturer.capture_relative_to(
             self.window_info.panel_rect.to_rect_i32(),
@@ -173,6 +192,7 @@ impl GenshinArtifactScanner {
 
     pub fn scan(&mut self) -> Result<Vec<GenshinArtifactScanResult>> {
         info!("开始扫描，使用鼠标右键中断扫描");
+        emit_status("scanning");
 
         let now = SystemTime::now();
         let (tx, rx) = mpsc::channel::<Option<SendItem>>();
@@ -181,12 +201,14 @@ impl GenshinArtifactScanner {
         let worker = ArtifactScannerWorker::new(
             self.window_info.clone(),
             self.scanner_config.clone(),
+            self.known_artifacts.clone(),
         )?;
 
         let join_handle = worker.run(rx);
         info!("Worker created");
 
         self.send(&tx, count);
+        emit_status("recognizing");
 
         match tx.send(None) {
             Ok(_) => info!("扫描结束，等待识别线程结束，请勿关闭程序"),
@@ -243,6 +265,11 @@ impl GenshinArtifactScanner {
             let pinned_generator = Pin::new(&mut generator);
             match pinned_generator.resume(()) {
                 CoroutineState::Yielded(_) => {
+                    if self.cancellation_token.cancelled() {
+                        info!("扫描任务被取消");
+                        break;
+                    }
+
                     let image = self.capture_panel().unwrap();
                     let star = self.get_star().unwrap();
 
@@ -301,6 +328,7 @@ impl GenshinArtifactScanner {
                     {
                         break;
                     }
+                    emit_progress(artifact_index as usize, count as usize);
 
                     // scanned_count += 1;
                 }
@@ -19,4 +19,9 @@ pub struct GenshinArtifactScannerConfig {
     /// the exact amount to scan
     #[arg(id = "number", long, help = "指定圣遗物数量", value_name = "NUMBER", default_value_t = -1)]
     pub number: i32,
+
+    /// A previous GOOD or mona export. With the inventory sorted by newest first,
+    /// the scan stops once it reaches a row of artifacts already in this file
+    #[arg(id = "since", long, help = "增量扫描，遇到已存在于该 GOOD 或莫娜文件中的圣遗物时停止", value_name = "FILE")]
+    pub since: Option<String>,
 }
@@ -7,11 +7,13 @@ use image::Rgb;
 use image::{GenericImageView, RgbImage};
 use log::{error, info, warn};
 
+use yas::export::{emit_error, emit_item, is_event_stream_active};
 use yas::ocr::ImageToText;
 use yas::ocr::yas_ocr_model;
 use yas::positioning::{Pos, Rect};
 use yas::utils::color_distance;
 
+use crate::artifact::GenshinArtifact;
 use crate::scanner::artifact_scanner::artifact_scanner_window_info::ArtifactScannerWindowInfo;
 use crate::scanner::artifact_scanner::GenshinArtifactScannerConfig;
 use crate::scanner::artifact_scanner::message_items::SendItem;
@@ -41,17 +43,21 @@ pub struct ArtifactScannerWorker {
     model: Box<dyn ImageToText<RgbImage> + Send>,
     window_info: ArtifactScannerWindowInfo,
     config: GenshinArtifactScannerConfig,
+    /// identities of the artifacts of a previous export, see `--since`
+    known_artifacts: HashSet<GenshinArtifact>,
 }
 
 impl ArtifactScannerWorker {
     pub fn new(
         window_info: ArtifactScannerWindowInfo,
         config: GenshinArtifactScannerConfig,
+        known_artifacts: HashSet<GenshinArtifact>,
     ) -> Result<Self> {
         Ok(ArtifactScannerWorker {
             model: get_image_to_text()?,
             window_info,
             config,
+            known_artifacts,
         })
     }
 
@@ -150,6 +156,8 @@ impl ArtifactScannerWorker {
             let mut hash: HashSet<GenshinArtifactScanResult> = HashSet::new();
             // if too many artifacts are same in consecutive, then an error has occurred
             let mut consecutive_dup_count = 0;
+            // if a row of artifacts are already known, then the rest are scanned in the previous export
+            let mut consecutive_known_count = 0;
 
             let is_verbose = self.config.verbose;
             let min_level = self.config.min_level;
@@ -198,12 +206,23 @@ impl ArtifactScannerWorker {
                     break;
                 }
 
+                let is_known = !self.known_artifacts.is_empty() && match GenshinArtifact::try_from(&result) {
+                    Ok(artifact) => self.known_artifacts.contains(&artifact.identity()),
+                    Err(_) => false,
+                };
+
                 if hash.contains(&result) {
                     consecutive_dup_count += 1;
                     warn!("识别到重复物品: {:#?}", result);
                 } else {
                     consecutive_dup_count = 0;
                     hash.insert(result.clone());
+                    if is_event_stream_active() {
+                        match GenshinArtifact::try_from(&result) {
+                            Ok(artifact) => emit_item(results.len(), &artifact),
+                            Err(e) => emit_error(&e.to_string()),
+                        }
+                    }
                     results.push(result);
                 }
 
@@ -213,6 +232,17 @@ impl ArtifactScannerWorker {
                     break;
                 }
 
+                if is_known {
+                    consecutive_known_count += 1;
+                } else {
+                    consecutive_known_count = 0;
+                }
+
+                if consecutive_known_count >= info.col {
+                    info!("连续 {} 件圣遗物已存在于之前的导出中，增量扫描结束", consecutive_known_count);
+                    break;
+                }
+
                 // if token.cancelled() {
                 // error!("扫描任务被取消");
                 // break;
@@ -0,0 +1,124 @@
+use clap::{command, ArgMatches, Args, FromArgMatches};
+use yas::game_info::{GameInfo, GameInfoBuilder};
+use yas::window_info::{load_window_info_repo, WindowInfoRepository};
+use crate::export::{ExportLightConeConfig, StarRailLightConeExporter};
+use crate::light_cone::StarRailLightCone;
+use crate::scanner::light_cone_scanner::{StarRailLightConeScanner, StarRailLightConeScannerConfig};
+use crate::scanner::equipper_matcher::EquipperGalleryConfig;
+use crate::scanner_controller::repository_layout::StarRailRepositoryScannerLogicConfig;
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use yas::common::cancel::CancellationToken;
+use yas::export::{emit_status, AssetEmitter, ExportAssets, ExportSaveConfig, JsonLinesConfig};
+
+pub struct LightConeScannerApplication {
+    arg_matches: ArgMatches,
+    cancellation_token: CancellationToken,
+}
+
+impl LightConeScannerApplication {
+    pub fn new(args: ArgMatches) -> Self {
+        LightConeScannerApplication {
+            arg_matches: args,
+            cancellation_token: CancellationToken::new(),
+        }
+    }
+
+    /// Cancel the scan through `token`, e.g. from `yas serve`
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = token;
+        self
+    }
+
+    pub fn build_command() -> clap::Command {
+        let mut cmd = command!();
+        cmd = <StarRailLightConeScannerConfig as Args>::augment_args_for_update(cmd);
+        cmd = <StarRailRepositoryScannerLogicConfig as Args>::augment_args_for_update(cmd);
+        cmd = <EquipperGalleryConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ExportLightConeConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ExportSaveConfig as Args>::augment_args_for_update(cmd);
+        cmd = <JsonLinesConfig as Args>::augment_args_for_update(cmd);
+        cmd
+    }
+
+    fn get_window_info_repository() -> WindowInfoRepository {
+        load_window_info_repo!(
+            "../../window_info/windows1920x1080.json"
+        )
+    }
+
+    fn get_game_info() -> Result<GameInfo> {
+        GameInfoBuilder::new()
+            .add_local_window_name("崩坏：星穹铁道")
+            .add_local_window_name("Honkai: Star Rail")
+            .add_cloud_window_name("云·星穹铁道")
+            .build()
+    }
+}
+
+impl LightConeScannerApplication {
+    pub fn run(&self) -> Result<()> {
+        let arg_matches = &self.arg_matches;
+        JsonLinesConfig::from_arg_matches(arg_matches)?.apply();
+        let window_info_repository = Self::get_window_info_repository();
+        let game_info = Self::get_game_info()?;
+
+        info!("window: {:?}", game_info.window);
+        info!("ui: {:?}", game_info.ui);
+        info!("cloud: {}", game_info.is_cloud);
+        info!("resolution family: {:?}", game_info.resolution_family);
+
+        #[cfg(target_os = "windows")]
+        {
+            // assure admin
+            if !yas::utils::is_admin() {
+                return Err(anyhow!("请使用管理员运行"));
+            }
+        }
+
+        let mut scanner = StarRailLightConeScanner::from_arg_matches(
+            &window_info_repository,
+            arg_matches,
+            game_info.clone()
+        )?;
+        if let Some(name) = EquipperGalleryConfig::from_arg_matches(arg_matches)?.equipper_capture {
+            let path = scanner.save_equipper_avatar(&name)?;
+            info!("已将 {} 的头像保存到 {:?}", name, path);
+            return Ok(());
+        }
+        scanner.set_cancellation_token(self.cancellation_token.clone());
+
+        let results = scanner.scan()?;
+        let mut light_cones = Vec::new();
+        let mut failed_count = 0;
+        for (index, item) in results.iter().enumerate() {
+            match StarRailLightCone::try_from(item) {
+                Ok(v) => light_cones.push(v),
+                Err(e) => {
+                    warn!("第 {} 件{}", index + 1, e);
+                    failed_count += 1;
+                },
+            }
+        }
+
+        let exporter = StarRailLightConeExporter::new(arg_matches, &light_cones)?;
+        let mut export_assets = ExportAssets::new();
+        exporter.emit(&mut export_assets);
+
+        let stats = export_assets.save_with(&ExportSaveConfig::from_arg_matches(arg_matches)?);
+        info!("保存结果：");
+        let table = format!("{}", stats);
+        // print multiline
+        for line in table.lines() {
+            info!("{}", line);
+        }
+
+        if failed_count > 0 {
+            warn!("{} 件光锥转换失败", failed_count);
+        }
+        info!("Yas 识别结束，共识别到 {} 件光锥。", results.len());
+        emit_status("finished");
+
+        Ok(())
+    }
+}
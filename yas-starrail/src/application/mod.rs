@@ -1,3 +1,11 @@
+pub use character_scanner::CharacterScannerApplication;
+pub use light_cone_scanner::LightConeScannerApplication;
+pub use relic_converter::RelicConverterApplication;
+pub use relic_diff::RelicDiffApplication;
 pub use relic_scanner::RelicScannerApplication;
 
+mod character_scanner;
+mod light_cone_scanner;
+mod relic_converter;
+mod relic_diff;
 mod relic_scanner;
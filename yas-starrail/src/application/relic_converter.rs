@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use log::info;
+
+use yas::export::{AssetEmitter, ExportAssets};
+
+use crate::export::{relics_from_str, StarRailRelicExporter, StarRailRelicImportFormat};
+
+/// Convert a previous export to another format, without scanning again
+pub struct RelicConverterApplication {
+    pub input: PathBuf,
+    pub from: StarRailRelicImportFormat,
+    /// A name in `RELIC_EXPORT_FORMATS`, or `all`
+    pub to: String,
+    pub output_dir: PathBuf,
+}
+
+impl RelicConverterApplication {
+    pub fn run(&self) -> Result<()> {
+        let contents = std::fs::read_to_string(&self.input)?;
+        let relics = relics_from_str(&contents, self.from)?;
+        info!("读取到 {} 件遗器", relics.len());
+
+        let exporter = StarRailRelicExporter {
+            format: self.to.clone(),
+            results: Some(&relics),
+            failed_items: &[],
+            output_dir: self.output_dir.clone(),
+            output_failed_items: false,
+            template: None,
+        };
+        let mut export_assets = ExportAssets::new();
+        exporter.emit(&mut export_assets);
+
+        let stats = export_assets.save();
+        info!("保存结果：");
+        let table = format!("{}", stats);
+        // print multiline
+        for line in table.lines() {
+            info!("{}", line);
+        }
+
+        Ok(())
+    }
+}
@@ -1,30 +1,46 @@
-use clap::{command, ArgMatches, Args};
+use clap::{command, ArgMatches, Args, FromArgMatches};
 use yas::game_info::{GameInfo, GameInfoBuilder};
 use yas::window_info::{load_window_info_repo, WindowInfoRepository};
 use crate::export::{ExportRelicConfig, StarRailRelicExporter};
 use crate::scanner::relic_scanner::{StarRailRelicScanner, StarRailRelicScannerConfig};
+use crate::scanner::equipper_matcher::EquipperGalleryConfig;
 use crate::scanner_controller::repository_layout::StarRailRepositoryScannerLogicConfig;
 use anyhow::{anyhow, Result};
-use log::info;
-use yas::export::{AssetEmitter, ExportAssets};
-use crate::relic::StarRailRelic;
+use log::{info, warn};
+use yas::common::cancel::CancellationToken;
+use yas::common::main_stat_check::MainStatCheckSummary;
+use yas::export::{emit_item, emit_removed, emit_status, AssetEmitter, ExportAssets, ExportSaveConfig, JsonLinesConfig, ScanHistoryConfig, TemplateExportConfig};
+use crate::relic::{RelicField, StarRailRelic, StarRailRelicConversionError};
 
 pub struct RelicScannerApplication {
     arg_matches: ArgMatches,
+    cancellation_token: CancellationToken,
 }
 
 impl RelicScannerApplication {
     pub fn new(args: ArgMatches) -> Self {
         RelicScannerApplication {
-            arg_matches: args
+            arg_matches: args,
+            cancellation_token: CancellationToken::new(),
         }
     }
 
+    /// Cancel the scan through `token`, e.g. from `yas serve`
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = token;
+        self
+    }
+
     pub fn build_command() -> clap::Command {
         let mut cmd = command!();
         cmd = <StarRailRelicScannerConfig as Args>::augment_args_for_update(cmd);
         cmd = <StarRailRepositoryScannerLogicConfig as Args>::augment_args_for_update(cmd);
+        cmd = <EquipperGalleryConfig as Args>::augment_args_for_update(cmd);
         cmd = <ExportRelicConfig as Args>::augment_args_for_update(cmd);
+        cmd = <TemplateExportConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ScanHistoryConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ExportSaveConfig as Args>::augment_args_for_update(cmd);
+        cmd = <JsonLinesConfig as Args>::augment_args_for_update(cmd);
         cmd
     }
 
@@ -54,6 +70,9 @@ impl RelicScannerApplication {
     pub fn run(&self) -> Result<()> {
         // Self::init();
         let arg_matches = &self.arg_matches;
+        JsonLinesConfig::from_arg_matches(arg_matches)?.apply();
+        // fail on a bad template before spending a whole scan on it
+        TemplateExportConfig::from_arg_matches(arg_matches)?.build::<StarRailRelic>()?;
         let window_info_repository = Self::get_window_info_repository();
         let game_info = Self::get_game_info()?;
 
@@ -75,25 +94,70 @@ impl RelicScannerApplication {
             &arg_matches,
             game_info.clone()
         )?;
+        if let Some(name) = EquipperGalleryConfig::from_arg_matches(arg_matches)?.equipper_capture {
+            let path = scanner.save_equipper_avatar(&name)?;
+            info!("已将 {} 的头像保存到 {:?}", name, path);
+            return Ok(());
+        }
+        scanner.set_cancellation_token(self.cancellation_token.clone());
 
         let results = scanner.scan()?;
-        let starrail_relics = results.iter()
-            .map(|x| StarRailRelic::try_from(x))
-            .filter(|x| x.is_ok())
-            .map(|x| x.unwrap())
-            .collect::<Vec<_>>();
-        let exporter = StarRailRelicExporter::new(&arg_matches, &starrail_relics)?;
+        let mut main_stat_summary = MainStatCheckSummary::default();
+        let mut starrail_relics = Vec::new();
+        let mut failed_items = Vec::new();
+        for (index, item) in results.iter().enumerate() {
+            let mut relic = match StarRailRelic::try_from(item) {
+                Ok(v) => v,
+                Err(e) => {
+                    failed_items.push(e.with_index(index));
+                    continue;
+                },
+            };
+
+            let check = relic.check_main_stat();
+            main_stat_summary.add(&check);
+            // the worker streamed the item before it was checked
+            if check.is_corrected() {
+                emit_item(index, &relic);
+            }
+            if check.is_rejected() {
+                emit_removed(index);
+                let raw = format!("{}+{} Lv.{}", item.main_stat_name, item.main_stat_value, item.level);
+                failed_items.push(StarRailRelicConversionError::new(RelicField::MainStatLevel, raw).with_index(index));
+                continue;
+            }
+
+            starrail_relics.push(relic);
+        }
+        let exporter = StarRailRelicExporter::new(&arg_matches, &starrail_relics, &failed_items)?;
         let mut export_assets = ExportAssets::new();
         exporter.emit(&mut export_assets);
 
-        let stats = export_assets.save();
+        let stats = export_assets.save_with(&ExportSaveConfig::from_arg_matches(arg_matches)?);
         info!("保存结果：");
         let table = format!("{}", stats);
         // print multiline
         for line in table.lines() {
             info!("{}", line);
         }
+
+        let history_config = ScanHistoryConfig::from_arg_matches(arg_matches)?;
+        if let Some(run_id) = history_config.record(env!("CARGO_PKG_VERSION"), &starrail_relics)? {
+            info!("已追加到历史数据库，本次记录编号 {}", run_id);
+        }
+        if main_stat_summary.has_discrepancy() {
+            warn!("{}", main_stat_summary);
+        } else {
+            info!("{}", main_stat_summary);
+        }
+        if !failed_items.is_empty() {
+            warn!("{} 件遗器转换失败：", failed_items.len());
+            for e in failed_items.iter() {
+                warn!("{}", e);
+            }
+        }
         info!("Yas 识别结束，共识别到 {} 件圣遗物。", results.len());
+        emit_status("finished");
 
         Ok(())
     }
@@ -0,0 +1,107 @@
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use log::warn;
+use regex::Regex;
+use yas::common::fuzzy_match::{FuzzyMatcher, FuzzyMatchResult};
+use crate::character::CHARACTERS;
+use crate::scanner::character_scanner::StarRailCharacterScanResult;
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct CharacterSkills {
+    pub basic: i32,
+    pub skill: i32,
+    pub ult: i32,
+    pub talent: i32,
+}
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct StarRailCharacter {
+    /// The key used by HSR-Scanner, e.g. `DanHengImbibitorLunae`
+    pub name: String,
+    pub level: i32,
+    /// 0 to 6, each ascension raises the max level by 10
+    pub ascension: i32,
+    /// 0 to 6
+    pub eidolon: i32,
+    pub skills: CharacterSkills,
+    /// Whether the 3 ability traces are unlocked
+    pub abilities: [bool; 3],
+    /// Whether the 10 stat bonus traces are unlocked
+    pub stats: [bool; 10],
+}
+
+lazy_static! {
+    static ref CHARACTER_NAME_MATCHER: FuzzyMatcher<'static, &'static str> = FuzzyMatcher::new(
+        CHARACTERS.iter().map(|x| (x.name_chs, x.key))
+    );
+    static ref LEVEL_RE: Regex = Regex::new(r"(\d+)\s*/\s*(\d+)").unwrap();
+    static ref NUMBER_RE: Regex = Regex::new(r"\d+").unwrap();
+}
+
+impl StarRailCharacter {
+    /// The HSR-Scanner key of a character name in zh-cn
+    pub fn name_from_zh_cn(s: &str) -> Option<&'static str> {
+        match CHARACTER_NAME_MATCHER.find(s) {
+            FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                warn!("character name `{}` is ambiguous: {:?}", s, candidates);
+                None
+            },
+            result => result.value(),
+        }
+    }
+}
+
+/// `80/80` is level 80 with a max level of 80, which is reached after 6 ascensions
+fn parse_level_ascension(s: &str) -> Option<(i32, i32)> {
+    let cap = LEVEL_RE.captures(s)?;
+    let level = cap[1].parse::<i32>().ok()?;
+    let max_level = cap[2].parse::<i32>().ok()?;
+    if !(1..=80).contains(&level) || level > max_level {
+        return None;
+    }
+    let ascension = ((max_level - 20) / 10).clamp(0, 6);
+    Some((level, ascension))
+}
+
+/// `6/10` is skill level 6, the level can exceed the max with eidolons
+fn parse_skill_level(s: &str) -> Option<i32> {
+    let level = NUMBER_RE.find(s)?.as_str().parse::<i32>().ok()?;
+    if (1..=15).contains(&level) {
+        Some(level)
+    } else {
+        None
+    }
+}
+
+impl TryFrom<&StarRailCharacterScanResult> for StarRailCharacter {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &StarRailCharacterScanResult) -> Result<Self, Self::Error> {
+        let name = StarRailCharacter::name_from_zh_cn(&value.name)
+            .ok_or_else(|| anyhow!("角色的名称无法识别：`{}`", value.name))?;
+        let (level, ascension) = parse_level_ascension(&value.level)
+            .ok_or_else(|| anyhow!("角色的等级无法识别：`{}`", value.level))?;
+
+        let skill_level = |index: usize| {
+            let s = &value.skills[index];
+            parse_skill_level(s).ok_or_else(|| anyhow!("角色的行迹等级无法识别：`{}`", s))
+        };
+        let skills = CharacterSkills {
+            basic: skill_level(0)?,
+            skill: skill_level(1)?,
+            ult: skill_level(2)?,
+            talent: skill_level(3)?,
+        };
+
+        Ok(StarRailCharacter {
+            name: String::from(name),
+            level,
+            ascension,
+            // eidolons are unlocked in order
+            eidolon: value.eidolons.iter().take_while(|x| **x).count() as i32,
+            skills,
+            abilities: value.abilities,
+            stats: value.stats,
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use image::Rgb;
+
+#[derive(Debug, Clone)]
+pub struct CharacterData {
+    /// The HSR-Scanner key, e.g. `Seele`
+    pub key: &'static str,
+    pub name_chs: &'static str,
+    /// The StarRailRes id, e.g. `1102`
+    pub id: &'static str,
+    /// The center pixel of the equipper avatar, used when no avatar gallery is available
+    pub equipper_color: Option<Rgb<u8>>,
+}
+
+// https://github.com/Mar-7th/StarRailRes/blob/master/index_new/cn/characters.json
+// The trailblazer is named by the player and cannot be recognized
+yas_derive_starrail::yas_starrail_characters!("yas-starrail/data/characters.json");
+
+impl CharacterData {
+    pub fn from_key(key: &str) -> Option<&'static CharacterData> {
+        CHARACTERS.iter().find(|x| x.key == key)
+    }
+}
@@ -0,0 +1,5 @@
+pub use character::{CharacterSkills, StarRailCharacter};
+pub use character_data::{CharacterData, CHARACTERS};
+
+mod character;
+mod character_data;
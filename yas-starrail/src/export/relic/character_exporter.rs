@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::FromArgMatches;
+use log::error;
+
+use yas::export::{AssetEmitter, ExportAssets};
+
+use crate::character::StarRailCharacter;
+use crate::relic::StarRailRelic;
+
+use super::config::ExportCharacterConfig;
+use super::hsr::StarRailHSRFormat;
+use super::import::load_previous_export;
+
+/// Exports characters in the `characters` section of the HSR format,
+/// together with the relics and the other sections of a previous export if `--relics` is given
+pub struct StarRailCharacterExporter<'a> {
+    pub results: &'a [StarRailCharacter],
+    pub relics: Vec<StarRailRelic>,
+    /// The file given by `--relics`, its light cones are kept in the HSR format
+    pub previous: Option<serde_json::Value>,
+    pub output_dir: PathBuf,
+}
+
+impl<'a> StarRailCharacterExporter<'a> {
+    pub fn new(arg_matches: &clap::ArgMatches, results: &'a [StarRailCharacter]) -> Result<Self> {
+        let config = ExportCharacterConfig::from_arg_matches(arg_matches)?;
+        let (relics, previous) = match config.relics.as_ref() {
+            Some(path) => {
+                let previous = load_previous_export(path)?;
+                (previous.relics, Some(previous.root))
+            },
+            None => (Vec::new(), None),
+        };
+        Ok(Self {
+            results,
+            relics,
+            previous,
+            output_dir: PathBuf::from(&config.output_dir),
+        })
+    }
+}
+
+impl<'a> AssetEmitter for StarRailCharacterExporter<'a> {
+    fn emit(&self, asset_bundle: &mut ExportAssets) {
+        let format = StarRailHSRFormat::new_version3(&self.relics)
+            .with_characters(self.results)
+            .with_previous(self.previous.as_ref());
+        match serde_json::to_vec(&format) {
+            Ok(contents) => asset_bundle.add_asset(
+                Some(String::from("hsr")),
+                self.output_dir.join("hsr.json"),
+                contents,
+                Some(String::from("HSR角色格式"))
+            ),
+            Err(e) => error!("无法导出 hsr 格式: {}", e),
+        }
+    }
+}
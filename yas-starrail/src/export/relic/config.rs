@@ -1,11 +1,36 @@
-use crate::export::relic::export_format::StarRailRelicExportFormat;
+use crate::export::relic::export_format::RELIC_EXPORT_FORMATS;
 
 #[derive(clap::Args)]
 pub struct ExportRelicConfig {
-    #[arg(id = "format", long = "format", short = 'f', default_value_t = StarRailRelicExportFormat::HSR, help = "输出格式")]
-    #[arg(value_enum)]
-    pub format: StarRailRelicExportFormat,
+    #[arg(id = "format", long = "format", short = 'f', default_value = "hsr", help = "输出格式")]
+    #[arg(value_parser = RELIC_EXPORT_FORMATS.value_parser())]
+    pub format: String,
 
     #[arg(id = "output-dir", long = "output-dir", short, default_value_t = String::from("."), help = "输出目录")]
     pub output_dir: String,
+
+    #[arg(id = "output-failed-items", long = "output-failed-items", help = "将转换失败的遗器输出到 failed_items.json")]
+    pub output_failed_items: bool,
+}
+
+#[derive(clap::Args)]
+pub struct ExportLightConeConfig {
+    #[arg(id = "format", long = "format", short = 'f', default_value = "hsr", help = "输出格式")]
+    #[arg(value_parser = ["hsr", "march7th", "all"])]
+    pub format: String,
+
+    #[arg(id = "output-dir", long = "output-dir", short, default_value_t = String::from("."), help = "输出目录")]
+    pub output_dir: String,
+
+    #[arg(id = "relics", long = "relics", help = "合并之前导出的 HSR 或三月七文件，保留其中的遗器和角色，输出同时包含光锥的文件", value_name = "FILE")]
+    pub relics: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct ExportCharacterConfig {
+    #[arg(id = "output-dir", long = "output-dir", short, default_value_t = String::from("."), help = "输出目录")]
+    pub output_dir: String,
+
+    #[arg(id = "relics", long = "relics", help = "合并之前导出的 HSR 或三月七文件，保留其中的遗器和光锥，输出同时包含角色的文件", value_name = "FILE")]
+    pub relics: Option<String>,
 }
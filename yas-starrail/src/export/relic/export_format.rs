@@ -1,13 +1,20 @@
-use clap::ValueEnum;
+use lazy_static::lazy_static;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-pub enum StarRailRelicExportFormat {
-    March7th,
-    HSR,
-}
+use yas::export::ExportFormatRegistry;
+
+use crate::relic::StarRailRelic;
 
-impl Default for StarRailRelicExportFormat {
-    fn default() -> Self {
-        Self::HSR
-    }
-}
\ No newline at end of file
+use super::fribbels::FribbelsExportFormat;
+use super::hsr::HSRExportFormat;
+use super::march7th::March7thExportFormat;
+
+lazy_static! {
+    /// The formats of `--format`. Other crates can register more before the command line is built
+    pub static ref RELIC_EXPORT_FORMATS: ExportFormatRegistry<StarRailRelic> = {
+        let registry = ExportFormatRegistry::new();
+        registry.register(March7thExportFormat);
+        registry.register(HSRExportFormat);
+        registry.register(FribbelsExportFormat);
+        registry
+    };
+}
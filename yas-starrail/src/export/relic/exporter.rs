@@ -2,28 +2,37 @@ use std::path::{PathBuf};
 
 use clap::{FromArgMatches};
 
-use crate::relic::StarRailRelic;
+use crate::relic::{StarRailRelic, StarRailRelicConversionError};
 
-use crate::export::relic::{ExportRelicConfig, StarRailRelicExportFormat};
+use crate::export::relic::{ExportRelicConfig, RELIC_EXPORT_FORMATS};
 use anyhow::Result;
-use yas::export::{AssetEmitter, ExportAssets};
-use crate::export::relic::hsr::StarRailHSRFormat;
-
-use super::march7th::March7thFormat;
+use yas::export::{AssetEmitter, ExportAssets, TemplateExport, TemplateExportConfig};
 
 pub struct StarRailRelicExporter<'a> {
-    pub format: StarRailRelicExportFormat,
+    /// A name in `RELIC_EXPORT_FORMATS`, or `all`
+    pub format: String,
     pub results: Option<&'a [StarRailRelic]>,
+    pub failed_items: &'a [StarRailRelicConversionError],
     pub output_dir: PathBuf,
+    pub output_failed_items: bool,
+    /// A user defined template, exported alongside `format`
+    pub template: Option<TemplateExport>,
 }
 
 impl<'a> StarRailRelicExporter<'a> {
-    pub fn new(arg_matches: &clap::ArgMatches, results: &'a [StarRailRelic]) -> Result<Self> {
+    pub fn new(
+        arg_matches: &clap::ArgMatches,
+        results: &'a [StarRailRelic],
+        failed_items: &'a [StarRailRelicConversionError]
+    ) -> Result<Self> {
         let config = ExportRelicConfig::from_arg_matches(arg_matches)?;
         Ok(Self {
             format: config.format,
             results: Some(results),
-            output_dir: PathBuf::from(&config.output_dir)
+            failed_items,
+            output_dir: PathBuf::from(&config.output_dir),
+            output_failed_items: config.output_failed_items,
+            template: TemplateExportConfig::from_arg_matches(arg_matches)?.build::<StarRailRelic>()?,
         })
     }
 }
@@ -36,31 +45,23 @@ impl<'a> AssetEmitter for StarRailRelicExporter<'a> {
 
         let results = self.results.unwrap();
 
-        match self.format {
-            StarRailRelicExportFormat::March7th => {
-                let path = self.output_dir.join("march7th.json");
-                let format = March7thFormat::new(results);
-                let contents = serde_json::to_string(&format).unwrap();
+        for format in RELIC_EXPORT_FORMATS.select(&self.format) {
+            format.emit(results, &self.output_dir, asset_bundle);
+        }
 
-                asset_bundle.add_asset(
-                    Some(String::from("relics")),
-                    path,
-                    contents.into_bytes(),
-                    Some(String::from("三月七遗器格式"))
-                );
-            },
-            StarRailRelicExportFormat::HSR => {
-                let path = self.output_dir.join("hsr.json");
-                let format = StarRailHSRFormat::new_version3(results);
-                let contents = serde_json::to_string(&format).unwrap();
+        if let Some(template) = &self.template {
+            template.emit(results, &self.output_dir, asset_bundle);
+        }
 
-                asset_bundle.add_asset(
-                    Some(String::from("relics")),
-                    path,
-                    contents.into_bytes(),
-                    Some(String::from("HSR遗器格式"))
-                );
-            }
+        if self.output_failed_items && !self.failed_items.is_empty() {
+            let path = self.output_dir.join("failed_items.json");
+            let contents = serde_json::to_string(&self.failed_items).unwrap();
+            asset_bundle.add_asset(
+                Some(String::from("failed items")),
+                path,
+                contents.into_bytes(),
+                Some(String::from("转换失败的遗器"))
+            );
         }
     }
 }
@@ -0,0 +1,167 @@
+use anyhow::Result;
+use log::warn;
+use nanoid::nanoid;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use yas::export::ExportFormat;
+
+use crate::character::CharacterData;
+use crate::relic::{RelicSlot, RelicStat, RelicStatName, StarRailRelic};
+
+fn character_id(name: &str) -> Option<&'static str> {
+    CharacterData::from_key(name).map(|x| x.id)
+}
+
+impl RelicStatName {
+    /// The HSR name, with a `%` for the percentage HP, ATK and DEF
+    pub fn to_fribbels_stat_name(&self) -> String {
+        let name = self.to_hsr_stat_name();
+        match *self {
+            RelicStatName::HPPercentage | RelicStatName::ATKPercentage | RelicStatName::DEFPercentage => format!("{}%", name),
+            _ => String::from(name),
+        }
+    }
+}
+
+impl RelicSlot {
+    /// The HSR name without spaces, e.g. `PlanarSphere`
+    pub fn to_fribbels_part_name(&self) -> String {
+        self.to_hsr_slot_name().replace(' ', "")
+    }
+}
+
+struct FribbelsStat<'a> {
+    stat: &'a RelicStat,
+    is_main_stat: bool,
+}
+
+impl<'a> FribbelsStat<'a> {
+    fn main(stat: &'a RelicStat) -> Self {
+        FribbelsStat { stat, is_main_stat: true }
+    }
+
+    fn sub(stat: &'a RelicStat) -> Self {
+        FribbelsStat { stat, is_main_stat: false }
+    }
+}
+
+impl<'a> Serialize for FribbelsStat<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut root = serializer.serialize_map(Some(2))?;
+        root.serialize_entry("stat", &self.stat.name.to_fribbels_stat_name())?;
+        // percentages are written as in game, e.g. 3.24 for 3.24%,
+        // and main stats such as ERR or elemental DMG are percentages that sub stats never are
+        let is_percentage = if self.is_main_stat {
+            self.stat.name.is_main_stat_percentage()
+        } else {
+            self.stat.name.is_percentage()
+        };
+        let value = if is_percentage {
+            self.stat.value * 100.0
+        } else {
+            self.stat.value
+        };
+        root.serialize_entry("value", &value)?;
+        root.end()
+    }
+}
+
+struct FribbelsRelic<'a>(&'a StarRailRelic);
+
+impl<'a> Serialize for FribbelsRelic<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let relic = self.0;
+        let mut root = serializer.serialize_map(Some(9))?;
+
+        root.serialize_entry("id", &nanoid!())?;
+        root.serialize_entry("set", relic.set_name.to_hsr_set_name())?;
+        root.serialize_entry("part", &relic.slot.to_fribbels_part_name())?;
+        root.serialize_entry("grade", &relic.star)?;
+        root.serialize_entry("enhance", &relic.level)?;
+        root.serialize_entry("main", &FribbelsStat::main(&relic.main_stat))?;
+
+        let sub_stats: Vec<FribbelsStat> = [&relic.sub_stat_1, &relic.sub_stat_2, &relic.sub_stat_3, &relic.sub_stat_4]
+            .into_iter()
+            .flatten()
+            .map(FribbelsStat::sub)
+            .collect();
+        root.serialize_entry("substats", &sub_stats)?;
+
+        let equipped_by = relic.equip.as_deref().and_then(|name| {
+            let id = character_id(name);
+            if id.is_none() {
+                warn!("无法转换角色 {} 的 Fribbels ID", name);
+            }
+            id
+        });
+        root.serialize_entry("equippedBy", &equipped_by)?;
+        root.serialize_entry("verified", &false)?;
+
+        root.end()
+    }
+}
+
+/// https://github.com/fribbels/hsr-optimizer
+pub struct StarRailFribbelsFormat<'a> {
+    relics: Vec<FribbelsRelic<'a>>,
+}
+
+impl<'a> StarRailFribbelsFormat<'a> {
+    pub fn new(results: &'a [StarRailRelic]) -> Self {
+        StarRailFribbelsFormat {
+            relics: results.iter().map(FribbelsRelic).collect(),
+        }
+    }
+}
+
+impl<'a> Serialize for StarRailFribbelsFormat<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut root = serializer.serialize_map(Some(4))?;
+        root.serialize_entry("source", "yas-scanner")?;
+        root.serialize_entry("build", &(String::from("v") + env!("CARGO_PKG_VERSION")))?;
+        root.serialize_entry("relics", &self.relics)?;
+        root.serialize_entry::<str, [usize; 0]>("characters", &[])?;
+        root.end()
+    }
+}
+
+pub struct FribbelsExportFormat;
+
+impl ExportFormat<StarRailRelic> for FribbelsExportFormat {
+    fn name(&self) -> &'static str {
+        "fribbels"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "fribbels.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fribbels HSR Optimizer 遗器格式"
+    }
+
+    fn serialize(&self, items: &[StarRailRelic]) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&StarRailFribbelsFormat::new(items))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat_value(stat: FribbelsStat) -> f64 {
+        serde_json::to_value(&stat).unwrap()["value"].as_f64().unwrap()
+    }
+
+    #[test]
+    fn test_percentage_values() {
+        let err = RelicStat { name: RelicStatName::EnergyRegenerationRate, value: 0.194, roll_count: None, impossible: false };
+        assert!((stat_value(FribbelsStat::main(&err)) - 19.4).abs() < 1e-9);
+
+        let spd = RelicStat { name: RelicStatName::SPD, value: 25.0, roll_count: None, impossible: false };
+        assert_eq!(stat_value(FribbelsStat::main(&spd)), 25.0);
+
+        let def = RelicStat { name: RelicStatName::DEF, value: 19.0, roll_count: None, impossible: false };
+        assert_eq!(stat_value(FribbelsStat::sub(&def)), 19.0);
+    }
+}
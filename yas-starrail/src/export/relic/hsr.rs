@@ -1,8 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ops::Deref;
+use anyhow::Result;
+use log::warn;
 use nanoid::nanoid;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde::ser::SerializeMap;
+use strum::IntoEnumIterator;
+use yas::export::ExportFormat;
+use crate::character::StarRailCharacter;
+use crate::light_cone::StarRailLightCone;
 use crate::relic::{RelicSetName, RelicSlot, RelicStat, RelicStatName, StarRailRelic};
 
 struct HSRRelic<'a>(&'a StarRailRelic);
@@ -15,9 +21,15 @@ impl<'a> Deref for HSRRelic<'a> {
     }
 }
 
+struct HSRLightCone<'a>(&'a StarRailLightCone);
+
 /// https://github.com/kel-z/HSR-Scanner
 pub struct StarRailHSRFormat<'a> {
     results: Vec<HSRRelic<'a>>,
+    /// `None` when not scanned, the section of `previous` is kept then
+    light_cones: Option<Vec<HSRLightCone<'a>>>,
+    characters: Option<Vec<&'a StarRailCharacter>>,
+    previous: Option<&'a serde_json::Value>,
     version: usize,
 }
 
@@ -29,9 +41,36 @@ impl<'a> StarRailHSRFormat<'a> {
         }
         Self {
             results: r,
+            light_cones: None,
+            characters: None,
+            previous: None,
             version: 3
         }
     }
+
+    pub fn with_light_cones(mut self, light_cones: &'a [StarRailLightCone]) -> Self {
+        self.light_cones = Some(light_cones.iter().map(HSRLightCone).collect());
+        self
+    }
+
+    pub fn with_characters(mut self, characters: &'a [StarRailCharacter]) -> Self {
+        self.characters = Some(characters.iter().collect());
+        self
+    }
+
+    /// Keep the light cones and characters of a previous export that are not scanned this time
+    pub fn with_previous(mut self, previous: Option<&'a serde_json::Value>) -> Self {
+        self.previous = previous;
+        self
+    }
+
+    fn serialize_section<M: SerializeMap, T: Serialize>(&self, root: &mut M, key: &str, scanned: &Option<Vec<T>>) -> Result<(), M::Error> {
+        match (scanned, self.previous.and_then(|x| x.get(key))) {
+            (Some(v), _) => root.serialize_entry(key, v),
+            (None, Some(v)) => root.serialize_entry(key, v),
+            (None, None) => root.serialize_entry(key, &Vec::<T>::new()),
+        }
+    }
 }
 
 impl RelicStatName {
@@ -64,46 +103,7 @@ impl RelicStatName {
 
 impl RelicSetName {
     pub fn to_hsr_set_name(&self) -> &'static str {
-        match *self {
-            RelicSetName::PasserbyofWanderingCloud => "Passerby of Wandering Cloud",
-            RelicSetName::MusketeerofWildWheat => "Musketeer of Wild Wheat",
-            RelicSetName::KnightofPurityPalace => "Knight of Purity Palace",
-            RelicSetName::HunterofGlacialForest => "Hunter of Glacial Forest",
-            RelicSetName::ChampionofStreetwiseBoxing => "Champion of Streetwise Boxing",
-            RelicSetName::GuardofWutheringSnow => "Guard of Wuthering Snow",
-            RelicSetName::FiresmithofLavaForging => "Firesmith of Lava-Forging",
-            RelicSetName::GeniusofBrilliantStars => "Genius of Brilliant Stars",
-            RelicSetName::BandofSizzlingThunder => "Band of Sizzling Thunder",
-            RelicSetName::EagleofTwilightLine => "Eagle of Twilight Line",
-            RelicSetName::ThiefofShootingMeteor => "Thief of Shooting Meteor",
-            RelicSetName::WastelanderofBanditryDesert => "Wastelander of Banditry Desert",
-            RelicSetName::LongevousDisciple => "Longevous Disciple",
-            RelicSetName::MessengerTraversingHackerspace => "Messenger Traversing Hackerspace",
-            RelicSetName::TheAshblazingGrandDuke => "The Ashblazing Grand Duke",
-            RelicSetName::PrisonerinDeepConfinement => "Prisoner in Deep Confinement",
-            RelicSetName::PioneerDiverofDeadWaters => "Pioneer Diver of Dead Waters",
-            RelicSetName::WatchmakerMasterofDreamMachinations => "Watchmaker, Master of Dream Machinations",
-            RelicSetName::IronCavalryAgainsttheScourge => "Iron Cavalry Against the Scourge",
-            RelicSetName::TheWindSoaringValorous => "The Wind-Soaring Valorous",
-            RelicSetName::SpaceSealingStation => "Space Sealing Station",
-            RelicSetName::FleetoftheAgeless => "Fleet of the Ageless",
-            RelicSetName::PanCosmicCommercialEnterprise => "Pan-Cosmic Commercial Enterprise",
-            RelicSetName::BelobogoftheArchitects => "Belobog's Fortress of Preservation",
-            RelicSetName::CelestialDifferentiator => "Celestial Differentiator",
-            RelicSetName::InertSalsotto => "Inert Salsotto",
-            RelicSetName::TaliaKingdomofBanditry => "Talia: Kingdom of Banditry",
-            RelicSetName::SprightlyVonwacq => "Sprightly Vonwacq",
-            RelicSetName::RutilantArena => "Rutilant Arena",
-            RelicSetName::BrokenKeel => "Broken Keel",
-            RelicSetName::FirmamentFrontlineGlamoth => "Firmament Frontline: Glamoth",
-            RelicSetName::PenaconyLandoftheDreams => "Penacony, Land of the Dreams",
-            RelicSetName::SigoniatheUnclaimedDesolation => "Sigonia, the Unclaimed Desolation",
-            RelicSetName::IzumoGenseiandTakamaDivineRealm => "Izumo Gensei and Takama Divine Realm",
-            RelicSetName::DuranDynastyofRunningWolves => "Duran, Dynasty of Running Wolves",
-            RelicSetName::ForgeoftheKalpagniLantern => "Forge of the Kalpagni Lantern",
-            RelicSetName::LushakatheSunkenSeas => "Lushaka, the Sunken Seas",
-            RelicSetName::TheWondrousBananAmusementPark => "The Wondrous BananAmusement Park",
-        }
+        self.name_en()
     }
 }
 
@@ -176,6 +176,52 @@ impl<'a> Serialize for HSRRelic<'a> {
     }
 }
 
+impl<'a> Serialize for HSRLightCone<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut root = serializer.serialize_map(Some(7))?;
+
+        root.serialize_entry("key", &self.0.name)?;
+        root.serialize_entry("level", &self.0.level)?;
+        root.serialize_entry("ascension", &self.0.ascension)?;
+        root.serialize_entry("superimposition", &self.0.superimposition)?;
+        root.serialize_entry("location", self.0.equip.as_deref().unwrap_or(""))?;
+        root.serialize_entry("lock", &self.0.lock)?;
+        root.serialize_entry("_id", &nanoid!())?;
+
+        root.end()
+    }
+}
+
+/// Characters only exist in the HSR format, so this is their only serialization
+impl Serialize for StarRailCharacter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let mut root = serializer.serialize_map(Some(6))?;
+
+        root.serialize_entry("key", &self.name)?;
+        root.serialize_entry("level", &self.level)?;
+        root.serialize_entry("ascension", &self.ascension)?;
+        root.serialize_entry("eidolon", &self.eidolon)?;
+
+        let mut skills: BTreeMap<&str, i32> = BTreeMap::new();
+        skills.insert("basic", self.skills.basic);
+        skills.insert("skill", self.skills.skill);
+        skills.insert("ult", self.skills.ult);
+        skills.insert("talent", self.skills.talent);
+        root.serialize_entry("skills", &skills)?;
+
+        let mut traces: BTreeMap<String, bool> = BTreeMap::new();
+        for (i, unlocked) in self.abilities.iter().enumerate() {
+            traces.insert(format!("ability_{}", i + 1), *unlocked);
+        }
+        for (i, unlocked) in self.stats.iter().enumerate() {
+            traces.insert(format!("stat_{}", i + 1), *unlocked);
+        }
+        root.serialize_entry("traces", &traces)?;
+
+        root.end()
+    }
+}
+
 impl<'a> Serialize for StarRailHSRFormat<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
         let mut root = serializer.serialize_map(None)?;
@@ -183,9 +229,9 @@ impl<'a> Serialize for StarRailHSRFormat<'a> {
         root.serialize_entry("source", "yas-scanner")?;
         root.serialize_entry("build", &(String::from("v") + env!("CARGO_PKG_VERSION")))?;
         root.serialize_entry("version", &self.version)?;
-        root.serialize_entry::<str, [usize; 0]>("light_cones", &[])?;
+        self.serialize_section(&mut root, "light_cones", &self.light_cones)?;
         root.serialize_entry("relics", &self.results)?;
-        root.serialize_entry::<str, [usize; 0]>("characters", &[])?;
+        self.serialize_section(&mut root, "characters", &self.characters)?;
 
         let mut metadata: HashMap<String, String> = HashMap::new();
         metadata.insert(String::from("trailblazer"), String::from("Stelle"));
@@ -194,3 +240,133 @@ impl<'a> Serialize for StarRailHSRFormat<'a> {
         root.end()
     }
 }
+
+#[derive(Deserialize)]
+struct HSRStatInput {
+    key: String,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+struct HSRRelicInput {
+    set: String,
+    slot: String,
+    rarity: i32,
+    level: i32,
+    mainstat: String,
+    #[serde(default)]
+    substats: Vec<HSRStatInput>,
+    #[serde(default)]
+    location: String,
+    #[serde(default)]
+    lock: bool,
+    #[serde(default)]
+    discard: bool,
+}
+
+#[derive(Deserialize)]
+struct HSRFormatInput {
+    #[serde(default)]
+    relics: Vec<HSRRelicInput>,
+}
+
+impl RelicStatName {
+    /// HP, ATK and DEF share a name with their percentage version, which is told apart by `is_percentage`
+    pub fn from_hsr_stat_name(name: &str, is_percentage: bool) -> Option<RelicStatName> {
+        let mut candidates = RelicStatName::iter().filter(|x| x.to_hsr_stat_name() == name);
+        let first = candidates.next()?;
+        match candidates.next() {
+            None => Some(first),
+            Some(second) => {
+                if first.is_percentage() == is_percentage { Some(first) } else { Some(second) }
+            },
+        }
+    }
+}
+
+impl RelicSetName {
+    pub fn from_hsr_set_name(name: &str) -> Option<RelicSetName> {
+        RelicSetName::iter().find(|x| x.to_hsr_set_name() == name)
+    }
+}
+
+impl RelicSlot {
+    pub fn from_hsr_slot_name(name: &str) -> Option<RelicSlot> {
+        RelicSlot::iter().find(|x| x.to_hsr_slot_name() == name)
+    }
+}
+
+impl HSRStatInput {
+    fn to_stat(&self) -> Option<RelicStat> {
+        let (name, is_percentage) = match self.key.strip_suffix('_') {
+            Some(name) => (name, true),
+            None => (self.key.as_str(), false),
+        };
+        let name = RelicStatName::from_hsr_stat_name(name, is_percentage)?;
+        let value = if is_percentage { self.value / 100.0 } else { self.value };
+        Some(RelicStat { name, value, roll_count: None, impossible: false })
+    }
+}
+
+impl HSRRelicInput {
+    fn to_relic(&self) -> Option<StarRailRelic> {
+        let slot = RelicSlot::from_hsr_slot_name(&self.slot)?;
+        // only head and hands have a flat HP or ATK main stat
+        let is_main_stat_percentage = !matches!(slot, RelicSlot::Head | RelicSlot::Hands);
+        let main_stat_name = RelicStatName::from_hsr_stat_name(&self.mainstat, is_main_stat_percentage)?;
+        // the main stat value is not stored, it is determined by the level
+        let main_stat_value = main_stat_name.main_stat_value(self.rarity, self.level).unwrap_or(0.0);
+
+        let mut sub_stats = self.substats.iter().map(|x| x.to_stat());
+
+        Some(StarRailRelic {
+            set_name: RelicSetName::from_hsr_set_name(&self.set)?,
+            slot,
+            star: self.rarity,
+            level: self.level,
+            main_stat: RelicStat { name: main_stat_name, value: main_stat_value, roll_count: None, impossible: false },
+            sub_stat_1: sub_stats.next().flatten(),
+            sub_stat_2: sub_stats.next().flatten(),
+            sub_stat_3: sub_stats.next().flatten(),
+            sub_stat_4: sub_stats.next().flatten(),
+            equip: if self.location.is_empty() { None } else { Some(self.location.clone()) },
+            lock: self.lock,
+            discard: self.discard,
+        })
+    }
+}
+
+/// Read the relics of a HSR-Scanner file, unrecognized relics are skipped
+pub fn relics_from_hsr(contents: &str) -> Result<Vec<StarRailRelic>> {
+    let input: HSRFormatInput = serde_json::from_str(contents)?;
+
+    let mut result = Vec::new();
+    for relic in input.relics.iter() {
+        match relic.to_relic() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别 HSR 遗器 {}/{}", relic.set, relic.slot),
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct HSRExportFormat;
+
+impl ExportFormat<StarRailRelic> for HSRExportFormat {
+    fn name(&self) -> &'static str {
+        "hsr"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "hsr.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "HSR遗器格式"
+    }
+
+    fn serialize(&self, items: &[StarRailRelic]) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&StarRailHSRFormat::new_version3(items))?)
+    }
+}
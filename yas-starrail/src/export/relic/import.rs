@@ -0,0 +1,144 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::relic::StarRailRelic;
+
+use super::hsr::relics_from_hsr;
+use super::import_format::StarRailRelicImportFormat;
+use super::march7th::relics_from_march7th;
+
+/// Detect the format of a previous export from its contents
+pub fn detect_relic_format(contents: &str) -> Result<StarRailRelicImportFormat> {
+    let value: serde_json::Value = serde_json::from_str(contents)?;
+
+    let format = if value.get("relics").is_some() {
+        StarRailRelicImportFormat::HSR
+    } else {
+        StarRailRelicImportFormat::March7th
+    };
+    Ok(format)
+}
+
+/// Read relics from the contents of a previous export in `format`
+pub fn relics_from_str(contents: &str, format: StarRailRelicImportFormat) -> Result<Vec<StarRailRelic>> {
+    match format {
+        StarRailRelicImportFormat::March7th => relics_from_march7th(contents),
+        StarRailRelicImportFormat::HSR => relics_from_hsr(contents),
+    }
+}
+
+/// Read relics from a previous HSR or march7th export, the format is detected from the contents
+pub fn load_relics<P: AsRef<Path>>(path: P) -> Result<Vec<StarRailRelic>> {
+    Ok(load_previous_export(path)?.relics)
+}
+
+/// A previous HSR or march7th export
+pub struct PreviousExport {
+    pub relics: Vec<StarRailRelic>,
+    /// The whole file, so that the sections a scan does not replace can be kept
+    pub root: serde_json::Value,
+}
+
+pub fn load_previous_export<P: AsRef<Path>>(path: P) -> Result<PreviousExport> {
+    let contents = std::fs::read_to_string(path)?;
+    let format = detect_relic_format(&contents)?;
+    Ok(PreviousExport {
+        relics: relics_from_str(&contents, format)?,
+        root: serde_json::from_str(&contents)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use yas::export::ExportFormat;
+
+    use crate::export::relic::hsr::{HSRExportFormat, StarRailHSRFormat};
+    use crate::export::relic::march7th::March7thExportFormat;
+    use crate::relic::{RelicSetName, RelicSlot, RelicStat, RelicStatName};
+
+    use super::*;
+
+    fn stat(name: RelicStatName, value: f64) -> RelicStat {
+        RelicStat { name, value, roll_count: None, impossible: false }
+    }
+
+    fn main_stat(name: RelicStatName, star: i32, level: i32) -> RelicStat {
+        let value = name.main_stat_value(star, level).unwrap();
+        stat(name, value)
+    }
+
+    fn relics() -> Vec<StarRailRelic> {
+        let head = StarRailRelic {
+            set_name: RelicSetName::PasserbyofWanderingCloud,
+            slot: RelicSlot::Head,
+            star: 5,
+            level: 15,
+            main_stat: main_stat(RelicStatName::HP, 5, 15),
+            sub_stat_1: Some(stat(RelicStatName::SPD, 4.0)),
+            sub_stat_2: Some(stat(RelicStatName::CRITRate, 0.058)),
+            sub_stat_3: Some(stat(RelicStatName::CRITDMG, 0.116)),
+            sub_stat_4: Some(stat(RelicStatName::ATK, 38.0)),
+            equip: Some(String::from("Kafka")),
+            lock: true,
+            discard: false,
+        };
+        let rope = StarRailRelic {
+            set_name: RelicSetName::IzumoGenseiandTakamaDivineRealm,
+            slot: RelicSlot::LinkRope,
+            star: 4,
+            level: 3,
+            main_stat: main_stat(RelicStatName::EnergyRegenerationRate, 4, 3),
+            sub_stat_1: Some(stat(RelicStatName::EffectRES, 0.034)),
+            sub_stat_2: Some(stat(RelicStatName::DEFPercentage, 0.043)),
+            sub_stat_3: None,
+            sub_stat_4: None,
+            equip: None,
+            lock: false,
+            discard: true,
+        };
+        vec![head, rope]
+    }
+
+    fn round_trip<F: ExportFormat<StarRailRelic>>(format: F, expected: StarRailRelicImportFormat) -> Vec<StarRailRelic> {
+        let contents = String::from_utf8(format.serialize(&relics()).unwrap()).unwrap();
+        let detected = detect_relic_format(&contents).unwrap();
+        assert_eq!(detected, expected);
+        relics_from_str(&contents, detected).unwrap()
+    }
+
+    #[test]
+    fn test_hsr_round_trip() {
+        let result = round_trip(HSRExportFormat, StarRailRelicImportFormat::HSR);
+        // the HSR format does not store the equipping character of a relic
+        let expected = relics().into_iter()
+            .map(|x| StarRailRelic { equip: None, ..x })
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_march7th_round_trip() {
+        let result = round_trip(March7thExportFormat, StarRailRelicImportFormat::March7th);
+        assert_eq!(result, relics());
+    }
+
+    #[test]
+    fn test_hsr_keeps_previous_sections() {
+        let relics = relics();
+        let previous = serde_json::json!({
+            "relics": [],
+            "light_cones": [{ "key": "Night on the Milky Way" }],
+            "characters": [{ "key": "Kafka" }],
+        });
+
+        let format = StarRailHSRFormat::new_version3(&relics)
+            .with_light_cones(&[])
+            .with_previous(Some(&previous));
+        let value = serde_json::to_value(&format).unwrap();
+
+        assert_eq!(value["light_cones"], serde_json::json!([]));
+        assert_eq!(value["characters"], previous["characters"]);
+        assert_eq!(value["relics"].as_array().unwrap().len(), 2);
+    }
+}
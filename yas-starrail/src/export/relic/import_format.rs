@@ -0,0 +1,7 @@
+use clap::ValueEnum;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum StarRailRelicImportFormat {
+    March7th,
+    HSR,
+}
@@ -0,0 +1,76 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::FromArgMatches;
+use log::error;
+
+use yas::export::{AssetEmitter, ExportAssets};
+
+use crate::light_cone::StarRailLightCone;
+use crate::relic::StarRailRelic;
+
+use super::config::ExportLightConeConfig;
+use super::hsr::StarRailHSRFormat;
+use super::import::load_previous_export;
+use super::march7th::March7thFormat;
+
+/// Exports light cones in the `light_cones` section of the HSR and march7th formats,
+/// together with the relics and the other sections of a previous export if `--relics` is given
+pub struct StarRailLightConeExporter<'a> {
+    /// `hsr`, `march7th` or `all`
+    pub format: String,
+    pub results: &'a [StarRailLightCone],
+    pub relics: Vec<StarRailRelic>,
+    /// The file given by `--relics`, its characters are kept in the HSR format
+    pub previous: Option<serde_json::Value>,
+    pub output_dir: PathBuf,
+}
+
+impl<'a> StarRailLightConeExporter<'a> {
+    pub fn new(arg_matches: &clap::ArgMatches, results: &'a [StarRailLightCone]) -> Result<Self> {
+        let config = ExportLightConeConfig::from_arg_matches(arg_matches)?;
+        let (relics, previous) = match config.relics.as_ref() {
+            Some(path) => {
+                let previous = load_previous_export(path)?;
+                (previous.relics, Some(previous.root))
+            },
+            None => (Vec::new(), None),
+        };
+        Ok(Self {
+            format: config.format,
+            results,
+            relics,
+            previous,
+            output_dir: PathBuf::from(&config.output_dir),
+        })
+    }
+
+    fn add_json<T: serde::Serialize>(&self, asset_bundle: &mut ExportAssets, name: &str, filename: &str, description: &str, value: &T) {
+        match serde_json::to_vec(value) {
+            Ok(contents) => asset_bundle.add_asset(
+                Some(String::from(name)),
+                self.output_dir.join(filename),
+                contents,
+                Some(String::from(description))
+            ),
+            Err(e) => error!("无法导出 {} 格式: {}", name, e),
+        }
+    }
+}
+
+impl<'a> AssetEmitter for StarRailLightConeExporter<'a> {
+    fn emit(&self, asset_bundle: &mut ExportAssets) {
+        let all = self.format == "all";
+
+        if all || self.format == "hsr" {
+            let format = StarRailHSRFormat::new_version3(&self.relics)
+                .with_light_cones(self.results)
+                .with_previous(self.previous.as_ref());
+            self.add_json(asset_bundle, "hsr", "hsr.json", "HSR光锥格式", &format);
+        }
+        if all || self.format == "march7th" {
+            let format = March7thFormat::new(&self.relics).with_light_cones(self.results);
+            self.add_json(asset_bundle, "march7th", "march7th.json", "三月七光锥格式", &format);
+        }
+    }
+}
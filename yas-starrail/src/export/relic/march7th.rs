@@ -1,6 +1,13 @@
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::Deserialize;
 use serde::ser::{Serialize, SerializeMap, Serializer};
 use std::convert::From;
+use strum::IntoEnumIterator;
 
+use yas::export::{ExportFormat, HistoryItem};
+
+use crate::light_cone::StarRailLightCone;
 use crate::relic::{
     RelicSetName, RelicSlot, RelicStat, RelicStatName, StarRailRelic,
 };
@@ -37,49 +44,9 @@ impl RelicStatName {
 }
 
 impl RelicSetName {
+    /// March7th uses the variant names
     pub fn to_march7th(&self) -> String {
-        let temp = match self {
-            RelicSetName::PasserbyofWanderingCloud => "PasserbyofWanderingCloud",
-            RelicSetName::MusketeerofWildWheat => "MusketeerofWildWheat",
-            RelicSetName::KnightofPurityPalace => "KnightofPurityPalace",
-            RelicSetName::HunterofGlacialForest => "HunterofGlacialForest",
-            RelicSetName::ChampionofStreetwiseBoxing => "ChampionofStreetwiseBoxing",
-            RelicSetName::GuardofWutheringSnow => "GuardofWutheringSnow",
-            RelicSetName::FiresmithofLavaForging => "FiresmithofLavaForging",
-            RelicSetName::GeniusofBrilliantStars => "GeniusofBrilliantStars",
-            RelicSetName::BandofSizzlingThunder => "BandofSizzlingThunder",
-            RelicSetName::EagleofTwilightLine => "EagleofTwilightLine",
-            RelicSetName::ThiefofShootingMeteor => "ThiefofShootingMeteor",
-            RelicSetName::WastelanderofBanditryDesert => "WastelanderofBanditryDesert",
-            RelicSetName::LongevousDisciple => "LongevousDisciple",
-            RelicSetName::MessengerTraversingHackerspace => "MessengerTraversingHackerspace",
-            RelicSetName::TheAshblazingGrandDuke => "TheAshblazingGrandDuke",
-            RelicSetName::PrisonerinDeepConfinement => "PrisonerinDeepConfinement",
-            RelicSetName::PioneerDiverofDeadWaters => "PioneerDiverofDeadWaters",
-            RelicSetName::WatchmakerMasterofDreamMachinations => "WatchmakerMasterofDreamMachinations",
-            RelicSetName::IronCavalryAgainsttheScourge => "IronCavalryAgainsttheScourge",
-            RelicSetName::TheWindSoaringValorous => "TheWindSoaringValorous",
-
-            RelicSetName::SpaceSealingStation => "SpaceSealingStation",
-            RelicSetName::FleetoftheAgeless => "FleetoftheAgeless",
-            RelicSetName::PanCosmicCommercialEnterprise => "PanCosmicCommercialEnterprise",
-            RelicSetName::BelobogoftheArchitects => "BelobogoftheArchitects",
-            RelicSetName::CelestialDifferentiator => "CelestialDifferentiator",
-            RelicSetName::InertSalsotto => "InertSalsotto",
-            RelicSetName::TaliaKingdomofBanditry => "TaliaKingdomofBanditry",
-            RelicSetName::SprightlyVonwacq => "SprightlyVonwacq",
-            RelicSetName::RutilantArena => "RutilantArena",
-            RelicSetName::BrokenKeel => "BrokenKeel",
-            RelicSetName::FirmamentFrontlineGlamoth => "FirmamentFrontlineGlamoth",
-            RelicSetName::PenaconyLandoftheDreams => "PenaconyLandoftheDreams",
-            RelicSetName::SigoniatheUnclaimedDesolation => "SigoniatheUnclaimedDesolation",
-            RelicSetName::IzumoGenseiandTakamaDivineRealm => "IzumoGenseiandTakamaDivineRealm",
-            RelicSetName::DuranDynastyofRunningWolves => "DuranDynastyofRunningWolves",
-            RelicSetName::ForgeoftheKalpagniLantern => "ForgeoftheKalpagniLantern",
-            RelicSetName::LushakatheSunkenSeas => "LushakatheSunkenSeas",
-            RelicSetName::TheWondrousBananAmusementPark => "TheWondrousBananAmusementPark",
-        };
-        String::from(temp)
+        self.to_string()
     }
 }
 
@@ -102,9 +69,15 @@ impl Serialize for RelicStat {
     where
         S: Serializer,
     {
-        let mut root = serializer.serialize_map(Some(2))?;
+        let mut root = serializer.serialize_map(None)?;
         root.serialize_entry("name", &self.name.to_march7th())?;
         root.serialize_entry("value", &self.value)?;
+        if let Some(roll_count) = self.roll_count {
+            root.serialize_entry("rollCount", &roll_count)?;
+        }
+        if self.impossible {
+            root.serialize_entry("impossible", &true)?;
+        }
         root.end()
     }
 }
@@ -145,6 +118,24 @@ impl Serialize for March7thRelic {
     }
 }
 
+type March7thLightCone = StarRailLightCone;
+
+impl Serialize for March7thLightCone {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut root = serializer.serialize_map(Some(6))?;
+        root.serialize_entry("name", &self.name)?;
+        root.serialize_entry("level", &self.level)?;
+        root.serialize_entry("ascension", &self.ascension)?;
+        root.serialize_entry("superimposition", &self.superimposition)?;
+        root.serialize_entry("equip", &self.equip)?;
+        root.serialize_entry("lock", &self.lock)?;
+        root.end()
+    }
+}
+
 pub struct March7thFormat<'a> {
     version: String,
     head: Vec<&'a March7thRelic>,
@@ -153,6 +144,7 @@ pub struct March7thFormat<'a> {
     feet: Vec<&'a March7thRelic>,
     sphere: Vec<&'a March7thRelic>,
     rope: Vec<&'a March7thRelic>,
+    light_cones: Vec<&'a March7thLightCone>,
 }
 
 impl<'a> Serialize for March7thFormat<'a> {
@@ -160,7 +152,7 @@ impl<'a> Serialize for March7thFormat<'a> {
     where
         S: Serializer,
     {
-        let mut root = serializer.serialize_map(Some(6))?;
+        let mut root = serializer.serialize_map(Some(8))?;
         root.serialize_entry("version", &self.version)?;
         root.serialize_entry("head", &self.head)?;
         root.serialize_entry("hands", &self.hands)?;
@@ -168,6 +160,7 @@ impl<'a> Serialize for March7thFormat<'a> {
         root.serialize_entry("feet", &self.feet)?;
         root.serialize_entry("planarSphere", &self.sphere)?;
         root.serialize_entry("linkRope", &self.rope)?;
+        root.serialize_entry("lightCones", &self.light_cones)?;
         root.end()
     }
 }
@@ -199,7 +192,159 @@ impl<'a> March7thFormat<'a> {
             feet,
             sphere,
             rope,
+            light_cones: Vec::new(),
             version: String::from("1"),
         }
     }
-}
\ No newline at end of file
+
+    pub fn with_light_cones(mut self, light_cones: &'a [StarRailLightCone]) -> Self {
+        self.light_cones = light_cones.iter().collect();
+        self
+    }
+}
+
+#[derive(Deserialize)]
+struct March7thStatInput {
+    name: String,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct March7thRelicInput {
+    set_name: String,
+    position: String,
+    main_tag: March7thStatInput,
+    #[serde(default)]
+    normal_tags: Vec<March7thStatInput>,
+    level: i32,
+    star: i32,
+    #[serde(default)]
+    equip: Option<String>,
+    #[serde(default)]
+    lock: bool,
+    #[serde(default)]
+    discard: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct March7thFormatInput {
+    #[serde(default)]
+    head: Vec<March7thRelicInput>,
+    #[serde(default)]
+    hands: Vec<March7thRelicInput>,
+    #[serde(default)]
+    body: Vec<March7thRelicInput>,
+    #[serde(default)]
+    feet: Vec<March7thRelicInput>,
+    #[serde(default)]
+    planar_sphere: Vec<March7thRelicInput>,
+    #[serde(default)]
+    link_rope: Vec<March7thRelicInput>,
+}
+
+impl RelicStatName {
+    pub fn from_march7th(name: &str) -> Option<RelicStatName> {
+        RelicStatName::iter().find(|x| x.to_march7th() == name)
+    }
+}
+
+impl RelicSetName {
+    pub fn from_march7th(name: &str) -> Option<RelicSetName> {
+        RelicSetName::iter().find(|x| x.to_march7th() == name)
+    }
+}
+
+impl RelicSlot {
+    pub fn from_march7th(name: &str) -> Option<RelicSlot> {
+        RelicSlot::iter().find(|x| x.to_march7th() == name)
+    }
+}
+
+impl March7thStatInput {
+    fn to_stat(&self) -> Option<RelicStat> {
+        Some(RelicStat {
+            name: RelicStatName::from_march7th(&self.name)?,
+            value: self.value,
+            roll_count: None,
+            impossible: false,
+        })
+    }
+}
+
+impl March7thRelicInput {
+    fn to_relic(&self) -> Option<StarRailRelic> {
+        let mut sub_stats = self.normal_tags.iter().map(|x| x.to_stat());
+
+        Some(StarRailRelic {
+            set_name: RelicSetName::from_march7th(&self.set_name)?,
+            slot: RelicSlot::from_march7th(&self.position)?,
+            star: self.star,
+            level: self.level,
+            main_stat: self.main_tag.to_stat()?,
+            sub_stat_1: sub_stats.next().flatten(),
+            sub_stat_2: sub_stats.next().flatten(),
+            sub_stat_3: sub_stats.next().flatten(),
+            sub_stat_4: sub_stats.next().flatten(),
+            equip: self.equip.clone(),
+            lock: self.lock,
+            discard: self.discard,
+        })
+    }
+}
+
+/// Read the relics of a march7th file, unrecognized relics are skipped
+pub fn relics_from_march7th(contents: &str) -> Result<Vec<StarRailRelic>> {
+    let input: March7thFormatInput = serde_json::from_str(contents)?;
+
+    let mut result = Vec::new();
+    for relic in input.head.iter()
+        .chain(input.hands.iter())
+        .chain(input.body.iter())
+        .chain(input.feet.iter())
+        .chain(input.planar_sphere.iter())
+        .chain(input.link_rope.iter())
+    {
+        match relic.to_relic() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别三月七遗器 {}/{}", relic.set_name, relic.position),
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct March7thExportFormat;
+
+impl ExportFormat<StarRailRelic> for March7thExportFormat {
+    fn name(&self) -> &'static str {
+        "march7th"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "march7th.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "三月七遗器格式"
+    }
+
+    fn serialize(&self, items: &[StarRailRelic]) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&March7thFormat::new(items))?)
+    }
+}
+
+/// Relics are stored in the history database as march7th relics
+impl HistoryItem for StarRailRelic {
+    const GAME: &'static str = "starrail";
+
+    fn to_history_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    fn from_history_json(json: &str) -> Result<Self> {
+        let input: March7thRelicInput = serde_json::from_str(json)?;
+        input.to_relic().ok_or_else(|| anyhow!("无法识别三月七遗器 {}/{}", input.set_name, input.position))
+    }
+}
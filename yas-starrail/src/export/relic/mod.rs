@@ -1,8 +1,17 @@
+pub use character_exporter::StarRailCharacterExporter;
 pub use exporter::StarRailRelicExporter;
-pub use config::ExportRelicConfig;
-pub use export_format::StarRailRelicExportFormat;
+pub use light_cone_exporter::StarRailLightConeExporter;
+pub use config::{ExportCharacterConfig, ExportLightConeConfig, ExportRelicConfig};
+pub use export_format::RELIC_EXPORT_FORMATS;
+pub use import::{detect_relic_format, load_relics, relics_from_str};
+pub use import_format::StarRailRelicImportFormat;
 mod march7th;
+mod character_exporter;
 mod exporter;
+mod light_cone_exporter;
 mod export_format;
 mod config;
 mod hsr;
+mod fribbels;
+mod import;
+mod import_format;
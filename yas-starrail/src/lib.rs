@@ -6,4 +6,6 @@ pub mod scanner_controller;
 pub mod export;
 pub mod scanner;
 pub mod relic;
+pub mod light_cone;
+pub mod character;
 pub mod application;
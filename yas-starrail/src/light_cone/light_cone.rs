@@ -0,0 +1,204 @@
+use anyhow::anyhow;
+use lazy_static::lazy_static;
+use log::warn;
+use regex::Regex;
+use yas::common::fuzzy_match::{FuzzyMatcher, FuzzyMatchResult};
+use crate::scanner::light_cone_scanner::StarRailLightConeScanResult;
+
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct StarRailLightCone {
+    /// The english name, e.g. `Cruising in the Stellar Sea`
+    pub name: String,
+    pub level: i32,
+    /// 0 to 6, each ascension raises the max level by 10
+    pub ascension: i32,
+    /// 1 to 5
+    pub superimposition: i32,
+    pub equip: Option<String>,
+    pub lock: bool,
+}
+
+// https://github.com/Mar-7th/StarRailRes/blob/master/index_new/cn/light_cones.json
+/// All light cone names in zh-cn, with their english names
+#[rustfmt::skip]
+const LIGHT_CONE_NAMES_ZH_CN: &[(&str, &str)] = &[
+    ("锋镝", "Arrows"),
+    ("物穰", "Cornucopia"),
+    ("天倾", "Collapsing Sky"),
+    ("琥珀", "Amber"),
+    ("幽邃", "Void"),
+    ("齐颂", "Chorus"),
+    ("智库", "Data Bank"),
+    ("离弦", "Darting Arrow"),
+    ("嘉果", "Fine Fruit"),
+    ("乐圮", "Shattered Home"),
+    ("戍御", "Defense"),
+    ("渊环", "Loop"),
+    ("轮契", "Meshing Cogs"),
+    ("灵钥", "Passkey"),
+    ("相抗", "Adversarial"),
+    ("蕃息", "Multiplication"),
+    ("俱殁", "Mutual Demise"),
+    ("开疆", "Pioneering"),
+    ("匿影", "Hidden Shadow"),
+    ("调和", "Mediation"),
+    ("睿见", "Sagacity"),
+
+    ("后会有期", "We Will Meet Again"),
+    ("我们是地火", "We Are Wildfire"),
+    ("春水初生", "River Flows in Spring"),
+    ("论剑", "Swordplay"),
+    ("唯有沉默", "Only Silence Remains"),
+    ("记忆中的模样", "Memories of the Past"),
+    ("鼹鼠党欢迎你", "The Moles Welcome You"),
+    ("天才们的休憩", "Geniuses' Repose"),
+    ("「我」的诞生", "The Birth of the Self"),
+    ("宇宙市场趋势", "Trend of the Universal Market"),
+    ("猎物的视线", "Eyes of the Prey"),
+    ("决心如汗珠般闪耀", "Resolution Shines As Pearls of Sweat"),
+    ("同一种心情", "Shared Feeling"),
+    ("此时恰好", "Perfect Timing"),
+    ("余生的第一天", "Day One of My New Life"),
+    ("朗道的选择", "Landau's Choice"),
+    ("镂月裁云之意", "Carve the Moon, Weave the Clouds"),
+    ("晚安与睡颜", "Good Night and Sleep Well"),
+    ("在蓝天下", "Under the Blue Sky"),
+    ("延长记号", "Fermata"),
+    ("过往未来", "Past and Future"),
+    ("点个关注吧！", "Subscribe for More!"),
+    ("舞！舞！舞！", "Dance! Dance! Dance!"),
+    ("一场术后对话", "Post-Op Conversation"),
+    ("等价交换", "Quid Pro Quo"),
+    ("与行星相会", "Planetary Rendezvous"),
+    ("这就是我啦！", "This Is Me!"),
+    ("秘密誓心", "A Secret Vow"),
+    ("无处可逃", "Nowhere to Run"),
+    ("在新手任务开始前", "Before the Tutorial Mission Starts"),
+    ("重返幽冥", "Return to Darkness"),
+    ("汪！散步时间！", "Woof! Walk Time!"),
+    ("早餐的仪式感", "The Seriousness of Breakfast"),
+    ("暖夜不会漫长", "Warmth Shortens Cold Nights"),
+    ("嘿，我在这儿", "Hey, Over Here"),
+    ("何物为真", "What Is Real?"),
+    ("铭记于心的约定", "Indelible Promise"),
+    ("两个人的演唱会", "Concert for Two"),
+    ("织造命运之线", "Destiny's Threads Forewoven"),
+    ("谐乐静默之后", "After the Charmony Fall"),
+    ("美梦小镇大冒险", "Dreamville Adventure"),
+    ("最后的赢家", "Final Victor"),
+
+    ("银河铁道之夜", "Night on the Milky Way"),
+    ("无可取代的东西", "Something Irreplaceable"),
+    ("但战斗还未结束", "But the Battle Isn't Over"),
+    ("以世界之名", "In the Name of the World"),
+    ("制胜的瞬间", "Moment of Victory"),
+    ("时节不居", "Time Waits for No One"),
+    ("如泥酣眠", "Sleep Like the Dead"),
+    ("星海巡航", "Cruising in the Stellar Sea"),
+    ("记一位星神的陨落", "On the Fall of an Aeon"),
+    ("记忆的质料", "Texture of Memories"),
+    ("于夜色中", "In the Night"),
+    ("拂晓之前", "Before Dawn"),
+    ("只需等待", "Patience Is All You Need"),
+    ("雨一直下", "Incessant Rain"),
+    ("棺的回响", "Echoes of the Coffin"),
+    ("到不了的彼岸", "The Unreachable Side"),
+    ("比阳光更明亮的", "Brighter Than the Sun"),
+    ("她已闭上双眼", "She Already Shut Her Eyes"),
+    ("此身为剑", "I Shall Be My Own Sword"),
+    ("烦恼着，幸福着", "Worrisome, Blissful"),
+    ("片刻，留在眼底", "An Instant Before A Gaze"),
+    ("惊魂夜", "Night of Fright"),
+    ("镜中故我", "Past Self in Mirror"),
+    ("纯粹思维的洗礼", "Baptism of Pure Thought"),
+    ("重塑时光之忆", "Reforged Remembrance"),
+    ("行于流逝的岸", "Along the Passing Shore"),
+    ("命运从未公平", "Inherently Unjust Destiny"),
+    ("游戏尘寰", "Earthly Escapade"),
+    ("夜色流光溢彩", "Flowing Nightglow"),
+    ("驶向第二次生命", "Sailing Towards a Second Life"),
+    ("梦应归于何处", "Whereabouts Should Dreams Rest"),
+    ("偏偏希望无价", "Yet Hope Is Priceless"),
+    ("落日时起舞", "Dance at Sunset"),
+    ("那无数个春天", "Those Many Springs"),
+    ("我将，巡征追猎", "I Venture Forth to Hunt"),
+    ("唯有香如故", "Scent Alone Stays True"),
+    ("回到大地的飞行", "A Grounded Ascent"),
+];
+
+lazy_static! {
+    static ref LIGHT_CONE_NAME_MATCHER: FuzzyMatcher<'static, &'static str> = FuzzyMatcher::new(
+        LIGHT_CONE_NAMES_ZH_CN.iter().map(|(name, en)| (*name, *en))
+    );
+}
+
+impl StarRailLightCone {
+    /// The english name of a light cone name in zh-cn
+    pub fn name_from_zh_cn(s: &str) -> Option<&'static str> {
+        match LIGHT_CONE_NAME_MATCHER.find(s) {
+            FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                warn!("light cone name `{}` is ambiguous: {:?}", s, candidates);
+                None
+            },
+            result => result.value(),
+        }
+    }
+}
+
+lazy_static! {
+    static ref LEVEL_RE: Regex = Regex::new(r"(\d+)\s*/\s*(\d+)").unwrap();
+    static ref DIGIT_RE: Regex = Regex::new(r"\d").unwrap();
+}
+
+/// `80/80` is level 80 with a max level of 80, which is reached after 6 ascensions
+fn parse_level_ascension(s: &str) -> Option<(i32, i32)> {
+    let cap = LEVEL_RE.captures(s)?;
+    let level = cap[1].parse::<i32>().ok()?;
+    let max_level = cap[2].parse::<i32>().ok()?;
+    if !(1..=80).contains(&level) || level > max_level {
+        return None;
+    }
+    let ascension = ((max_level - 20) / 10).clamp(0, 6);
+    Some((level, ascension))
+}
+
+/// `叠影1阶` is superimposition 1
+fn parse_superimposition(s: &str) -> Option<i32> {
+    let digit = DIGIT_RE.find(s)?;
+    let superimposition = digit.as_str().parse::<i32>().ok()?;
+    if (1..=5).contains(&superimposition) {
+        Some(superimposition)
+    } else {
+        None
+    }
+}
+
+impl TryFrom<&StarRailLightConeScanResult> for StarRailLightCone {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &StarRailLightConeScanResult) -> Result<Self, Self::Error> {
+        let name = StarRailLightCone::name_from_zh_cn(&value.name)
+            .ok_or_else(|| anyhow!("光锥的名称无法识别：`{}`", value.name))?;
+        let (level, ascension) = parse_level_ascension(&value.level)
+            .ok_or_else(|| anyhow!("光锥的等级无法识别：`{}`", value.level))?;
+        let superimposition = parse_superimposition(&value.superimposition)
+            .ok_or_else(|| anyhow!("光锥的叠影无法识别：`{}`", value.superimposition))?;
+
+        let equip = if value.equip.ends_with("装备中") {
+            let chars = value.equip.chars().collect::<Vec<_>>();
+            let equip_name = chars[..chars.len() - 3].iter().collect::<String>();
+            Some(equip_name)
+        } else {
+            None
+        };
+
+        Ok(StarRailLightCone {
+            name: String::from(name),
+            level,
+            ascension,
+            superimposition,
+            equip,
+            lock: value.lock,
+        })
+    }
+}
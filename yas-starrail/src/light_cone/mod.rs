@@ -0,0 +1,3 @@
+pub use light_cone::StarRailLightCone;
+
+mod light_cone;
@@ -0,0 +1,63 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// The field of a scan result that fails to convert
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RelicField {
+    Name,
+    MainStat,
+    /// The main stat value does not fit the level, and neither can be corrected
+    MainStatLevel,
+    /// The main stat cannot appear on the slot, e.g. SPD on Body
+    SlotMainStat,
+}
+
+impl RelicField {
+    pub fn to_zh_cn(&self) -> &'static str {
+        match *self {
+            RelicField::Name => "名称",
+            RelicField::MainStat => "主词条",
+            RelicField::MainStatLevel => "主词条与等级",
+            RelicField::SlotMainStat => "部位与主词条",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StarRailRelicConversionError {
+    /// Index of the item in the scan, `None` if it is not known
+    pub index: Option<usize>,
+    pub field: RelicField,
+    /// The OCR text of the field
+    pub raw: String,
+}
+
+impl StarRailRelicConversionError {
+    pub fn new(field: RelicField, raw: impl Into<String>) -> Self {
+        StarRailRelicConversionError {
+            index: None,
+            field,
+            raw: raw.into(),
+        }
+    }
+
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+}
+
+impl fmt::Display for StarRailRelicConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "第 {} 件遗器", index + 1)?,
+            None => write!(f, "遗器")?,
+        }
+        write!(f, "的{}无法识别：`{}`", self.field.to_zh_cn(), self.raw)
+    }
+}
+
+impl std::error::Error for StarRailRelicConversionError {}
@@ -0,0 +1,69 @@
+use yas::common::inventory_diff::InventoryItem;
+
+use crate::relic::{RelicStat, StarRailRelic};
+
+impl RelicStat {
+    /// The value as the game displays it
+    fn display_value(&self) -> String {
+        if self.name.is_percentage() {
+            format!("{:.1}%", self.value * 100.0)
+        } else {
+            format!("{:.0}", self.value)
+        }
+    }
+}
+
+impl StarRailRelic {
+    fn sub_stats(&self) -> impl Iterator<Item = &RelicStat> {
+        [&self.sub_stat_1, &self.sub_stat_2, &self.sub_stat_3, &self.sub_stat_4]
+            .into_iter()
+            .flatten()
+    }
+}
+
+impl InventoryItem for StarRailRelic {
+    fn is_same_item(&self, newer: &Self) -> bool {
+        if self.set_name != newer.set_name
+            || self.slot != newer.slot
+            || self.star != newer.star
+            || self.main_stat.name != newer.main_stat.name
+            || self.level > newer.level
+        {
+            return false;
+        }
+
+        // sub stats can only grow, and a 4th one can be added
+        self.sub_stats().all(|old| {
+            newer.sub_stats().any(|new| new.name == old.name && new.value >= old.value - 1e-6)
+        })
+    }
+
+    fn level(&self) -> i32 {
+        self.level
+    }
+
+    fn equip(&self) -> Option<&str> {
+        self.equip.as_deref()
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "{} {:?} {}★ +{} {:?}",
+            self.set_name, self.slot, self.star, self.level, self.main_stat.name
+        )
+    }
+
+    fn sub_stat_changes(&self, newer: &Self) -> Vec<String> {
+        let mut result = Vec::new();
+        for new in newer.sub_stats() {
+            match self.sub_stats().find(|old| old.name == new.name) {
+                None => result.push(format!("{:?} {}（新增）", new.name, new.display_value())),
+                Some(old) if old.display_value() != new.display_value() => {
+                    result.push(format!("{:?} {} → {}", new.name, old.display_value(), new.display_value()));
+                },
+                _ => {},
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,129 @@
+use log::warn;
+
+use yas::common::main_stat_check::MainStatCheck;
+use yas::common::sub_stat_roll::RollItem;
+
+use crate::relic::{RelicStatName, StarRailRelic};
+
+impl RelicStatName {
+    // https://honkai-star-rail.fandom.com/wiki/Relic/Stats#Main_Stats
+    /// The main stat value at level 0 and the increase per level, in displayed unit
+    #[rustfmt::skip]
+    fn main_stat_base_and_step(&self, star: i32) -> Option<(f64, f64)> {
+        use RelicStatName::*;
+
+        let v = match (self, star) {
+            (HP, 5) => (112.896, 39.5136),
+            (ATK, 5) => (56.448, 19.7568),
+            (HPPercentage | ATKPercentage | EffectHitRate, 5) => (6.912, 2.4192),
+            (DEFPercentage, 5) => (8.64, 3.024),
+            (CRITRate, 5) => (5.184, 1.8144),
+            (CRITDMG | BreakEffect, 5) => (10.368, 3.6288),
+            (OutgoingHealingBoost, 5) => (5.5296, 1.9354),
+            (SPD, 5) => (4.032, 1.4),
+            (EnergyRegenerationRate, 5) => (3.1104, 1.0886),
+            (PhysicalDMGBoost | FireDMGBoost | IceDMGBoost | LightningDMGBoost | WindDMGBoost | QuantumDMGBoost | ImaginaryDMGBoost, 5) => (6.2208, 2.1773),
+
+            (HP, 4) => (90.3168, 31.6108),
+            (ATK, 4) => (45.1584, 15.8054),
+            (HPPercentage | ATKPercentage | EffectHitRate, 4) => (5.5296, 1.9354),
+            (DEFPercentage, 4) => (6.912, 2.4192),
+            (CRITRate, 4) => (4.1472, 1.4515),
+            (CRITDMG | BreakEffect, 4) => (8.2944, 2.903),
+            (OutgoingHealingBoost, 4) => (4.4237, 1.5483),
+            (SPD, 4) => (3.2256, 1.1),
+            (EnergyRegenerationRate, 4) => (2.4883, 0.8709),
+            (PhysicalDMGBoost | FireDMGBoost | IceDMGBoost | LightningDMGBoost | WindDMGBoost | QuantumDMGBoost | ImaginaryDMGBoost, 4) => (4.9766, 1.7418),
+
+            (HP, 3) => (67.7376, 23.7082),
+            (ATK, 3) => (33.8688, 11.8541),
+            (HPPercentage | ATKPercentage | EffectHitRate, 3) => (4.1472, 1.4515),
+            (DEFPercentage, 3) => (5.184, 1.8144),
+            (CRITRate, 3) => (3.1104, 1.0886),
+            (CRITDMG | BreakEffect, 3) => (6.2208, 2.1773),
+            (OutgoingHealingBoost, 3) => (3.3178, 1.1612),
+            (SPD, 3) => (2.4192, 1.0),
+            (EnergyRegenerationRate, 3) => (1.8662, 0.6532),
+            (PhysicalDMGBoost | FireDMGBoost | IceDMGBoost | LightningDMGBoost | WindDMGBoost | QuantumDMGBoost | ImaginaryDMGBoost, 3) => (3.7325, 1.3064),
+
+            (HP, 2) => (45.1584, 15.8054),
+            (ATK, 2) => (22.5792, 7.9027),
+            (HPPercentage | ATKPercentage | EffectHitRate, 2) => (2.7648, 0.9677),
+            (DEFPercentage, 2) => (3.456, 1.2096),
+            (CRITRate, 2) => (2.0736, 0.7258),
+            (CRITDMG | BreakEffect, 2) => (4.1472, 1.4515),
+            (OutgoingHealingBoost, 2) => (2.2118, 0.7741),
+            (SPD, 2) => (1.6128, 1.0),
+            (EnergyRegenerationRate, 2) => (1.2442, 0.4355),
+            (PhysicalDMGBoost | FireDMGBoost | IceDMGBoost | LightningDMGBoost | WindDMGBoost | QuantumDMGBoost | ImaginaryDMGBoost, 2) => (2.4883, 0.8709),
+
+            _ => return None,
+        };
+        Some(v)
+    }
+
+    /// The main stat value of every level up to the max level of `star`, in displayed unit
+    pub fn main_stat_curve(&self, star: i32) -> Option<Vec<f64>> {
+        let (base, step) = self.main_stat_base_and_step(star)?;
+        let max_level = star * 3;
+        Some((0..=max_level).map(|level| base + step * level as f64).collect())
+    }
+
+    /// The main stat value at `level` as the game displays it, in the unit stored in `RelicStat`
+    pub fn main_stat_value(&self, star: i32, level: i32) -> Option<f64> {
+        let curve = self.main_stat_curve(star)?;
+        let value = *curve.get(usize::try_from(level).ok()?)?;
+        let value = if self.is_main_stat_percentage() {
+            (value * 10.0).floor() / 1000.0
+        } else {
+            value.floor()
+        };
+        Some(value)
+    }
+
+    /// Whether the value of this stat is shown with a `%`, as a main stat
+    pub fn is_main_stat_percentage(&self) -> bool {
+        !matches!(self, RelicStatName::HP | RelicStatName::ATK | RelicStatName::SPD)
+    }
+}
+
+impl StarRailRelic {
+    /// Cross-check the main stat value and the level, and correct whichever is misread.
+    ///
+    /// Sub stat rolls are checked again if the level changes
+    pub fn check_main_stat(&mut self) -> MainStatCheck {
+        let curve = match self.main_stat.name.main_stat_curve(self.star) {
+            Some(v) => v,
+            None => return MainStatCheck::Unchecked,
+        };
+
+        // the game truncates the displayed value, so compare from the middle of the truncated range
+        let is_percentage = self.main_stat.name.is_main_stat_percentage();
+        let (value, unit) = if is_percentage {
+            (self.main_stat.value * 100.0, 0.1)
+        } else {
+            (self.main_stat.value, 1.0)
+        };
+
+        let check = MainStatCheck::check(&curve, value + unit / 2.0, self.level, unit);
+        match &check {
+            MainStatCheck::LevelCorrected { read, inferred } => {
+                warn!("{} 主词条 {:?}+{} 与等级 {} 不符，修正等级为 {}", self.set_name, self.main_stat.name, value, read, inferred);
+                self.level = *inferred;
+                self.check_sub_stat_rolls();
+            },
+            MainStatCheck::ValueCorrected { expected, .. } => {
+                // keep the value as the game would display it
+                let expected = (expected / unit).floor() * unit;
+                warn!("{} 主词条 {:?}+{} 与等级 {} 不符，修正为 {}", self.set_name, self.main_stat.name, value, self.level, expected);
+                self.main_stat.value = if is_percentage { expected / 100.0 } else { expected };
+            },
+            MainStatCheck::Rejected => {
+                warn!("{} 主词条 {:?}+{} 与等级 {} 均不可信", self.set_name, self.main_stat.name, value, self.level);
+            },
+            _ => {},
+        }
+
+        check
+    }
+}
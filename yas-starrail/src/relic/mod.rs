@@ -3,5 +3,13 @@ pub use relic::RelicSetName;
 pub use relic::RelicStat;
 pub use relic::RelicStatName;
 pub use relic::StarRailRelic;
+pub use conversion_error::{RelicField, StarRailRelicConversionError};
+pub use slot_rules::check_slot_rules;
 
 mod relic;
+mod conversion_error;
+mod inventory_diff;
+mod main_stat_curve;
+mod template_item;
+mod sub_stat_roll;
+mod slot_rules;
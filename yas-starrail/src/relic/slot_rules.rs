@@ -0,0 +1,41 @@
+use crate::relic::{RelicField, RelicSlot, RelicStatName, StarRailRelicConversionError};
+
+impl RelicSlot {
+    // https://honkai-star-rail.fandom.com/wiki/Relic/Stats#Main_Stats
+    /// The main stats that can appear on this slot
+    #[rustfmt::skip]
+    pub fn main_stat_names(&self) -> &'static [RelicStatName] {
+        use RelicStatName::*;
+
+        match *self {
+            RelicSlot::Head => &[HP],
+            RelicSlot::Hands => &[ATK],
+            RelicSlot::Body => &[HPPercentage, ATKPercentage, DEFPercentage, CRITRate, CRITDMG, OutgoingHealingBoost, EffectHitRate],
+            RelicSlot::Feet => &[HPPercentage, ATKPercentage, DEFPercentage, SPD],
+            RelicSlot::PlanarSphere => &[
+                HPPercentage, ATKPercentage, DEFPercentage,
+                PhysicalDMGBoost, FireDMGBoost, IceDMGBoost, LightningDMGBoost, WindDMGBoost, QuantumDMGBoost, ImaginaryDMGBoost,
+            ],
+            RelicSlot::LinkRope => &[HPPercentage, ATKPercentage, DEFPercentage, BreakEffect, EnergyRegenerationRate],
+        }
+    }
+
+    pub fn is_valid_main_stat(&self, name: &RelicStatName) -> bool {
+        self.main_stat_names().contains(name)
+    }
+}
+
+/// Check that the main stat can appear on the slot.
+///
+/// The set needs no check, as the set and the slot are both read from the piece name.
+/// `main_stat_raw` is the OCR text reported when the rule is violated
+pub fn check_slot_rules(
+    slot: &RelicSlot,
+    main_stat: &RelicStatName,
+    main_stat_raw: &str,
+) -> Result<(), StarRailRelicConversionError> {
+    if !slot.is_valid_main_stat(main_stat) {
+        return Err(StarRailRelicConversionError::new(RelicField::SlotMainStat, main_stat_raw));
+    }
+    Ok(())
+}
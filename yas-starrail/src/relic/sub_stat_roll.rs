@@ -0,0 +1,141 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use yas::common::sub_stat_roll::{RollItem, RollStat, RollTable};
+
+use crate::relic::{RelicStat, RelicStatName, StarRailRelic};
+
+impl RelicStatName {
+    // https://honkai-star-rail.fandom.com/wiki/Relic/Stats#Sub_Stats
+    /// The values a single roll of this sub stat can take, in displayed unit.
+    /// Returns `None` if it cannot be a sub stat of a relic of this star
+    #[rustfmt::skip]
+    pub fn roll_table(&self, star: i32) -> Option<RollTable> {
+        // tier values are rounded to 3 decimals
+        const fn t(tiers: &'static [f64]) -> Option<RollTable> {
+            Some(RollTable::new(tiers, 0.0005))
+        }
+
+        match (self, star) {
+            (RelicStatName::HP, 5) => t(&[33.870, 38.104, 42.338]),
+            (RelicStatName::ATK, 5) => t(&[16.935, 19.052, 21.169]),
+            (RelicStatName::DEF, 5) => t(&[16.935, 19.052, 21.169]),
+            (RelicStatName::HPPercentage, 5) => t(&[3.456, 3.888, 4.320]),
+            (RelicStatName::ATKPercentage, 5) => t(&[3.456, 3.888, 4.320]),
+            (RelicStatName::DEFPercentage, 5) => t(&[4.320, 4.860, 5.400]),
+            (RelicStatName::SPD, 5) => t(&[2.0, 2.3, 2.6]),
+            (RelicStatName::CRITRate, 5) => t(&[2.592, 2.916, 3.240]),
+            (RelicStatName::CRITDMG, 5) => t(&[5.184, 5.832, 6.480]),
+            (RelicStatName::EffectHitRate, 5) => t(&[3.456, 3.888, 4.320]),
+            (RelicStatName::EffectRES, 5) => t(&[3.456, 3.888, 4.320]),
+            (RelicStatName::BreakEffect, 5) => t(&[5.184, 5.832, 6.480]),
+
+            (RelicStatName::HP, 4) => t(&[27.096, 30.483, 33.870]),
+            (RelicStatName::ATK, 4) => t(&[13.548, 15.242, 16.935]),
+            (RelicStatName::DEF, 4) => t(&[13.548, 15.242, 16.935]),
+            (RelicStatName::HPPercentage, 4) => t(&[2.765, 3.110, 3.456]),
+            (RelicStatName::ATKPercentage, 4) => t(&[2.765, 3.110, 3.456]),
+            (RelicStatName::DEFPercentage, 4) => t(&[3.456, 3.888, 4.320]),
+            (RelicStatName::SPD, 4) => t(&[1.6, 1.8, 2.0]),
+            (RelicStatName::CRITRate, 4) => t(&[2.074, 2.333, 2.592]),
+            (RelicStatName::CRITDMG, 4) => t(&[4.147, 4.666, 5.184]),
+            (RelicStatName::EffectHitRate, 4) => t(&[2.765, 3.110, 3.456]),
+            (RelicStatName::EffectRES, 4) => t(&[2.765, 3.110, 3.456]),
+            (RelicStatName::BreakEffect, 4) => t(&[4.147, 4.666, 5.184]),
+
+            (RelicStatName::HP, 3) => t(&[20.322, 22.862, 25.403]),
+            (RelicStatName::ATK, 3) => t(&[10.161, 11.431, 12.701]),
+            (RelicStatName::DEF, 3) => t(&[10.161, 11.431, 12.701]),
+            (RelicStatName::HPPercentage, 3) => t(&[2.074, 2.333, 2.592]),
+            (RelicStatName::ATKPercentage, 3) => t(&[2.074, 2.333, 2.592]),
+            (RelicStatName::DEFPercentage, 3) => t(&[2.592, 2.916, 3.240]),
+            (RelicStatName::SPD, 3) => t(&[1.2, 1.3, 1.4]),
+            (RelicStatName::CRITRate, 3) => t(&[1.555, 1.750, 1.944]),
+            (RelicStatName::CRITDMG, 3) => t(&[3.110, 3.499, 3.888]),
+            (RelicStatName::EffectHitRate, 3) => t(&[2.074, 2.333, 2.592]),
+            (RelicStatName::EffectRES, 3) => t(&[2.074, 2.333, 2.592]),
+            (RelicStatName::BreakEffect, 3) => t(&[3.110, 3.499, 3.888]),
+
+            (RelicStatName::HP, 2) => t(&[13.548, 15.242, 16.935]),
+            (RelicStatName::ATK, 2) => t(&[6.774, 7.621, 8.468]),
+            (RelicStatName::DEF, 2) => t(&[6.774, 7.621, 8.468]),
+            (RelicStatName::HPPercentage, 2) => t(&[1.382, 1.555, 1.728]),
+            (RelicStatName::ATKPercentage, 2) => t(&[1.382, 1.555, 1.728]),
+            (RelicStatName::DEFPercentage, 2) => t(&[1.728, 1.944, 2.160]),
+            (RelicStatName::SPD, 2) => t(&[1.0, 1.1, 1.2]),
+            (RelicStatName::CRITRate, 2) => t(&[1.037, 1.166, 1.296]),
+            (RelicStatName::CRITDMG, 2) => t(&[2.074, 2.333, 2.592]),
+            (RelicStatName::EffectHitRate, 2) => t(&[1.382, 1.555, 1.728]),
+            (RelicStatName::EffectRES, 2) => t(&[1.382, 1.555, 1.728]),
+            (RelicStatName::BreakEffect, 2) => t(&[2.074, 2.333, 2.592]),
+
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for RelicStat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}+{}", self.name, self.value)
+    }
+}
+
+impl RollStat for RelicStat {
+    fn roll_table(&self, star: i32) -> Option<RollTable> {
+        self.name.roll_table(star)
+    }
+
+    /// The game truncates the displayed value, to 1 decimal for percentages and to integer otherwise
+    fn displayed_value(&self) -> (f64, f64) {
+        let (value, unit) = if self.name.is_percentage() {
+            (self.value * 100.0, 0.1)
+        } else {
+            (self.value, 1.0)
+        };
+        (value + unit / 2.0, unit / 2.0)
+    }
+
+    fn value(&self) -> f64 {
+        self.value
+    }
+
+    fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    fn set_roll_count(&mut self, roll_count: Option<usize>) {
+        self.roll_count = roll_count;
+    }
+
+    fn set_impossible(&mut self, impossible: bool) {
+        self.impossible = impossible;
+    }
+}
+
+impl RollItem for StarRailRelic {
+    type Stat = RelicStat;
+
+    const ITEM_NAME: &'static str = "遗器";
+    const LEVELS_PER_UPGRADE: i32 = 3;
+    const ROLL_TABLE_STARS: RangeInclusive<i32> = 2..=5;
+
+    fn star(&self) -> i32 {
+        self.star
+    }
+
+    fn level(&self) -> i32 {
+        self.level
+    }
+
+    fn sub_stats_mut(&mut self) -> Vec<&mut RelicStat> {
+        [
+            &mut self.sub_stat_1,
+            &mut self.sub_stat_2,
+            &mut self.sub_stat_3,
+            &mut self.sub_stat_4,
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
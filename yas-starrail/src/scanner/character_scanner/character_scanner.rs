@@ -0,0 +1,238 @@
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use anyhow::Result;
+use clap::FromArgMatches;
+use image::{GenericImageView, Rgb, RgbImage};
+use log::{error, info};
+
+use yas::capture::{Capturer, GenericCapturer};
+use yas::common::cancel::CancellationToken;
+use yas::export::{emit_error, emit_item, emit_progress, emit_status, is_event_stream_active};
+use yas::game_info::GameInfo;
+use yas::ocr::{ImageToText, yas_ocr_model};
+use yas::positioning::{Pos, Rect};
+use yas::system_control::SystemControl;
+use yas::utils;
+use yas::utils::color_distance;
+use yas::window_info::{FromWindowInfoRepository, WindowInfoRepository};
+
+use crate::character::StarRailCharacter;
+use crate::scanner::character_scanner::character_scanner_window_info::CharacterScannerWindowInfo;
+use crate::scanner::character_scanner::match_colors::{MATCH_COLORS, MatchColors};
+use crate::scanner::character_scanner::scan_result::StarRailCharacterScanResult;
+
+use super::character_scanner_config::StarRailCharacterScannerConfig;
+
+/// Scans the character screen, one character at a time through the character list.
+///
+/// Unlike the inventory, the character list is not a grid, so this does not use
+/// `StarRailRepositoryScanController`
+pub struct StarRailCharacterScanner {
+    scanner_config: StarRailCharacterScannerConfig,
+    window_info: CharacterScannerWindowInfo,
+    game_info: GameInfo,
+    image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
+    system_control: SystemControl,
+    capturer: Rc<dyn Capturer<RgbImage>>,
+    cancellation_token: CancellationToken,
+
+    match_colors: MatchColors,
+}
+
+// constructor
+impl StarRailCharacterScanner {
+    fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+        let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
+            yas_ocr_model!("../relic_scanner/models/model_training.onnx", "../relic_scanner/models/index_2_word.json")?
+        );
+        Ok(model)
+    }
+
+    fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
+        Ok(Rc::new(GenericCapturer::new()?))
+    }
+
+    pub fn from_arg_matches(
+        window_info_repo: &WindowInfoRepository,
+        arg_matches: &clap::ArgMatches,
+        game_info: GameInfo,
+    ) -> Result<Self> {
+        let window_info = CharacterScannerWindowInfo::from_window_info_repository(
+            game_info.window.to_rect_usize().size(),
+            game_info.ui,
+            game_info.platform,
+            window_info_repo
+        )?;
+        Ok(StarRailCharacterScanner {
+            scanner_config: StarRailCharacterScannerConfig::from_arg_matches(arg_matches)?,
+            window_info,
+            game_info,
+            image_to_text: Self::get_image_to_text()?,
+            system_control: SystemControl::new(),
+            capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
+            match_colors: MATCH_COLORS,
+        })
+    }
+}
+
+impl StarRailCharacterScanner {
+    /// Stop scanning once `token` is cancelled, the characters scanned so far are kept
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
+    fn click(&mut self, pos: Pos<f64>) -> Result<()> {
+        let origin = self.game_info.window.to_rect_f64().origin();
+        self.system_control.mouse_move_to((origin.x + pos.x) as i32, (origin.y + pos.y) as i32)?;
+
+        #[cfg(target_os = "macos")]
+        utils::sleep(20);
+
+        self.system_control.mouse_click()?;
+        utils::sleep(self.scanner_config.wait_switch_character);
+        Ok(())
+    }
+
+    fn capture_window(&self) -> Result<RgbImage> {
+        self.capturer.capture_rect(self.game_info.window)
+    }
+
+    fn ocr(&self, rect: Rect<f64>, window_image: &RgbImage) -> Result<String> {
+        let image = window_image.view(
+            rect.left as u32, rect.top as u32, rect.width as u32, rect.height as u32
+        ).to_image();
+        self.image_to_text.image_to_text(&image, false)
+    }
+
+    /// Whether the node at `pos` has the first of the two colors
+    fn is_unlocked(window_image: &RgbImage, pos: Pos<f64>, colors: &[Rgb<u8>; 2]) -> bool {
+        let color = window_image.get_pixel(pos.x as u32, pos.y as u32);
+        color_distance(&colors[0], color) < color_distance(&colors[1], color)
+    }
+
+    fn scan_character(&mut self) -> Result<StarRailCharacterScanResult> {
+        let info = self.window_info.clone();
+
+        self.click(info.details_tab_pos)?;
+        let image = self.capture_window()?;
+        let name = self.ocr(info.name_rect, &image)?;
+        let level = self.ocr(info.level_rect, &image)?;
+
+        self.click(info.traces_tab_pos)?;
+        let image = self.capture_window()?;
+        let skills = [
+            self.ocr(info.basic_rect, &image)?,
+            self.ocr(info.skill_rect, &image)?,
+            self.ocr(info.ult_rect, &image)?,
+            self.ocr(info.talent_rect, &image)?,
+        ];
+        let trace_colors = &self.match_colors.match_colors_trace;
+        let abilities = [info.ability_pos_1, info.ability_pos_2, info.ability_pos_3]
+            .map(|pos| Self::is_unlocked(&image, pos, trace_colors));
+        let stats = [
+            info.stat_pos_1, info.stat_pos_2, info.stat_pos_3, info.stat_pos_4, info.stat_pos_5,
+            info.stat_pos_6, info.stat_pos_7, info.stat_pos_8, info.stat_pos_9, info.stat_pos_10,
+        ].map(|pos| Self::is_unlocked(&image, pos, trace_colors));
+
+        self.click(info.eidolons_tab_pos)?;
+        let image = self.capture_window()?;
+        let eidolon_colors = &self.match_colors.match_colors_eidolon;
+        let eidolons = [
+            info.eidolon_pos_1, info.eidolon_pos_2, info.eidolon_pos_3,
+            info.eidolon_pos_4, info.eidolon_pos_5, info.eidolon_pos_6,
+        ].map(|pos| Self::is_unlocked(&image, pos, eidolon_colors));
+
+        Ok(StarRailCharacterScanResult {
+            name,
+            level,
+            skills,
+            abilities,
+            stats,
+            eidolons,
+        })
+    }
+
+    /// Select the `index`th character of the list, scrolling the list once the visible avatars run out
+    fn select_character(&mut self, index: usize) -> Result<()> {
+        let visible = self.window_info.list_count as usize;
+        let slot = index.min(visible - 1);
+        let pos = Pos {
+            x: self.window_info.list_first_pos.x + self.window_info.list_step.x * slot as f64,
+            y: self.window_info.list_first_pos.y + self.window_info.list_step.y * slot as f64,
+        };
+
+        if index >= visible {
+            let origin = self.game_info.window.to_rect_f64().origin();
+            self.system_control.mouse_move_to((origin.x + pos.x) as i32, (origin.y + pos.y) as i32)?;
+            #[cfg(not(target_os = "macos"))]
+            self.system_control.mouse_scroll(1, false)?;
+            #[cfg(target_os = "macos")]
+            self.system_control.mouse_scroll(1)?;
+            utils::sleep(self.scanner_config.scroll_delay);
+        }
+
+        self.click(pos)
+    }
+
+    pub fn scan(&mut self) -> Result<Vec<StarRailCharacterScanResult>> {
+        info!("开始扫描，使用鼠标右键中断扫描");
+        emit_status("scanning");
+
+        let now = SystemTime::now();
+        let count = if self.scanner_config.number > 0 {
+            Some(self.scanner_config.number as usize)
+        } else {
+            None
+        };
+
+        let mut results: Vec<StarRailCharacterScanResult> = Vec::new();
+        let mut index = 0;
+        loop {
+            if count.is_some_and(|c| index >= c) {
+                break;
+            }
+            if utils::is_rmb_down() {
+                info!("用户中断");
+                break;
+            }
+            if self.cancellation_token.cancelled() {
+                info!("扫描任务被取消");
+                break;
+            }
+
+            self.select_character(index)?;
+            let result = match self.scan_character() {
+                Ok(v) => v,
+                Err(e) => {
+                    error!("扫描发生错误：{}", e);
+                    break;
+                },
+            };
+
+            if self.scanner_config.verbose {
+                info!("{:?}", result);
+            }
+
+            // scrolling past the end of the list selects the last character again
+            if results.last().is_some_and(|x| x.name == result.name) {
+                info!("到达角色列表末尾，准备退出……");
+                break;
+            }
+
+            if is_event_stream_active() {
+                match StarRailCharacter::try_from(&result) {
+                    Ok(character) => emit_item(results.len(), &character),
+                    Err(e) => emit_error(&e.to_string()),
+                }
+            }
+            results.push(result);
+            index += 1;
+            emit_progress(index, count.unwrap_or(0));
+        }
+
+        info!("扫描结束，共 {} 个角色，耗时: {:?}", results.len(), now.elapsed()?);
+        Ok(results)
+    }
+}
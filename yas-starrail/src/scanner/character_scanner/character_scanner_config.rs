@@ -0,0 +1,16 @@
+#[derive(Clone, clap::Args)]
+pub struct StarRailCharacterScannerConfig {
+    #[arg(id = "verbose", long, help = "显示详细信息")]
+    pub verbose: bool,
+
+    #[arg(id = "number", long, help = "指定角色数量，默认扫描到列表末尾", value_name = "NUMBER", default_value_t = -1)]
+    pub number: i32,
+
+    /// The time to wait for switching characters and tabs
+    #[arg(id = "wait-switch-character", long = "wait-switch-character", help = "切换角色和页面的等待时间（ms）", default_value_t = 600)]
+    pub wait_switch_character: u32,
+
+    /// The time to wait for scrolling the character list
+    #[arg(id = "character-scroll-delay", long = "character-scroll-delay", help = "滚动角色列表的等待时间（ms）", default_value_t = 200)]
+    pub scroll_delay: u32,
+}
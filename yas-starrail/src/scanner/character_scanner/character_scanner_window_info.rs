@@ -0,0 +1,84 @@
+use yas::positioning::{Pos, Rect};
+
+#[derive(Clone, yas_derive::YasWindowInfo, Debug)]
+pub struct CharacterScannerWindowInfo {
+    #[window_info(rename = "starrail_character_name_rect")]
+    pub name_rect: Rect<f64>,
+
+    /// the level and the max level on the details tab, e.g. `80/80`
+    #[window_info(rename = "starrail_character_level_rect")]
+    pub level_rect: Rect<f64>,
+
+    /// the first avatar of the character list
+    #[window_info(rename = "starrail_character_list_first_pos")]
+    pub list_first_pos: Pos<f64>,
+
+    /// the offset from an avatar of the character list to the next one
+    #[window_info(rename = "starrail_character_list_step")]
+    pub list_step: Pos<f64>,
+
+    /// the number of avatars visible in the character list
+    #[window_info(rename = "starrail_character_list_count")]
+    pub list_count: i32,
+
+    #[window_info(rename = "starrail_character_details_tab_pos")]
+    pub details_tab_pos: Pos<f64>,
+
+    #[window_info(rename = "starrail_character_traces_tab_pos")]
+    pub traces_tab_pos: Pos<f64>,
+
+    #[window_info(rename = "starrail_character_eidolons_tab_pos")]
+    pub eidolons_tab_pos: Pos<f64>,
+
+    /// the skill levels on the traces tab
+    #[window_info(rename = "starrail_character_basic_rect")]
+    pub basic_rect: Rect<f64>,
+    #[window_info(rename = "starrail_character_skill_rect")]
+    pub skill_rect: Rect<f64>,
+    #[window_info(rename = "starrail_character_ult_rect")]
+    pub ult_rect: Rect<f64>,
+    #[window_info(rename = "starrail_character_talent_rect")]
+    pub talent_rect: Rect<f64>,
+
+    /// the ability and stat bonus nodes on the traces tab, sampled to tell whether they are unlocked
+    #[window_info(rename = "starrail_character_ability1_pos")]
+    pub ability_pos_1: Pos<f64>,
+    #[window_info(rename = "starrail_character_ability2_pos")]
+    pub ability_pos_2: Pos<f64>,
+    #[window_info(rename = "starrail_character_ability3_pos")]
+    pub ability_pos_3: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat1_pos")]
+    pub stat_pos_1: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat2_pos")]
+    pub stat_pos_2: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat3_pos")]
+    pub stat_pos_3: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat4_pos")]
+    pub stat_pos_4: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat5_pos")]
+    pub stat_pos_5: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat6_pos")]
+    pub stat_pos_6: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat7_pos")]
+    pub stat_pos_7: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat8_pos")]
+    pub stat_pos_8: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat9_pos")]
+    pub stat_pos_9: Pos<f64>,
+    #[window_info(rename = "starrail_character_stat10_pos")]
+    pub stat_pos_10: Pos<f64>,
+
+    /// the eidolon nodes on the eidolons tab
+    #[window_info(rename = "starrail_character_eidolon1_pos")]
+    pub eidolon_pos_1: Pos<f64>,
+    #[window_info(rename = "starrail_character_eidolon2_pos")]
+    pub eidolon_pos_2: Pos<f64>,
+    #[window_info(rename = "starrail_character_eidolon3_pos")]
+    pub eidolon_pos_3: Pos<f64>,
+    #[window_info(rename = "starrail_character_eidolon4_pos")]
+    pub eidolon_pos_4: Pos<f64>,
+    #[window_info(rename = "starrail_character_eidolon5_pos")]
+    pub eidolon_pos_5: Pos<f64>,
+    #[window_info(rename = "starrail_character_eidolon6_pos")]
+    pub eidolon_pos_6: Pos<f64>,
+}
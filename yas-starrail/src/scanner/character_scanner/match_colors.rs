@@ -0,0 +1,17 @@
+use image::Rgb;
+
+pub struct MatchColors {
+    pub match_colors_trace: [Rgb<u8>; 2],
+    pub match_colors_eidolon: [Rgb<u8>; 2],
+}
+
+pub const MATCH_COLORS: MatchColors = MatchColors {
+    match_colors_trace: [
+        Rgb([235, 221, 190]),   // unlocked
+        Rgb([88, 88, 96]),      // locked
+    ],
+    match_colors_eidolon: [
+        Rgb([240, 232, 214]),   // unlocked
+        Rgb([70, 70, 78]),      // locked
+    ],
+};
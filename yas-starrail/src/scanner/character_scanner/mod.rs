@@ -0,0 +1,9 @@
+pub use character_scanner::StarRailCharacterScanner;
+pub use character_scanner_config::StarRailCharacterScannerConfig;
+pub use scan_result::StarRailCharacterScanResult;
+
+mod character_scanner;
+mod character_scanner_config;
+mod character_scanner_window_info;
+mod scan_result;
+mod match_colors;
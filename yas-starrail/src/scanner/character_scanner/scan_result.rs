@@ -0,0 +1,11 @@
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct StarRailCharacterScanResult {
+    pub name: String,
+    /// e.g. `80/80`
+    pub level: String,
+    /// basic attack, skill, ultimate and talent, e.g. `6/10`
+    pub skills: [String; 4],
+    pub abilities: [bool; 3],
+    pub stats: [bool; 10],
+    pub eidolons: [bool; 6],
+}
@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+use image::RgbImage;
+
+use yas::common::image_hash::{difference_hash, hash_distance};
+use yas::utils::color_distance;
+
+use crate::character::CHARACTERS;
+
+/// The furthest a color may be from a known equipper color, when there is no avatar gallery
+const MAX_COLOR_DISTANCE: f64 = 40.0;
+
+#[derive(Clone, clap::Args)]
+pub struct EquipperGalleryConfig {
+    #[arg(id = "equipper-gallery", long = "equipper-gallery", default_value_t = String::from("equipper_gallery"), help = "装备角色头像库目录，其中的 <角色>.png 用于识别装备角色，可以添加新角色", value_name = "DIR")]
+    pub equipper_gallery: String,
+
+    #[arg(id = "equipper-max-distance", long = "equipper-max-distance", default_value_t = 10, help = "头像与头像库的最大差异（0-64），超过时装备角色为 unknown")]
+    pub equipper_max_distance: u32,
+
+    #[arg(id = "equipper-capture", long = "equipper-capture", help = "不扫描，将当前选中物品的装备角色头像保存到头像库，如 --equipper-capture=Kafka", value_name = "CHARACTER")]
+    pub equipper_capture: Option<String>,
+}
+
+/// The equipper of an item, `name` is `unknown` if no avatar is close enough
+#[derive(Debug, Clone)]
+pub struct EquipperMatch {
+    pub name: String,
+    /// 0 to 1, of the closest avatar even if it is not close enough
+    pub confidence: f64,
+}
+
+impl EquipperMatch {
+    pub const UNKNOWN: &'static str = "unknown";
+
+    pub fn is_unknown(&self) -> bool {
+        self.name == Self::UNKNOWN
+    }
+}
+
+/// Recognizes the equipper from the avatar next to the equip text.
+///
+/// The avatar is compared by difference hash with the images of a gallery directory,
+/// where `Seele.png` or `Seele_2.png` is an avatar of `Seele`, so that new characters
+/// only need a new image. Without a gallery the color at the center of the avatar is used.
+///
+/// No gallery is shipped, it is built with `--equipper-capture=<character>` while an item
+/// equipped by that character is selected in the game
+pub struct EquipperMatcher {
+    gallery_dir: PathBuf,
+    gallery: Vec<(String, u64)>,
+    max_distance: u32,
+}
+
+impl EquipperMatcher {
+    pub fn load(config: &EquipperGalleryConfig) -> Result<EquipperMatcher> {
+        let mut gallery = Vec::new();
+        let dir = Path::new(&config.equipper_gallery);
+        if dir.is_dir() {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.extension().is_none_or(|x| x != "png") {
+                    continue;
+                }
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let name = stem.split('_').next().unwrap_or_default().to_string();
+                match image::open(&path) {
+                    Ok(image) => gallery.push((name, difference_hash(&image.to_rgb8()))),
+                    Err(e) => warn!("无法读取头像 {:?}: {}", path, e),
+                }
+            }
+        }
+
+        if gallery.is_empty() {
+            info!("头像库 {} 为空，使用颜色识别装备角色", config.equipper_gallery);
+        } else {
+            info!("头像库 {} 共 {} 个头像", config.equipper_gallery, gallery.len());
+        }
+
+        Ok(EquipperMatcher {
+            gallery_dir: dir.to_path_buf(),
+            gallery,
+            max_distance: config.equipper_max_distance,
+        })
+    }
+
+    /// Save `avatar` as an image of `name` in the gallery, next to the ones it already has
+    pub fn save_avatar(&self, name: &str, avatar: &RgbImage) -> Result<PathBuf> {
+        if name.is_empty() || name.contains(['_', '.', '/', '\\']) {
+            return Err(anyhow!("角色名 {} 不能为空，也不能包含 _ . / \\", name));
+        }
+        std::fs::create_dir_all(&self.gallery_dir)?;
+
+        let mut path = self.gallery_dir.join(format!("{}.png", name));
+        let mut index = 1;
+        while path.exists() {
+            index += 1;
+            path = self.gallery_dir.join(format!("{}_{}.png", name, index));
+        }
+        avatar.save(&path)?;
+        Ok(path)
+    }
+
+    pub fn match_avatar(&self, avatar: &RgbImage) -> EquipperMatch {
+        if self.gallery.is_empty() {
+            return Self::match_color(avatar);
+        }
+
+        let hash = difference_hash(avatar);
+        let (name, distance) = self.gallery
+            .iter()
+            .map(|(name, x)| (name, hash_distance(hash, *x)))
+            .min_by_key(|&(_, distance)| distance)
+            .unwrap();
+
+        EquipperMatch {
+            name: if distance <= self.max_distance { name.clone() } else { String::from(EquipperMatch::UNKNOWN) },
+            confidence: 1.0 - distance as f64 / 64.0,
+        }
+    }
+
+    fn match_color(avatar: &RgbImage) -> EquipperMatch {
+        let color = avatar.get_pixel(avatar.width() / 2, avatar.height() / 2);
+        let (name, distance) = CHARACTERS
+            .iter()
+            .filter_map(|x| x.equipper_color.map(|c| (x.key, c)))
+            .map(|(name, x)| (name, (color_distance(&x, color) as f64).sqrt()))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+
+        // the largest distance of two colors is 255 * sqrt(3)
+        EquipperMatch {
+            name: String::from(if distance <= MAX_COLOR_DISTANCE { name } else { EquipperMatch::UNKNOWN }),
+            confidence: 1.0 - distance / (255.0 * 3.0_f64.sqrt()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_avatar() {
+        let dir = std::env::temp_dir().join(format!("yas_equipper_gallery_{}", std::process::id()));
+        let config = EquipperGalleryConfig {
+            equipper_gallery: dir.to_string_lossy().to_string(),
+            equipper_max_distance: 10,
+            equipper_capture: None,
+        };
+        let matcher = EquipperMatcher::load(&config).unwrap();
+        let avatar = RgbImage::from_fn(16, 16, |x, y| image::Rgb([(x * 16) as u8, (y * 16) as u8, 0]));
+
+        assert_eq!(matcher.save_avatar("Kafka", &avatar).unwrap(), dir.join("Kafka.png"));
+        assert_eq!(matcher.save_avatar("Kafka", &avatar).unwrap(), dir.join("Kafka_2.png"));
+        assert!(matcher.save_avatar("../Kafka", &avatar).is_err());
+
+        let matcher = EquipperMatcher::load(&config).unwrap();
+        let result = matcher.match_avatar(&avatar);
+        assert_eq!(result.name, "Kafka");
+        assert_eq!(result.confidence, 1.0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
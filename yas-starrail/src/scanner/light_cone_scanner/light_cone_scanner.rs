@@ -0,0 +1,231 @@
+use std::{cell::RefCell, ops::{Coroutine, CoroutineState}, path::PathBuf, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
+
+use anyhow::Result;
+use clap::FromArgMatches;
+use image::RgbImage;
+use log::{error, info};
+
+use yas::capture::{Capturer, GenericCapturer};
+use yas::common::cancel::CancellationToken;
+use yas::export::{emit_progress, emit_status};
+use yas::game_info::GameInfo;
+use yas::ocr::{ImageToText, yas_ocr_model};
+use yas::positioning::Pos;
+use yas::utils::color_distance;
+use yas::window_info::{FromWindowInfoRepository, WindowInfoRepository};
+
+use crate::scanner::light_cone_scanner::light_cone_scanner_window_info::LightConeScannerWindowInfo;
+use crate::scanner::light_cone_scanner::light_cone_scanner_worker::LightConeScannerWorker;
+use crate::scanner::light_cone_scanner::message_items::SendItem;
+use crate::scanner::light_cone_scanner::scan_result::StarRailLightConeScanResult;
+use crate::scanner::relic_scanner::match_colors::{MATCH_COLORS, MatchColors};
+use crate::scanner::equipper_matcher::{EquipperGalleryConfig, EquipperMatch, EquipperMatcher};
+use crate::scanner_controller::repository_layout::{ReturnResult, StarRailRepositoryScanController};
+
+use super::light_cone_scanner_config::StarRailLightConeScannerConfig;
+
+/// Scans the light cone page of the inventory, the lock and equipper colors are shared with relics
+pub struct StarRailLightConeScanner {
+    scanner_config: StarRailLightConeScannerConfig,
+    window_info: LightConeScannerWindowInfo,
+    game_info: GameInfo,
+    image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
+    controller: Rc<RefCell<StarRailRepositoryScanController>>,
+    capturer: Rc<dyn Capturer<RgbImage>>,
+    cancellation_token: CancellationToken,
+    equipper_matcher: EquipperMatcher,
+
+    match_colors: MatchColors,
+}
+
+// constructor
+impl StarRailLightConeScanner {
+    fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+        let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
+            yas_ocr_model!("../relic_scanner/models/model_training.onnx", "../relic_scanner/models/index_2_word.json")?
+        );
+        Ok(model)
+    }
+
+    fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
+        Ok(Rc::new(GenericCapturer::new()?))
+    }
+
+    pub fn from_arg_matches(
+        window_info_repo: &WindowInfoRepository,
+        arg_matches: &clap::ArgMatches,
+        game_info: GameInfo,
+    ) -> Result<Self> {
+        let window_info = LightConeScannerWindowInfo::from_window_info_repository(
+            game_info.window.to_rect_usize().size(),
+            game_info.ui,
+            game_info.platform,
+            window_info_repo
+        )?;
+        Ok(StarRailLightConeScanner {
+            scanner_config: StarRailLightConeScannerConfig::from_arg_matches(arg_matches)?,
+            window_info,
+            controller: Rc::new(RefCell::new(
+                StarRailRepositoryScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone())?
+            )),
+            game_info,
+            image_to_text: Self::get_image_to_text()?,
+            capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
+            equipper_matcher: EquipperMatcher::load(&EquipperGalleryConfig::from_arg_matches(arg_matches)?)?,
+            match_colors: MATCH_COLORS,
+        })
+    }
+}
+
+impl StarRailLightConeScanner {
+    /// Stop capturing once `token` is cancelled, the items captured so far are still recognized
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
+    pub fn capture_panel(&self) -> Result<RgbImage> {
+        self.capturer.capture_relative_to(
+            self.window_info.panel_rect.to_rect_i32(),
+            self.game_info.window.origin()
+        )
+    }
+
+    pub fn get_lock(&self) -> Result<bool> {
+        let pos: Pos<i32> = Pos {
+            x: self.game_info.window.left + self.window_info.lock_pos.x as i32,
+            y: self.game_info.window.top + self.window_info.lock_pos.y as i32,
+        };
+        let color = self.capturer.capture_color(pos)?;
+
+        let (index, _) = self.match_colors.match_colors_lock
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, match_color)| color_distance(match_color, &color))
+            .unwrap();
+
+        Ok(index == 0)
+    }
+
+    fn capture_equipper_avatar(&self) -> Result<RgbImage> {
+        self.capturer.capture_relative_to(
+            self.window_info.equipper_rect.to_rect_i32(),
+            self.game_info.window.origin()
+        )
+    }
+
+    pub fn get_equipper(&self) -> Result<EquipperMatch> {
+        let avatar = self.capture_equipper_avatar()?;
+        Ok(self.equipper_matcher.match_avatar(&avatar))
+    }
+
+    /// Add the avatar of the selected item to the gallery as `name`
+    pub fn save_equipper_avatar(&self, name: &str) -> Result<PathBuf> {
+        let avatar = self.capture_equipper_avatar()?;
+        self.equipper_matcher.save_avatar(name, &avatar)
+    }
+
+    pub fn get_item_count(&self) -> Result<i32> {
+        let count = self.scanner_config.number;
+        let item_name = "光锥数量";
+
+        let max_count = 1500;
+        if count > 0 {
+            return Ok(max_count.min(count));
+        }
+
+        let im = self.capturer.capture_relative_to(
+            self.window_info.item_count_rect.to_rect_i32(),
+            self.game_info.window.origin()
+        )?;
+        let s = self.image_to_text.image_to_text(&im, false)?;
+
+        info!("物品信息: {}", s);
+
+        if s.starts_with(item_name) {
+            let chars = s.chars().collect::<Vec<char>>();
+            let count_str = chars[4..chars.len() - 5].iter().collect::<String>();
+            Ok(match count_str.parse::<usize>() {
+                Ok(v) => (v as i32).min(max_count),
+                Err(_) => max_count,
+            })
+        } else {
+            Ok(max_count)
+        }
+    }
+
+    pub fn scan(&mut self) -> Result<Vec<StarRailLightConeScanResult>> {
+        info!("开始扫描，使用鼠标右键中断扫描");
+        emit_status("scanning");
+
+        let now = SystemTime::now();
+        let (tx, rx) = mpsc::channel::<Option<SendItem>>();
+        let count = self.get_item_count()?;
+        let worker = LightConeScannerWorker::new(
+            self.window_info.clone(),
+            self.scanner_config.clone()
+        )?;
+
+        let join_handle = worker.run(rx);
+        info!("Worker created");
+
+        self.send(&tx, count);
+        emit_status("recognizing");
+
+        match tx.send(None) {
+            Ok(_) => info!("扫描结束，等待识别线程结束，请勿关闭程序"),
+            Err(_) => info!("扫描结束，识别已完成"),
+        }
+
+        match join_handle.join() {
+            Ok(v) => {
+                info!("识别耗时: {:?}", now.elapsed()?);
+                Ok(v)
+            },
+            Err(_) => Err(anyhow::anyhow!("识别线程出现错误")),
+        }
+    }
+
+    fn send(&mut self, tx: &Sender<Option<SendItem>>, count: i32) {
+        let mut generator = StarRailRepositoryScanController::get_generator(
+            self.controller.clone(),
+            count as usize
+        );
+
+        let mut light_cone_index = 0;
+        loop {
+            let pinned_generator = Pin::new(&mut generator);
+            match pinned_generator.resume(()) {
+                CoroutineState::Yielded(_) => {
+                    if self.cancellation_token.cancelled() {
+                        info!("扫描任务被取消");
+                        break;
+                    }
+
+                    let panel_image = self.capture_panel().unwrap();
+                    let equip = self.get_equipper().unwrap();
+                    let lock = self.get_lock().unwrap();
+
+                    if tx.send(Some(SendItem { panel_image, equip, lock })).is_err() {
+                        break;
+                    }
+                    light_cone_index += 1;
+                    emit_progress(light_cone_index, count as usize);
+                },
+                CoroutineState::Complete(result) => {
+                    match result {
+                        Err(e) => error!("扫描发生错误：{}", e),
+                        Ok(value) => {
+                            match value {
+                                ReturnResult::Interrupted => info!("用户中断"),
+                                ReturnResult::Finished => ()
+                            }
+                        }
+                    }
+
+                    break;
+                }
+            }
+        }
+    }
+}
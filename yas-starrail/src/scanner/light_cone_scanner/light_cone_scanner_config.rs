@@ -0,0 +1,8 @@
+#[derive(Clone, clap::Args)]
+pub struct StarRailLightConeScannerConfig {
+    #[arg(id = "verbose", long, help = "显示详细信息")]
+    pub verbose: bool,
+
+    #[arg(id = "number", long, help = "指定光锥数量", value_name = "NUMBER", default_value_t = -1)]
+    pub number: i32,
+}
@@ -0,0 +1,30 @@
+use yas::positioning::{Pos, Rect};
+
+#[derive(Clone, yas_derive::YasWindowInfo, Debug)]
+pub struct LightConeScannerWindowInfo {
+    #[window_info(rename = "starrail_light_cone_title_rect")]
+    pub title_rect: Rect<f64>,
+
+    /// the level and the max level, e.g. `80/80`
+    #[window_info(rename = "starrail_light_cone_level_rect")]
+    pub level_rect: Rect<f64>,
+
+    #[window_info(rename = "starrail_light_cone_superimposition_rect")]
+    pub superimposition_rect: Rect<f64>,
+
+    #[window_info(rename = "starrail_light_cone_equip_rect")]
+    pub equip_rect: Rect<f64>,
+
+    /// the avatar of the equipper
+    #[window_info(rename = "starrail_light_cone_equipper_rect")]
+    pub equipper_rect: Rect<f64>,
+
+    #[window_info(rename = "starrail_light_cone_item_count_rect")]
+    pub item_count_rect: Rect<f64>,
+
+    #[window_info(rename = "starrail_light_cone_lock_pos")]
+    pub lock_pos: Pos<f64>,
+
+    #[window_info(rename = "starrail_repository_panel_rect")]
+    pub panel_rect: Rect<f64>,
+}
@@ -0,0 +1,115 @@
+use std::sync::mpsc::Receiver;
+use std::thread::JoinHandle;
+
+use anyhow::Result;
+use image::{GenericImageView, RgbImage};
+use log::{error, info, warn};
+
+use yas::export::{emit_error, emit_item, is_event_stream_active};
+use yas::ocr::{yas_ocr_model, ImageToText};
+use yas::positioning::{Pos, Rect};
+
+use crate::light_cone::StarRailLightCone;
+use crate::scanner::light_cone_scanner::light_cone_scanner_window_info::LightConeScannerWindowInfo;
+use crate::scanner::light_cone_scanner::message_items::SendItem;
+use crate::scanner::light_cone_scanner::scan_result::StarRailLightConeScanResult;
+use crate::scanner::light_cone_scanner::StarRailLightConeScannerConfig;
+
+pub struct LightConeScannerWorker {
+    model: Box<dyn ImageToText<RgbImage> + Send>,
+    window_info: LightConeScannerWindowInfo,
+    config: StarRailLightConeScannerConfig,
+}
+
+fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+    let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
+        yas_ocr_model!("../relic_scanner/models/model_training.onnx", "../relic_scanner/models/index_2_word.json")?
+    );
+    Ok(model)
+}
+
+impl LightConeScannerWorker {
+    pub fn new(
+        window_info: LightConeScannerWindowInfo,
+        config: StarRailLightConeScannerConfig,
+    ) -> Result<Self> {
+        Ok(LightConeScannerWorker {
+            model: get_image_to_text()?,
+            window_info,
+            config,
+        })
+    }
+
+    fn model_inference(&self, rect: Rect<f64>, captured_img: &RgbImage) -> Result<String> {
+        let relative_rect = rect.translate(Pos {
+            x: -self.window_info.panel_rect.left,
+            y: -self.window_info.panel_rect.top,
+        });
+
+        let raw_img = captured_img.view(
+            relative_rect.left as u32, relative_rect.top as u32, relative_rect.width as u32, relative_rect.height as u32
+        ).to_image();
+
+        self.model.image_to_text(&raw_img, false)
+    }
+
+    fn scan_item_image(&self, item: SendItem) -> Result<StarRailLightConeScanResult> {
+        let image = &item.panel_image;
+
+        let str_title = self.model_inference(self.window_info.title_rect, image)?;
+        let str_level = self.model_inference(self.window_info.level_rect, image)?;
+        let str_superimposition = self.model_inference(self.window_info.superimposition_rect, image)?;
+        let str_equip = self.model_inference(self.window_info.equip_rect, image)?;
+        if str_equip.ends_with("装备中") && item.equip.is_unknown() {
+            warn!("无法识别装备角色，最接近的头像置信度 {:.2}", item.equip.confidence);
+        }
+
+        Ok(StarRailLightConeScanResult {
+            name: str_title,
+            level: str_level,
+            superimposition: str_superimposition,
+            equip: item.equip.name + &str_equip,
+            lock: item.lock,
+        })
+    }
+
+    pub fn run(self, rx: Receiver<Option<SendItem>>) -> JoinHandle<Vec<StarRailLightConeScanResult>> {
+        std::thread::spawn(move || {
+            let mut results = Vec::new();
+            let is_verbose = self.config.verbose;
+
+            // identical light cones are common, e.g. several unlevelled 3 star ones,
+            // so unlike relics duplicates are kept
+            for item in rx.into_iter() {
+                let item = match item {
+                    Some(v) => v,
+                    None => break,
+                };
+
+                let result = match self.scan_item_image(item) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        error!("识别错误: {}", e);
+                        continue;
+                    },
+                };
+
+                if is_verbose {
+                    info!("{:?}", result);
+                }
+
+                if is_event_stream_active() {
+                    match StarRailLightCone::try_from(&result) {
+                        Ok(light_cone) => emit_item(results.len(), &light_cone),
+                        Err(e) => emit_error(&e.to_string()),
+                    }
+                }
+                results.push(result);
+            }
+
+            info!("识别结束，物品数量: {}", results.len());
+
+            results
+        })
+    }
+}
@@ -0,0 +1,9 @@
+use image::RgbImage;
+
+use crate::scanner::equipper_matcher::EquipperMatch;
+
+pub struct SendItem {
+    pub panel_image: RgbImage,
+    pub equip: EquipperMatch,
+    pub lock: bool,
+}
@@ -0,0 +1,10 @@
+pub use light_cone_scanner::StarRailLightConeScanner;
+pub use light_cone_scanner_config::StarRailLightConeScannerConfig;
+pub use scan_result::StarRailLightConeScanResult;
+
+mod light_cone_scanner;
+mod light_cone_scanner_config;
+mod light_cone_scanner_window_info;
+mod scan_result;
+mod light_cone_scanner_worker;
+mod message_items;
@@ -0,0 +1,10 @@
+#[derive(Debug, Hash, Clone, PartialEq, Eq)]
+pub struct StarRailLightConeScanResult {
+    pub name: String,
+    /// e.g. `80/80`
+    pub level: String,
+    /// e.g. `叠影1阶`
+    pub superimposition: String,
+    pub equip: String,
+    pub lock: bool,
+}
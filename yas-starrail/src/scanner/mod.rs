@@ -1 +1,4 @@
-pub mod relic_scanner;
\ No newline at end of file
+pub mod relic_scanner;
+pub mod light_cone_scanner;
+pub mod character_scanner;
+pub mod equipper_matcher;
\ No newline at end of file
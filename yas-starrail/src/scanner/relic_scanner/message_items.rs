@@ -1,8 +1,10 @@
 use image::RgbImage;
 
+use crate::scanner::equipper_matcher::EquipperMatch;
+
 pub struct SendItem {
     pub panel_image: RgbImage,
-    pub equip: String,
+    pub equip: EquipperMatch,
     pub star: usize,
     pub lock: bool,
     pub discard: bool,
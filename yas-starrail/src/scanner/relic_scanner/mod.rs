@@ -9,4 +9,4 @@ mod relic_scanner_window_info;
 mod scan_result;
 mod relic_scanner_worker;
 mod message_items;
-mod match_colors;
+pub(crate) mod match_colors;
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, ops::{Coroutine, CoroutineState}, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
+use std::{cell::RefCell, ops::{Coroutine, CoroutineState}, path::PathBuf, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
 
 use anyhow::Result;
 use clap::FromArgMatches;
@@ -6,6 +6,8 @@ use image::RgbImage;
 use log::{error, info};
 
 use yas::capture::{Capturer, GenericCapturer};
+use yas::common::cancel::CancellationToken;
+use yas::export::{emit_progress, emit_status};
 use yas::game_info::GameInfo;
 use yas::ocr::{ImageToText, yas_ocr_model};
 use yas::positioning::Pos;
@@ -17,6 +19,7 @@ use crate::scanner::relic_scanner::message_items::SendItem;
 use crate::scanner::relic_scanner::relic_scanner_window_info::RelicScannerWindowInfo;
 use crate::scanner::relic_scanner::relic_scanner_worker::RelicScannerWorker;
 use crate::scanner::relic_scanner::scan_result::StarRailRelicScanResult;
+use crate::scanner::equipper_matcher::{EquipperGalleryConfig, EquipperMatch, EquipperMatcher};
 use crate::scanner_controller::repository_layout::{ReturnResult, StarRailRepositoryScanController, StarRailRepositoryScannerLogicConfig};
 
 use super::relic_scanner_config::StarRailRelicScannerConfig;
@@ -28,6 +31,8 @@ pub struct StarRailRelicScanner {
     image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
     controller: Rc<RefCell<StarRailRepositoryScanController>>,
     capturer: Rc<dyn Capturer<RgbImage>>,
+    cancellation_token: CancellationToken,
+    equipper_matcher: EquipperMatcher,
 
     match_colors: MatchColors,
 }
@@ -50,6 +55,7 @@ impl StarRailRelicScanner {
         window_info_repo: &WindowInfoRepository,
         config: StarRailRelicScannerConfig,
         controller_config: StarRailRepositoryScannerLogicConfig,
+        gallery_config: EquipperGalleryConfig,
         game_info: GameInfo
     ) -> Result<Self> {
         Ok(StarRailRelicScanner {
@@ -68,6 +74,8 @@ impl StarRailRelicScanner {
             game_info,
             image_to_text: Self::get_image_to_text()?,
             capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
+            equipper_matcher: EquipperMatcher::load(&gallery_config)?,
 
             match_colors: MATCH_COLORS,
         })
@@ -93,12 +101,19 @@ impl StarRailRelicScanner {
             game_info,
             image_to_text: Self::get_image_to_text()?,
             capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
+            equipper_matcher: EquipperMatcher::load(&EquipperGalleryConfig::from_arg_matches(arg_matches)?)?,
             match_colors: MATCH_COLORS,
         })
     }
 }
 
 impl StarRailRelicScanner {
+    /// Stop capturing once `token` is cancelled, the items captured so far are still recognized
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
     pub fn capture_panel(&self) -> Result<RgbImage> {
         self.capturer.capture_relative_to(
             self.window_info.panel_rect.to_rect_i32(),
@@ -154,19 +169,22 @@ impl StarRailRelicScanner {
         Ok(index == 0)
     }
 
-    pub fn get_equipper(&self) -> Result<String> {
-        let pos: Pos<i32> = Pos {
-            x: self.game_info.window.left + self.window_info.equipper_pos.x as i32,
-            y: self.game_info.window.top + self.window_info.equipper_pos.y as i32,
-        };
-        let color = self.capturer.capture_color(pos)?;
+    fn capture_equipper_avatar(&self) -> Result<RgbImage> {
+        self.capturer.capture_relative_to(
+            self.window_info.equipper_rect.to_rect_i32(),
+            self.game_info.window.origin()
+        )
+    }
 
-        let (name, _) = self.match_colors.match_colors_equipper
-            .iter()
-            .min_by_key(|&(_, match_color)| color_distance(match_color, &color))
-            .unwrap();
+    pub fn get_equipper(&self) -> Result<EquipperMatch> {
+        let avatar = self.capture_equipper_avatar()?;
+        Ok(self.equipper_matcher.match_avatar(&avatar))
+    }
 
-        Ok(name.to_string())
+    /// Add the avatar of the selected item to the gallery as `name`
+    pub fn save_equipper_avatar(&self, name: &str) -> Result<PathBuf> {
+        let avatar = self.capture_equipper_avatar()?;
+        self.equipper_matcher.save_avatar(name, &avatar)
     }
 
     pub fn get_item_count(&self) -> Result<i32> {
@@ -200,6 +218,7 @@ impl StarRailRelicScanner {
 
     pub fn scan(&mut self) -> Result<Vec<StarRailRelicScanResult>> {
         info!("开始扫描，使用鼠标右键中断扫描");
+        emit_status("scanning");
 
         let now = SystemTime::now();
         let (tx, rx) = mpsc::channel::<Option<SendItem>>();
@@ -214,6 +233,7 @@ impl StarRailRelicScanner {
         info!("Worker created");
 
         self.send(&tx, count);
+        emit_status("recognizing");
 
         match tx.send(None) {
             Ok(_) => info!("扫描结束，等待识别线程结束，请勿关闭程序"),
@@ -241,10 +261,16 @@ impl StarRailRelicScanner {
             count as usize
         );
 
+        let mut relic_index = 0;
         loop {
             let pinned_generator = Pin::new(&mut generator);
             match pinned_generator.resume(()) {
                 CoroutineState::Yielded(_) => {
+                    if self.cancellation_token.cancelled() {
+                        info!("扫描任务被取消");
+                        break;
+                    }
+
                     // let image = self.capture_panel().unwrap();
                     let panel_image = self.capture_panel().unwrap();
                     let equip = self.get_equipper().unwrap();
@@ -264,6 +290,8 @@ impl StarRailRelicScanner {
                     if tx.send(Some(SendItem { panel_image, equip, star, lock, discard })).is_err() {
                         break;
                     }
+                    relic_index += 1;
+                    emit_progress(relic_index, count as usize);
 
                     // scanned_count += 1;
                 },
@@ -37,8 +37,9 @@ pub struct RelicScannerWindowInfo {
     #[window_info(rename = "starrail_relic_equip_rect")]
     pub equip_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_equipper_pos")]
-    pub equipper_pos: Pos<f64>,
+    /// the avatar of the equipper
+    #[window_info(rename = "starrail_relic_equipper_rect")]
+    pub equipper_rect: Rect<f64>,
 
     #[window_info(rename = "starrail_relic_item_count_rect")]
     pub item_count_rect: Rect<f64>,
@@ -6,14 +6,19 @@ use anyhow::Result;
 use image::{GenericImageView, RgbImage};
 use log::{error, info, warn};
 
+use yas::export::{emit_error, emit_item, is_event_stream_active};
 use yas::ocr::{yas_ocr_model, ImageToText};
 use yas::positioning::{Pos, Rect};
 
+use crate::relic::{RelicField, StarRailRelic};
 use crate::scanner::relic_scanner::message_items::SendItem;
 use crate::scanner::relic_scanner::relic_scanner_window_info::RelicScannerWindowInfo;
 use crate::scanner::relic_scanner::scan_result::StarRailRelicScanResult;
 use crate::scanner::relic_scanner::StarRailRelicScannerConfig;
 
+/// Pixels added around a field when it is OCR-ed again
+const RETRY_PADDING: f64 = 4.0;
+
 pub struct RelicScannerWorker {
     model: Box<dyn ImageToText<RgbImage> + Send>,
     window_info: RelicScannerWindowInfo,
@@ -67,6 +72,37 @@ impl RelicScannerWorker {
         inference_result
     }
 
+    /// OCR `rect` again with a few more pixels around it, so the model sees a different crop
+    fn model_inference_padded(&self, rect: Rect<f64>, captured_img: &RgbImage) -> Result<String> {
+        let panel = self.window_info.panel_rect;
+        let left = (rect.left - RETRY_PADDING).max(panel.left);
+        let top = (rect.top - RETRY_PADDING).max(panel.top);
+        let right = (rect.left + rect.width + RETRY_PADDING).min(panel.left + panel.width);
+        let bottom = (rect.top + rect.height + RETRY_PADDING).min(panel.top + panel.height);
+
+        self.model_inference(Rect::new(left, top, right - left, bottom - top), captured_img)
+    }
+
+    /// Re-OCR the main stat if it cannot appear on the slot, and keep the new text if it then can
+    fn recheck_slot_rules(&self, result: &mut StarRailRelicScanResult, image: &RgbImage) -> Result<()> {
+        match StarRailRelic::try_from(&*result) {
+            Err(e) if e.field == RelicField::SlotMainStat => (),
+            _ => return Ok(()),
+        };
+
+        let mut retry = result.clone();
+        retry.main_stat_name = self.model_inference_padded(self.window_info.main_stat_name_rect, image)?;
+        retry.main_stat_value = self.model_inference_padded(self.window_info.main_stat_value_rect, image)?;
+
+        if StarRailRelic::try_from(&retry).is_ok() {
+            warn!("主词条不符合部位规则，重新识别为 `{}+{}`", retry.main_stat_name, retry.main_stat_value);
+            *result = retry;
+        } else {
+            warn!("主词条不符合部位规则，重新识别后仍无法修正");
+        }
+        Ok(())
+    }
+
     fn scan_item_image(&self, item: SendItem) -> Result<StarRailRelicScanResult> {
         let image = &item.panel_image;
 
@@ -85,8 +121,11 @@ impl RelicScannerWorker {
 
         let str_level = self.model_inference(self.window_info.level_rect, &image)?;
         let str_equip = self.model_inference(self.window_info.equip_rect, &image)?;
+        if str_equip.ends_with("装备中") && item.equip.is_unknown() {
+            warn!("无法识别装备角色，最接近的头像置信度 {:.2}", item.equip.confidence);
+        }
 
-        Ok(StarRailRelicScanResult {
+        let mut result = StarRailRelicScanResult {
             name: str_title,
             main_stat_name: str_main_stat_name,
             main_stat_value: str_main_stat_value,
@@ -103,11 +142,14 @@ impl RelicScannerWorker {
                 str_sub_stat3_value,
             ],
             level: parse_level(&str_level)?,
-            equip: item.equip + &str_equip,
+            equip: item.equip.name + &str_equip,
             star: item.star as i32,
             lock: item.lock,
             discard: item.discard,
-        })
+        };
+        self.recheck_slot_rules(&mut result, image)?;
+
+        Ok(result)
     }
 
     pub fn run(self, rx: Receiver<Option<SendItem>>) -> JoinHandle<Vec<StarRailRelicScanResult>> {
@@ -153,6 +195,12 @@ impl RelicScannerWorker {
                 } else {
                     consecutive_dup_count = 0;
                     hash.insert(result.clone());
+                    if is_event_stream_active() {
+                        match StarRailRelic::try_from(&result) {
+                            Ok(relic) => emit_item(results.len(), &relic),
+                            Err(e) => emit_error(&e.to_string()),
+                        }
+                    }
                     results.push(result);
                 }
 
@@ -1,3 +1,5 @@
+pub use ww_echo_diff::WWEchoDiffApplication;
 pub use ww_echo_scanner::WWEchoScannerApplication;
 
+mod ww_echo_diff;
 mod ww_echo_scanner;
@@ -1,29 +1,43 @@
 use anyhow::anyhow;
-use clap::{ArgMatches, Args, command};
-use log::info;
-use yas::export::ExportAssets;
+use clap::{ArgMatches, Args, command, FromArgMatches};
+use log::{error, info};
+use yas::common::cancel::CancellationToken;
+use yas::export::{AssetEmitter, ExportAssets, ExportSaveConfig, ScanHistoryConfig, TemplateExportConfig};
 use yas::game_info::{GameInfo, GameInfoBuilder};
 use yas::window_info::{load_window_info_repo, WindowInfoRepository};
+use crate::echo::WWEcho;
+use crate::export::{WWEchoExporter, WWExportEchoConfig};
 use crate::scanner::{WWEchoScanner, WWEchoScannerConfig};
 use crate::scanner_controller::WWRepositoryLayoutConfig;
 use anyhow::Result;
 
 pub struct WWEchoScannerApplication {
-    arg_matches: ArgMatches
+    arg_matches: ArgMatches,
+    cancellation_token: CancellationToken,
 }
 
 impl WWEchoScannerApplication {
     pub fn new(args: ArgMatches) -> Self {
         Self {
-            arg_matches: args
+            arg_matches: args,
+            cancellation_token: CancellationToken::new(),
         }
     }
 
+    /// Cancel the scan through `token`, e.g. from `yas serve`
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = token;
+        self
+    }
+
     pub fn build_command() -> clap::Command {
         let mut cmd = command!();
         cmd = <WWEchoScannerConfig as Args>::augment_args_for_update(cmd);
         cmd = <WWRepositoryLayoutConfig as Args>::augment_args_for_update(cmd);
-        // cmd = <ExportRelicConfig as Args>::augment_args_for_update(cmd);
+        cmd = <WWExportEchoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <TemplateExportConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ScanHistoryConfig as Args>::augment_args_for_update(cmd);
+        cmd = <ExportSaveConfig as Args>::augment_args_for_update(cmd);
         cmd
     }
 
@@ -45,9 +59,10 @@ impl WWEchoScannerApplication {
 
 impl WWEchoScannerApplication {
     pub fn run(&self) -> Result<()> {
-        println!("START");
         // Self::init();
         let arg_matches = &self.arg_matches;
+        // fail on a bad template before spending a whole scan on it
+        TemplateExportConfig::from_arg_matches(arg_matches)?.build::<WWEcho>()?;
         let window_info_repository = Self::get_window_info_repository();
         let game_info = Self::get_game_info()?;
 
@@ -69,11 +84,33 @@ impl WWEchoScannerApplication {
             &arg_matches,
             game_info.clone()
         )?;
+        scanner.set_cancellation_token(self.cancellation_token.clone());
 
         let results = scanner.scan()?;
 
-        for item in results.iter() {
-            println!("{:?}", item);
+        let mut echoes = Vec::new();
+        for (index, item) in results.iter().enumerate() {
+            match WWEcho::try_from(item) {
+                Ok(echo) => echoes.push(echo),
+                Err(e) => error!("第 {} 个声骸：{}", index + 1, e),
+            }
+        }
+
+        let exporter = WWEchoExporter::new(arg_matches, &echoes)?;
+        let mut export_assets = ExportAssets::new();
+        exporter.emit(&mut export_assets);
+
+        let stats = export_assets.save_with(&ExportSaveConfig::from_arg_matches(arg_matches)?);
+        info!("保存结果：");
+        let table = format!("{}", stats);
+        // print multiline
+        for line in table.lines() {
+            info!("{}", line);
+        }
+
+        let history_config = ScanHistoryConfig::from_arg_matches(arg_matches)?;
+        if let Some(run_id) = history_config.record(env!("CARGO_PKG_VERSION"), &echoes)? {
+            info!("已追加到历史数据库，本次记录编号 {}", run_id);
         }
 
         Ok(())
@@ -1,11 +1,132 @@
-use crate::echo::{WWEchoName, WWStat};
+use anyhow::anyhow;
+use log::warn;
+use crate::echo::{WWEchoName, WWSonataName, WWStat, WWStatName};
+use crate::scanner::WWEchoScanResult;
 
 pub struct WWEcho {
     pub name: WWEchoName,
+    /// `None` if the sonata could not be recognized, or for echoes read from an export
+    /// written before the sonata was recognized
+    pub sonata: Option<WWSonataName>,
+    /// 1, 3 or 4, read from the panel, or else taken from the echo data. `None` if neither has it
+    pub cost: Option<usize>,
     pub main_stat1: WWStat,
     pub main_stat2: WWStat,
     pub sub_stats: Vec<WWStat>,
     pub level: usize,
     pub star: usize,
     pub lock: bool,
+    /// The name of the equipping resonator in zh-cn
+    pub equip: Option<String>,
+    /// The OCR text the echo is converted from, `None` for echoes read from an export
+    pub raw: Option<WWEchoScanResult>,
+}
+
+impl WWStat {
+    /// e.g. `暴击率`, `8.1%`. Percentages are stored as fractions
+    pub fn from_chs_raw(name: &str, value: &str) -> Option<WWStat> {
+        let is_percentage = value.contains('%');
+        let name = WWStatName::from_chs(name.trim(), is_percentage)?;
+
+        let mut value = value.replace(['%', ','], "").trim().parse::<f64>().ok()?;
+        if is_percentage {
+            value /= 100.0;
+        }
+
+        Some(WWStat { name, value })
+    }
+}
+
+/// `今汐装配中` is equipped by 今汐
+fn parse_equip(s: &str) -> Option<String> {
+    let name = s.trim().strip_suffix("装配中")?.trim();
+    if name.is_empty() {
+        None
+    } else {
+        Some(String::from(name))
+    }
+}
+
+/// `COST 4` costs 4
+fn parse_cost(s: &str) -> Option<usize> {
+    let digits = s.chars().filter(|c| c.is_ascii_digit()).collect::<String>();
+    match digits.parse::<usize>().ok()? {
+        cost @ (1 | 3 | 4) => Some(cost),
+        _ => None,
+    }
+}
+
+impl TryFrom<&WWEchoScanResult> for WWEcho {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &WWEchoScanResult) -> Result<Self, Self::Error> {
+        let name = WWEchoName::from_chs(&value.name)
+            .ok_or_else(|| anyhow!("声骸的名称无法识别：`{}`", value.name))?;
+        let sonata = WWSonataName::from_chs(&value.sonata);
+        if sonata.is_none() {
+            warn!("声骸 {} 的合鸣效果无法识别：`{}`", value.name, value.sonata);
+        }
+        let main_stat1 = WWStat::from_chs_raw(&value.main_stat1_name, &value.main_stat1_value)
+            .ok_or_else(|| anyhow!("声骸的主词条无法识别：`{}+{}`", value.main_stat1_name, value.main_stat1_value))?;
+        let main_stat2 = WWStat::from_chs_raw(&value.main_stat2_name, &value.main_stat2_value)
+            .ok_or_else(|| anyhow!("声骸的主词条无法识别：`{}+{}`", value.main_stat2_name, value.main_stat2_value))?;
+
+        // sub stats that are not unlocked yet are empty
+        let sub_stats = value.sub_stat_names.iter()
+            .zip(value.sub_stat_values.iter())
+            .filter_map(|(name, value)| WWStat::from_chs_raw(name, value))
+            .collect();
+
+        Ok(WWEcho {
+            name,
+            sonata,
+            cost: parse_cost(&value.cost).or_else(|| name.cost()),
+            main_stat1,
+            main_stat2,
+            sub_stats,
+            level: value.level,
+            star: value.star,
+            lock: value.lock,
+            equip: parse_equip(&value.equip),
+            raw: Some(value.clone()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan_result(sonata: &str, cost: &str) -> WWEchoScanResult {
+        WWEchoScanResult {
+            name: String::from(WWEchoName::ALL_CHS[0].0),
+            main_stat1_name: String::from("暴击率"),
+            main_stat1_value: String::from("22.0%"),
+            main_stat2_name: String::from("攻击"),
+            main_stat2_value: String::from("150"),
+            sub_stat_names: Default::default(),
+            sub_stat_values: Default::default(),
+            sonata: String::from(sonata),
+            cost: String::from(cost),
+            equip: String::new(),
+            level: 25,
+            star: 5,
+            lock: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_cost() {
+        assert_eq!(parse_cost("COST 4"), Some(4));
+        assert_eq!(parse_cost("COST1"), Some(1));
+        assert_eq!(parse_cost("COST 2"), None);
+        assert_eq!(parse_cost(""), None);
+    }
+
+    #[test]
+    fn test_unrecognized_sonata() {
+        let echo = WWEcho::try_from(&scan_result("", "COST 3")).unwrap();
+        assert_eq!(echo.sonata, None);
+        assert_eq!(echo.cost, Some(3));
+    }
 }
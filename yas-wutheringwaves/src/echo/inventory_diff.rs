@@ -0,0 +1,42 @@
+use yas::common::inventory_diff::InventoryItem;
+
+use crate::echo::WWEcho;
+
+impl InventoryItem for WWEcho {
+    fn is_same_item(&self, newer: &Self) -> bool {
+        if self.name != newer.name
+            // an older export may not have the sonata
+            || (self.sonata.is_some() && newer.sonata.is_some() && self.sonata != newer.sonata)
+            || self.star != newer.star
+            || self.main_stat1.name != newer.main_stat1.name
+            || self.main_stat2.name != newer.main_stat2.name
+            || self.level > newer.level
+        {
+            return false;
+        }
+
+        // sub stats are only unlocked when levelling up, and never change after that
+        self.sub_stats.iter().all(|old| {
+            newer.sub_stats.iter().any(|new| new.name == old.name && (new.value - old.value).abs() < 1e-6)
+        })
+    }
+
+    fn level(&self) -> i32 {
+        self.level as i32
+    }
+
+    fn equip(&self) -> Option<&str> {
+        self.equip.as_deref()
+    }
+
+    fn describe(&self) -> String {
+        format!("{} {}★ +{} {}", self.name, self.star, self.level, self.main_stat1.name)
+    }
+
+    fn sub_stat_changes(&self, newer: &Self) -> Vec<String> {
+        newer.sub_stats.iter()
+            .filter(|new| !self.sub_stats.iter().any(|old| old.name == new.name))
+            .map(|new| format!("{} {}（新增）", new.name, new.value))
+            .collect()
+    }
+}
@@ -1,7 +1,11 @@
 pub use echo_name::WWEchoName;
 pub use stats::{WWStat, WWStatName};
 pub use echo::WWEcho;
+pub use sonata::WWSonataName;
 
 mod echo;
 mod echo_name;
+mod inventory_diff;
+mod sonata;
 mod stats;
+mod template_item;
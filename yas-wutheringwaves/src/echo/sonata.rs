@@ -0,0 +1,58 @@
+use lazy_static::lazy_static;
+use log::warn;
+use yas::common::fuzzy_match::{FuzzyMatcher, FuzzyMatchResult};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display, strum_macros::EnumString)]
+pub enum WWSonataName {
+    FreezingFrost,
+    MoltenRift,
+    VoidThunder,
+    SierraGale,
+    CelestialLight,
+    SunSinkingEclipse,
+    RejuvenatingGlow,
+    MoonlitClouds,
+    LingeringTunes,
+    FrostyResolve,
+    EternalRadiance,
+    MidnightVeil,
+    EmpyreanAnthem,
+    TidebreakingCourage,
+}
+
+/// All sonata effect names in chs
+#[rustfmt::skip]
+const SONATA_NAMES_CHS: &[(&str, WWSonataName)] = &[
+    ("凝夜白霜", WWSonataName::FreezingFrost),
+    ("熔山裂谷", WWSonataName::MoltenRift),
+    ("彻空冥雷", WWSonataName::VoidThunder),
+    ("啸谷长风", WWSonataName::SierraGale),
+    ("浮星祛暗", WWSonataName::CelestialLight),
+    ("沉日劫明", WWSonataName::SunSinkingEclipse),
+    ("隐世回光", WWSonataName::RejuvenatingGlow),
+    ("轻云出月", WWSonataName::MoonlitClouds),
+    ("不绝余音", WWSonataName::LingeringTunes),
+    ("凌冽决断之心", WWSonataName::FrostyResolve),
+    ("此间永驻之光", WWSonataName::EternalRadiance),
+    ("幽夜隐匿之帷", WWSonataName::MidnightVeil),
+    ("高天共奏之曲", WWSonataName::EmpyreanAnthem),
+    ("无惧浪涛之勇", WWSonataName::TidebreakingCourage),
+];
+
+lazy_static! {
+    static ref SONATA_NAME_MATCHER: FuzzyMatcher<'static, WWSonataName> = FuzzyMatcher::new(
+        SONATA_NAMES_CHS.iter().cloned()
+    );
+}
+
+impl WWSonataName {
+    pub fn from_chs(chs: &str) -> Option<Self> {
+        match SONATA_NAME_MATCHER.find(chs) {
+            FuzzyMatchResult::Ambiguous { candidates, .. } => {
+                warn!("sonata name `{}` is ambiguous: {:?}", chs, candidates);
+                None
+            },
+            result => result.value(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display, strum_macros::EnumString)]
 pub enum WWStatName {
     CriticalDamage,
     CriticalRate,
@@ -0,0 +1,46 @@
+use yas::export::{split_indexed_field, TemplateItem};
+
+use crate::echo::{WWEcho, WWStat};
+
+impl WWStat {
+    fn template_field(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "name" => self.name.to_string(),
+            "value" => self.value.to_string(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl TemplateItem for WWEcho {
+    const FIELDS: &'static [&'static str] = &[
+        "name", "sonata", "cost", "star", "level", "lock", "equip",
+        "main.name", "main.value", "main2.name", "main2.value",
+        "sub[].name", "sub[].value",
+    ];
+
+    fn field(&self, name: &str) -> Option<String> {
+        if let Some((index, field)) = split_indexed_field(name, "sub") {
+            return self.sub_stats.get(index)?.template_field(field);
+        }
+        if let Some(field) = name.strip_prefix("main.") {
+            return self.main_stat1.template_field(field);
+        }
+        if let Some(field) = name.strip_prefix("main2.") {
+            return self.main_stat2.template_field(field);
+        }
+
+        let value = match name {
+            "name" => self.name.to_string(),
+            "sonata" => self.sonata?.to_string(),
+            "cost" => self.cost?.to_string(),
+            "star" => self.star.to_string(),
+            "level" => self.level.to_string(),
+            "lock" => self.lock.to_string(),
+            "equip" => self.equip.clone()?,
+            _ => return None,
+        };
+        Some(value)
+    }
+}
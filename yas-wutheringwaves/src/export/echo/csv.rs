@@ -0,0 +1,82 @@
+use yas::export::ExportFormat;
+
+use crate::echo::{WWEcho, WWStat};
+
+/// Quote a field that holds a separator, a quote or a line break, as OCR text may
+fn escape_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        String::from(s)
+    }
+}
+
+/// CSV format:
+/// name, sonata, cost, star, level, lock, [main stat name, main stat value]*2, [sub stat name, sub stat value]*5, equip
+fn single_echo_to_string(echo: &WWEcho) -> String {
+    let stat_to_string = |stat: Option<&WWStat>| match stat {
+        Some(stat) => format!("{},{}", escape_field(&stat.name.to_string()), stat.value),
+        None => String::from(","),
+    };
+
+    let mut s = String::new();
+    s = s + &escape_field(&echo.name.to_string());
+    s = s + "," + &escape_field(&echo.sonata.map(|x| x.to_string()).unwrap_or_default());
+    s = s + "," + &echo.cost.map(|x| x.to_string()).unwrap_or_default();
+    s = s + "," + &format!("{}", echo.star);
+    s = s + "," + &format!("{}", echo.level);
+    s = s + "," + &format!("{}", echo.lock);
+    s = s + "," + &stat_to_string(Some(&echo.main_stat1));
+    s = s + "," + &stat_to_string(Some(&echo.main_stat2));
+    for i in 0..5 {
+        s = s + "," + &stat_to_string(echo.sub_stats.get(i));
+    }
+    s = s + "," + &escape_field(echo.equip.as_deref().unwrap_or_default());
+
+    s
+}
+
+fn echoes_to_csv_string(echoes: &[WWEcho]) -> String {
+    let header = "名称,合鸣,COST,星级,等级,锁定,主词条名1,主词条值1,主词条名2,主词条值2,副词条名1,副词条值1,副词条名2,副词条值2,副词条名3,副词条值3,副词条名4,副词条值4,副词条名5,副词条值5,装备";
+    let mut result = String::from(header) + "\n";
+
+    for echo in echoes.iter() {
+        let line = single_echo_to_string(echo);
+        result = result + &line + "\n";
+    }
+
+    result
+}
+
+pub struct CSVExportFormat;
+
+impl ExportFormat<WWEcho> for CSVExportFormat {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "echoes.csv"
+    }
+
+    fn description(&self) -> &'static str {
+        "CSV格式声骸"
+    }
+
+    fn serialize(&self, items: &[WWEcho]) -> anyhow::Result<Vec<u8>> {
+        Ok(echoes_to_csv_string(items).into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("今汐"), "今汐");
+        assert_eq!(escape_field("今汐,长离"), "\"今汐,长离\"");
+        assert_eq!(escape_field("a\"b"), "\"a\"\"b\"");
+        assert_eq!(escape_field("a\nb"), "\"a\nb\"");
+    }
+}
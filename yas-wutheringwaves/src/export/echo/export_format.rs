@@ -1,12 +1,20 @@
-use clap::ValueEnum;
+use lazy_static::lazy_static;
 
-#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
-pub enum WWEchoExportFormat {
-    Hsi
-}
+use yas::export::ExportFormatRegistry;
+
+use crate::echo::WWEcho;
+
+use super::csv::CSVExportFormat;
+use super::hsi::HsiExportFormat;
+use super::native::NativeExportFormat;
 
-impl Default for WWEchoExportFormat {
-    fn default() -> Self {
-        Self::Hsi
-    }
+lazy_static! {
+    /// The formats of `--format`. Other crates can register more before the command line is built
+    pub static ref ECHO_EXPORT_FORMATS: ExportFormatRegistry<WWEcho> = {
+        let registry = ExportFormatRegistry::new();
+        registry.register(HsiExportFormat);
+        registry.register(NativeExportFormat);
+        registry.register(CSVExportFormat);
+        registry
+    };
 }
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::FromArgMatches;
+use yas::export::{AssetEmitter, ExportAssets, TemplateExport, TemplateExportConfig};
+
+use crate::echo::WWEcho;
+use crate::export::echo::{WWExportEchoConfig, ECHO_EXPORT_FORMATS};
+
+pub struct WWEchoExporter<'a> {
+    /// A name in `ECHO_EXPORT_FORMATS`, or `all`
+    pub format: String,
+    pub results: &'a [WWEcho],
+    pub output_dir: PathBuf,
+    /// A user defined template, exported alongside `format`
+    pub template: Option<TemplateExport>,
+}
+
+impl<'a> WWEchoExporter<'a> {
+    pub fn new(arg_matches: &clap::ArgMatches, results: &'a [WWEcho]) -> Result<Self> {
+        let config = WWExportEchoConfig::from_arg_matches(arg_matches)?;
+        Ok(Self {
+            format: config.format,
+            results,
+            output_dir: PathBuf::from(&config.output_dir),
+            template: TemplateExportConfig::from_arg_matches(arg_matches)?.build::<WWEcho>()?,
+        })
+    }
+}
+
+impl<'a> AssetEmitter for WWEchoExporter<'a> {
+    fn emit(&self, asset_bundle: &mut ExportAssets) {
+        for format in ECHO_EXPORT_FORMATS.select(&self.format) {
+            format.emit(self.results, &self.output_dir, asset_bundle);
+        }
+
+        if let Some(template) = &self.template {
+            template.emit(self.results, &self.output_dir, asset_bundle);
+        }
+    }
+}
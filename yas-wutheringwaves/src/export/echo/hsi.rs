@@ -1,7 +1,12 @@
 use std::ops::Deref;
-use serde::{Serialize, Serializer};
+use std::str::FromStr;
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::{Deserialize, Serialize, Serializer};
 use serde::ser::SerializeMap;
-use crate::echo::{WWEcho, WWStat};
+use yas::export::{ExportFormat, HistoryItem};
+use crate::echo::{WWEcho, WWEchoName, WWSonataName, WWStat, WWStatName};
+use crate::export::echo::native::{echoes_from_native, is_native};
 
 struct HsiStat<'a>(&'a WWStat);
 
@@ -50,17 +55,33 @@ impl<'a> Serialize for HsiEcho<'a> {
         }
 
         root.serialize_entry("name", &self.name.to_string())?;
+        if let Some(sonata) = self.sonata {
+            root.serialize_entry("sonata", &sonata.to_string())?;
+        }
+        if let Some(cost) = self.cost {
+            root.serialize_entry("cost", &cost)?;
+        }
         root.serialize_entry("main_stat1", &HsiStat(&self.main_stat1))?;
         root.serialize_entry("main_stat2", &HsiStat(&self.main_stat2))?;
         root.serialize_entry("sub_stats", &hsi_sub_stats)?;
         root.serialize_entry("star", &self.star)?;
         root.serialize_entry("level", &self.level)?;
-        // root.serialize_entry("lock", &self.lock)?;
+        root.serialize_entry("lock", &self.lock)?;
+        root.serialize_entry("equip", &self.equip)?;
 
         root.end()
     }
 }
 
+impl Serialize for WWEcho {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        HsiEcho(self).serialize(serializer)
+    }
+}
+
 pub struct WWHsiFormat<'a> {
     pub echoes: &'a [HsiEcho<'a>],
     pub version: usize,
@@ -78,3 +99,189 @@ impl<'a> Serialize for WWHsiFormat<'a> {
         map.end()
     }
 }
+
+#[derive(Deserialize)]
+struct HsiStatInput {
+    name: String,
+    value: f64,
+}
+
+#[derive(Deserialize)]
+struct HsiEchoInput {
+    name: String,
+    /// Files written before the sonata was recognized do not have it
+    #[serde(default)]
+    sonata: Option<String>,
+    #[serde(default)]
+    cost: Option<usize>,
+    main_stat1: HsiStatInput,
+    main_stat2: HsiStatInput,
+    #[serde(default)]
+    sub_stats: Vec<HsiStatInput>,
+    star: usize,
+    level: usize,
+    #[serde(default)]
+    lock: bool,
+    #[serde(default)]
+    equip: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WWHsiFormatInput {
+    #[serde(default)]
+    echoes: Vec<HsiEchoInput>,
+}
+
+impl HsiStatInput {
+    fn to_stat(&self) -> Option<WWStat> {
+        Some(WWStat {
+            name: WWStatName::from_str(&self.name).ok()?,
+            value: self.value,
+        })
+    }
+}
+
+impl HsiEchoInput {
+    fn to_echo(&self) -> Option<WWEcho> {
+        let sub_stats = self.sub_stats.iter()
+            .map(|x| x.to_stat())
+            .collect::<Option<Vec<_>>>()?;
+
+        let name = WWEchoName::from_str(&self.name).ok()?;
+        Some(WWEcho {
+            name,
+            sonata: match self.sonata.as_ref() {
+                Some(v) => Some(WWSonataName::from_str(v).ok()?),
+                None => None,
+            },
+            cost: self.cost.or(name.cost()),
+            main_stat1: self.main_stat1.to_stat()?,
+            main_stat2: self.main_stat2.to_stat()?,
+            sub_stats,
+            level: self.level,
+            star: self.star,
+            lock: self.lock,
+            equip: self.equip.clone(),
+            raw: None,
+        })
+    }
+}
+
+/// Read the echoes of a hsi file, unrecognized echoes are skipped
+pub fn echoes_from_hsi(contents: &str) -> Result<Vec<WWEcho>> {
+    let input: WWHsiFormatInput = serde_json::from_str(contents)?;
+
+    let mut result = Vec::new();
+    for echo in input.echoes.iter() {
+        match echo.to_echo() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别声骸 {}", echo.name),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Read echoes from a previous hsi or native export
+pub fn load_echoes<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<WWEcho>> {
+    let contents = std::fs::read_to_string(path)?;
+    if is_native(&contents) {
+        echoes_from_native(&contents)
+    } else {
+        echoes_from_hsi(&contents)
+    }
+}
+
+pub struct HsiExportFormat;
+
+impl ExportFormat<WWEcho> for HsiExportFormat {
+    fn name(&self) -> &'static str {
+        "hsi"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "hsi.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "Hsi声骸格式"
+    }
+
+    fn serialize(&self, items: &[WWEcho]) -> Result<Vec<u8>> {
+        let echoes: Vec<HsiEcho> = items.iter().map(HsiEcho).collect();
+        let format = WWHsiFormat {
+            echoes: &echoes,
+            version: 1,
+        };
+        Ok(serde_json::to_vec(&format)?)
+    }
+}
+
+/// Echoes are stored in the history database as hsi echoes
+impl HistoryItem for WWEcho {
+    const GAME: &'static str = "wutheringwaves";
+
+    fn to_history_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    fn from_history_json(json: &str) -> Result<Self> {
+        let input: HsiEchoInput = serde_json::from_str(json)?;
+        input.to_echo().ok_or_else(|| anyhow!("无法识别声骸 {}", input.name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echoes() -> Vec<WWEcho> {
+        let name = WWEchoName::ALL_CHS[0].1;
+        let echo = WWEcho {
+            name,
+            sonata: Some(WWSonataName::MoltenRift),
+            cost: name.cost(),
+            main_stat1: WWStat { name: WWStatName::CriticalRate, value: 0.22 },
+            main_stat2: WWStat { name: WWStatName::ATK, value: 150.0 },
+            sub_stats: vec![
+                WWStat { name: WWStatName::CriticalDamage, value: 0.162 },
+                WWStat { name: WWStatName::HP, value: 470.0 },
+            ],
+            level: 25,
+            star: 5,
+            lock: true,
+            equip: Some(String::from("今汐")),
+            raw: None,
+        };
+        vec![echo]
+    }
+
+    #[test]
+    fn test_hsi_round_trip() {
+        let contents = HsiExportFormat.serialize(&echoes()).unwrap();
+        let result = echoes_from_hsi(std::str::from_utf8(&contents).unwrap()).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(HsiExportFormat.serialize(&result).unwrap(), contents);
+    }
+
+    #[test]
+    fn test_hsi_without_sonata() {
+        // written before the sonata and the cost were recognized
+        let contents = serde_json::json!({
+            "echoes": [{
+                "name": WWEchoName::ALL_CHS[0].1.to_string(),
+                "main_stat1": { "name": "CriticalRate", "value": 0.22 },
+                "main_stat2": { "name": "ATK", "value": 150.0 },
+                "sub_stats": [],
+                "star": 5,
+                "level": 25,
+            }],
+            "version": 1,
+        });
+        let result = echoes_from_hsi(&contents.to_string()).unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].sonata, None);
+    }
+}
@@ -1,3 +1,12 @@
+pub use config::WWExportEchoConfig;
+pub use export_format::ECHO_EXPORT_FORMATS;
+pub use exporter::WWEchoExporter;
+pub use hsi::{echoes_from_hsi, load_echoes};
+pub use native::{echoes_from_native, NATIVE_SCHEMA, NATIVE_VERSION};
+
 mod config;
+mod csv;
 mod export_format;
+mod exporter;
 mod hsi;
+mod native;
@@ -0,0 +1,212 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use yas::export::ExportFormat;
+
+use crate::echo::{WWEcho, WWEchoName, WWSonataName, WWStat, WWStatName};
+use crate::scanner::WWEchoScanResult;
+
+/// The `schema` of a native export
+pub const NATIVE_SCHEMA: &str = "yas-wutheringwaves-echoes";
+/// A released version keeps its shape, any change to the shape bumps the version
+pub const NATIVE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct NativeStat {
+    name: String,
+    /// Percentages are fractions
+    value: f64,
+}
+
+/// The OCR text of every field, as the scanner read it
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeRaw {
+    name: String,
+    sonata: String,
+    cost: String,
+    main_stat_names: [String; 2],
+    main_stat_values: [String; 2],
+    sub_stat_names: [String; 5],
+    sub_stat_values: [String; 5],
+    equip: String,
+    level: usize,
+    star: usize,
+    lock: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NativeEcho {
+    name: String,
+    sonata: Option<String>,
+    cost: Option<usize>,
+    star: usize,
+    level: usize,
+    lock: bool,
+    equip: Option<String>,
+    main_stats: [NativeStat; 2],
+    sub_stats: Vec<NativeStat>,
+    raw: Option<NativeRaw>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NativeFormat {
+    schema: String,
+    version: u32,
+    echoes: Vec<NativeEcho>,
+}
+
+impl NativeStat {
+    fn new(stat: &WWStat) -> Self {
+        NativeStat {
+            name: stat.name.to_string(),
+            value: stat.value,
+        }
+    }
+
+    fn to_stat(&self) -> Option<WWStat> {
+        Some(WWStat {
+            name: WWStatName::from_str(&self.name).ok()?,
+            value: self.value,
+        })
+    }
+}
+
+impl NativeRaw {
+    fn new(raw: &WWEchoScanResult) -> Self {
+        NativeRaw {
+            name: raw.name.clone(),
+            sonata: raw.sonata.clone(),
+            cost: raw.cost.clone(),
+            main_stat_names: [raw.main_stat1_name.clone(), raw.main_stat2_name.clone()],
+            main_stat_values: [raw.main_stat1_value.clone(), raw.main_stat2_value.clone()],
+            sub_stat_names: raw.sub_stat_names.clone(),
+            sub_stat_values: raw.sub_stat_values.clone(),
+            equip: raw.equip.clone(),
+            level: raw.level,
+            star: raw.star,
+            lock: raw.lock,
+        }
+    }
+
+    fn to_scan_result(&self) -> WWEchoScanResult {
+        let [main_stat1_name, main_stat2_name] = self.main_stat_names.clone();
+        let [main_stat1_value, main_stat2_value] = self.main_stat_values.clone();
+        WWEchoScanResult {
+            name: self.name.clone(),
+            main_stat1_name,
+            main_stat1_value,
+            main_stat2_name,
+            main_stat2_value,
+            sub_stat_names: self.sub_stat_names.clone(),
+            sub_stat_values: self.sub_stat_values.clone(),
+            sonata: self.sonata.clone(),
+            cost: self.cost.clone(),
+            equip: self.equip.clone(),
+            level: self.level,
+            star: self.star,
+            lock: self.lock,
+        }
+    }
+}
+
+impl NativeEcho {
+    fn new(echo: &WWEcho) -> Self {
+        NativeEcho {
+            name: echo.name.to_string(),
+            sonata: echo.sonata.map(|x| x.to_string()),
+            cost: echo.cost,
+            star: echo.star,
+            level: echo.level,
+            lock: echo.lock,
+            equip: echo.equip.clone(),
+            main_stats: [NativeStat::new(&echo.main_stat1), NativeStat::new(&echo.main_stat2)],
+            sub_stats: echo.sub_stats.iter().map(NativeStat::new).collect(),
+            raw: echo.raw.as_ref().map(NativeRaw::new),
+        }
+    }
+
+    fn to_echo(&self) -> Option<WWEcho> {
+        let sub_stats = self.sub_stats.iter()
+            .map(|x| x.to_stat())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(WWEcho {
+            name: WWEchoName::from_str(&self.name).ok()?,
+            sonata: match self.sonata.as_ref() {
+                Some(v) => Some(WWSonataName::from_str(v).ok()?),
+                None => None,
+            },
+            cost: self.cost,
+            main_stat1: self.main_stats[0].to_stat()?,
+            main_stat2: self.main_stats[1].to_stat()?,
+            sub_stats,
+            level: self.level,
+            star: self.star,
+            lock: self.lock,
+            equip: self.equip.clone(),
+            raw: self.raw.as_ref().map(NativeRaw::to_scan_result),
+        })
+    }
+}
+
+/// Whether `contents` is a native export, judged by its `schema`
+pub fn is_native(contents: &str) -> bool {
+    #[derive(Deserialize)]
+    struct SchemaOnly {
+        schema: Option<String>,
+    }
+
+    serde_json::from_str::<SchemaOnly>(contents)
+        .is_ok_and(|x| x.schema.as_deref() == Some(NATIVE_SCHEMA))
+}
+
+/// Read the echoes of a native export, unrecognized echoes are skipped
+pub fn echoes_from_native(contents: &str) -> Result<Vec<WWEcho>> {
+    let input: NativeFormat = serde_json::from_str(contents)?;
+    if input.schema != NATIVE_SCHEMA {
+        return Err(anyhow!("不是 yas 声骸格式：{}", input.schema));
+    }
+    if input.version > NATIVE_VERSION {
+        return Err(anyhow!("不支持的 yas 声骸格式版本：{}", input.version));
+    }
+
+    let mut result = Vec::new();
+    for echo in input.echoes.iter() {
+        match echo.to_echo() {
+            Some(v) => result.push(v),
+            None => warn!("无法识别声骸 {}", echo.name),
+        }
+    }
+
+    Ok(result)
+}
+
+pub struct NativeExportFormat;
+
+impl ExportFormat<WWEcho> for NativeExportFormat {
+    fn name(&self) -> &'static str {
+        "yas"
+    }
+
+    fn default_filename(&self) -> &'static str {
+        "yas_echoes.json"
+    }
+
+    fn description(&self) -> &'static str {
+        "yas声骸格式（无损，含识别原文）"
+    }
+
+    fn serialize(&self, items: &[WWEcho]) -> Result<Vec<u8>> {
+        let format = NativeFormat {
+            schema: String::from(NATIVE_SCHEMA),
+            version: NATIVE_VERSION,
+            echoes: items.iter().map(NativeEcho::new).collect(),
+        };
+        Ok(serde_json::to_vec(&format)?)
+    }
+}
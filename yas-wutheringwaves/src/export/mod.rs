@@ -1 +1,3 @@
+pub use echo::*;
+
 mod echo;
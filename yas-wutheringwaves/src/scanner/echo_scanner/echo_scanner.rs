@@ -13,6 +13,7 @@ use regex::Regex;
 use clap::FromArgMatches;
 
 use yas::capture::{Capturer, GenericCapturer, StreamingCapturer};
+use yas::common::cancel::CancellationToken;
 use yas::game_info::GameInfo;
 use yas::ocr::{ImageToText, yas_ocr_model};
 use yas::window_info::{WindowInfoRepository, FromWindowInfoRepository};
@@ -31,6 +32,7 @@ pub struct WWEchoScanner {
     image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
     controller: Rc<RefCell<WWRepositoryLayoutScanController>>,
     capturer: Rc<dyn Capturer<RgbImage>>,
+    cancellation_token: CancellationToken,
 }
 
 impl WWEchoScanner {
@@ -68,6 +70,7 @@ impl WWEchoScanner {
             game_info,
             image_to_text: Self::get_image_to_text()?,
             capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
         })
     }
 
@@ -91,11 +94,17 @@ impl WWEchoScanner {
             game_info,
             image_to_text: Self::get_image_to_text()?,
             capturer: Self::get_capturer()?,
+            cancellation_token: CancellationToken::new(),
         })
     }
 }
 
 impl WWEchoScanner {
+    /// Stop clicking once `token` is cancelled, the items captured so far are still recognized
+    pub fn set_cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = token;
+    }
+
     // fn capture_panel(&self) -> Result<RgbImage> {
     //     self.capturer.capture_relative_to(
     //         self.window_info.panel_rect.to_rect_i32(),
@@ -134,10 +143,12 @@ impl WWEchoScanner {
 
         let (image_tx, image_rx) = mpsc::channel::<SendItem>();
         let count = self.get_item_count()?;
+        let min_star_token = CancellationToken::new();
 
         let worker = WWEchoScannerWorker::new(
             self.window_info.clone(),
-            self.scanner_config.clone()
+            self.scanner_config.clone(),
+            min_star_token.clone()
         )?;
 
         let worker_join_handle = worker.run(image_rx);
@@ -158,7 +169,7 @@ impl WWEchoScanner {
         //     info!("平均模型推理时间：{} ms", ms);
         // }
 
-        self.start_clicking_items(count);
+        self.start_clicking_items(count, &min_star_token);
 
         self.controller.borrow().profiler.borrow().print();
 
@@ -178,7 +189,8 @@ impl WWEchoScanner {
         // }
     }
 
-    fn start_clicking_items(&mut self, count: usize) {
+    /// Click through `count` items, or until `min_star_token` is cancelled by the worker
+    fn start_clicking_items(&mut self, count: usize, min_star_token: &CancellationToken) {
         let mut generator = WWRepositoryLayoutScanController::get_generator(
             self.controller.clone(),
             count
@@ -188,6 +200,13 @@ impl WWEchoScanner {
             let pinned_generator = Pin::new(&mut generator);
             match pinned_generator.resume(()) {
                 CoroutineState::Yielded(_) => {
+                    if self.cancellation_token.cancelled() {
+                        info!("扫描任务被取消");
+                        break;
+                    }
+                    if min_star_token.cancelled() {
+                        break;
+                    }
                     // let panel_image = self.capture_panel().unwrap();
                     //
                     // if tx.send(Some(SendItem { panel_image })).is_err() {
@@ -39,11 +39,17 @@ pub struct EchoScannerWindowInfo {
     #[window_info(rename = "ww_echo_sub_stat4_value_rect")]
     pub sub_stat_value_5: Rect<f64>,
 
+    #[window_info(rename = "ww_echo_sonata_rect")]
+    pub sonata_rect: Rect<f64>,
+
+    #[window_info(rename = "ww_echo_cost_rect")]
+    pub cost_rect: Rect<f64>,
+
     #[window_info(rename = "ww_echo_level_rect")]
     pub level_rect: Rect<f64>,
 
-    // #[window_info(rename = "ww_echo_equip_rect")]
-    // pub equip_rect: Rect<f64>,
+    #[window_info(rename = "ww_echo_equip_rect")]
+    pub equip_rect: Rect<f64>,
 
     #[window_info(rename = "ww_echo_item_count_rect")]
     pub item_count_rect: Rect<f64>,
@@ -51,8 +57,8 @@ pub struct EchoScannerWindowInfo {
     #[window_info(rename = "ww_echo_star_pos")]
     pub star_pos: Pos<f64>,
 
-    // #[window_info(rename = "ww_echo_lock_pos")]
-    // pub lock_pos: Pos<f64>,
+    #[window_info(rename = "ww_echo_lock_pos")]
+    pub lock_pos: Pos<f64>,
 
     #[window_info(rename = "ww_repository_panel_rect")]
     pub panel_rect: Rect<f64>,
@@ -8,7 +8,10 @@ use crate::scanner::echo_scanner::echo_scanner_window_info::EchoScannerWindowInf
 use anyhow::Result;
 use log::{error, info, warn};
 use rayon::iter::ParallelBridge;
+use yas::common::cancel::CancellationToken;
 use yas::positioning::{Pos, Rect};
+use yas::utils::color_distance;
+use crate::scanner::echo_scanner::match_colors::{MATCH_COLORS, MatchColors};
 use crate::scanner::echo_scanner::message_item::SendItem;
 use crate::scanner::echo_scanner::scan_result::WWEchoScanResult;
 
@@ -16,6 +19,9 @@ pub struct WWEchoScannerWorker {
     model: Box<dyn ImageToText<RgbImage> + Send>,
     window_info: EchoScannerWindowInfo,
     config: WWEchoScannerConfig,
+    match_colors: MatchColors,
+    /// Cancelled when an item below `min_star` is found, so the scanner stops clicking
+    cancellation_token: CancellationToken,
 }
 
 fn parse_level(s: &str) -> Result<usize> {
@@ -42,11 +48,14 @@ impl WWEchoScannerWorker {
     pub fn new(
         window_info: EchoScannerWindowInfo,
         config: WWEchoScannerConfig,
+        cancellation_token: CancellationToken,
     ) -> Result<Self> {
         Ok(Self {
             model: get_image_to_text()?,
             window_info,
             config,
+            match_colors: MATCH_COLORS,
+            cancellation_token,
         })
     }
 
@@ -65,16 +74,31 @@ impl WWEchoScannerWorker {
         inference_result
     }
 
+    /// Match the color of the rarity band with the colors of 2 to 5 stars
     fn determine_star(&self, im: &RgbImage) -> Result<usize> {
         let pos_relative_to_panel = self.window_info.star_pos - self.window_info.panel_rect.origin();
 
         let x = pos_relative_to_panel.x as u32;
-        let y = im.height() - pos_relative_to_panel.y as u32;
+        let y = pos_relative_to_panel.y as u32;
 
         let color = im.get_pixel(x, y);
-        // todo
+        let (index, _) = self.match_colors.match_colors_star
+            .iter()
+            .enumerate()
+            .min_by_key(|&(_, match_color)| color_distance(match_color, color))
+            .unwrap();
 
-        Ok(5)
+        Ok(index + 2)
+    }
+
+    /// The lock icon is bright when the echo is locked
+    fn determine_lock(&self, im: &RgbImage) -> Result<bool> {
+        let pos_relative_to_panel = self.window_info.lock_pos - self.window_info.panel_rect.origin();
+
+        let color = im.get_pixel(pos_relative_to_panel.x as u32, pos_relative_to_panel.y as u32);
+        let colors = &self.match_colors.match_colors_lock;
+
+        Ok(color_distance(&colors[0], color) < color_distance(&colors[1], color))
     }
 
     fn parse_item(&self, item: SendItem) -> Result<WWEchoScanResult> {
@@ -97,10 +121,13 @@ impl WWEchoScannerWorker {
         let str_sub_stat3_value = self.model_inference(self.window_info.sub_stat_value_4, &image)?;
         let str_sub_stat4_value = self.model_inference(self.window_info.sub_stat_value_5, &image)?;
 
+        let str_sonata = self.model_inference(self.window_info.sonata_rect, &image)?;
+        let str_cost = self.model_inference(self.window_info.cost_rect, &image)?;
         let str_level = self.model_inference(self.window_info.level_rect, &image)?;
-        // let str_equip = self.model_inference(self.window_info., &image)?;
+        let str_equip = self.model_inference(self.window_info.equip_rect, &image)?;
 
         let star = self.determine_star(&image)?;
+        let lock = self.determine_lock(&image)?;
 
         Ok(WWEchoScanResult {
             name: str_title,
@@ -122,10 +149,12 @@ impl WWEchoScannerWorker {
                 str_sub_stat3_value,
                 str_sub_stat4_value,
             ],
+            sonata: str_sonata,
+            cost: str_cost,
             level: parse_level(&str_level)?,
-            // equip: item.equip + &str_equip,
+            equip: str_equip,
             star,
-            // lock: item.lock,
+            lock,
         })
     }
 
@@ -134,6 +163,8 @@ impl WWEchoScannerWorker {
             let mut results = Vec::new();
             let mut hash = HashSet::new();
             let mut consecutive_dup_count = 0;
+            // the last frame, if it read below the minimum star
+            let mut low_star_result: Option<WWEchoScanResult> = None;
 
             let is_verbose = self.config.verbose;
             let min_level = self.config.min_level;
@@ -152,6 +183,22 @@ impl WWEchoScannerWorker {
                     info!("{:?}", result);
                 }
 
+                if (result.star as i32) < self.config.min_star {
+                    // a frame taken while switching items may misread the star,
+                    // so stop only when the same item reads below the minimum in consecutive frames
+                    if low_star_result.as_ref() == Some(&result) {
+                        info!(
+                            "找到满足最低星级要求 {} 的物品，准备退出……",
+                            self.config.min_star
+                        );
+                        self.cancellation_token.cancel();
+                        break;
+                    }
+                    low_star_result = Some(result);
+                    continue;
+                }
+                low_star_result = None;
+
                 if hash.contains(&result) {
                     consecutive_dup_count += 1;
                     // warn!("识别到重复物品: {:#?}", result);
@@ -0,0 +1,20 @@
+use image::Rgb;
+
+pub struct MatchColors {
+    /// The rarity band of the panel, from 2 stars to 5 stars
+    pub match_colors_star: [Rgb<u8>; 4],
+    pub match_colors_lock: [Rgb<u8>; 2],
+}
+
+pub const MATCH_COLORS: MatchColors = MatchColors {
+    match_colors_star: [
+        Rgb([94, 172, 104]),    // 2
+        Rgb([74, 133, 209]),    // 3
+        Rgb([160, 94, 218]),    // 4
+        Rgb([229, 189, 94]),    // 5
+    ],
+    match_colors_lock: [
+        Rgb([245, 245, 245]),   // locked
+        Rgb([92, 92, 92]),      // unlocked
+    ],
+};
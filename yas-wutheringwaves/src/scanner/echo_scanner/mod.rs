@@ -1,5 +1,6 @@
 pub use echo_scanner_config::WWEchoScannerConfig;
 pub use echo_scanner::WWEchoScanner;
+pub use scan_result::WWEchoScanResult;
 
 mod message_item;
 mod echo_scanner_config;
@@ -7,3 +8,4 @@ mod echo_scanner_window_info;
 mod scan_result;
 mod echo_scanner_worker;
 mod echo_scanner;
+mod match_colors;
@@ -7,7 +7,13 @@ pub struct WWEchoScanResult {
     pub main_stat2_value: String,
     pub sub_stat_names: [String; 5],
     pub sub_stat_values: [String; 5],
-    // pub equip: String,
+    /// The OCR text of the sonata effect name
+    pub sonata: String,
+    /// The OCR text of the cost label, e.g. `COST 4`
+    pub cost: String,
+    /// The OCR text of the equip line, e.g. `今汐装配中`
+    pub equip: String,
     pub level: usize,
     pub star: usize,
+    pub lock: bool,
 }
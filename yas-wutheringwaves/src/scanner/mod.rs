@@ -1,3 +1,3 @@
-pub use echo_scanner::{WWEchoScanner, WWEchoScannerConfig};
+pub use echo_scanner::{WWEchoScanResult, WWEchoScanner, WWEchoScannerConfig};
 
 mod echo_scanner;
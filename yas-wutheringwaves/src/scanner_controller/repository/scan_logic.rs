@@ -7,7 +7,7 @@ use std::time::SystemTime;
 use anyhow::{anyhow, Result};
 use clap::{ArgMatches, FromArgMatches};
 use image::{Rgb, RgbImage};
-use log::{error, info};
+use log::{debug, error, info};
 
 use yas::capture::{Capturer, GenericCapturer};
 use yas::game_info::GameInfo;
@@ -262,13 +262,13 @@ impl WWRepositoryLayoutScanController {
 
     pub fn sample_initial_color(&mut self) -> Result<()> {
         self.initial_flag = self.capture_flag()?;
-        println!("initial color: {:?}", self.initial_flag);
+        debug!("initial color: {:?}", self.initial_flag);
         Ok(())
     }
 
     pub fn check_flag(&self) -> Result<bool> {
         let flag = self.capture_flag()?;
-        println!("{:?}", &flag);
+        debug!("flag color: {:?}", &flag);
         // let mut same_count = 0;
         let threshold = 50;
 
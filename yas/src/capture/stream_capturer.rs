@@ -52,7 +52,7 @@ impl StreamingCapturer where {
         });
 
         let cancel = move || {
-            println!("cancel capture");
+            eprintln!("cancel capture");
             is_cancelled.store(true, atomic::Ordering::Relaxed);
         };
 
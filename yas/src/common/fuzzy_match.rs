@@ -0,0 +1,98 @@
+use edit_distance::edit_distance;
+
+/// The result of looking up an OCR string in a [`FuzzyMatcher`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum FuzzyMatchResult<'a, T> {
+    /// No candidate is within the maximum distance
+    NotFound,
+    /// A single value is the closest one
+    Matched {
+        key: &'a str,
+        value: T,
+        distance: usize,
+    },
+    /// Several candidates with different values share the smallest distance
+    Ambiguous {
+        distance: usize,
+        candidates: Vec<(&'a str, T)>,
+    },
+}
+
+impl<'a, T> FuzzyMatchResult<'a, T> {
+    /// Returns the matched value, ambiguous results are treated as not found
+    pub fn value(self) -> Option<T> {
+        match self {
+            FuzzyMatchResult::Matched { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn is_ambiguous(&self) -> bool {
+        matches!(self, FuzzyMatchResult::Ambiguous { .. })
+    }
+}
+
+/// Maps an OCR string to the closest entry of a known set of strings,
+/// so that one misread glyph does not make the whole item unrecognizable
+pub struct FuzzyMatcher<'a, T> {
+    candidates: Vec<(&'a str, T)>,
+    max_distance: usize,
+}
+
+impl<'a, T: Clone + PartialEq> FuzzyMatcher<'a, T> {
+    pub const DEFAULT_MAX_DISTANCE: usize = 2;
+
+    pub fn new<I: IntoIterator<Item = (&'a str, T)>>(candidates: I) -> Self {
+        FuzzyMatcher {
+            candidates: candidates.into_iter().collect(),
+            max_distance: Self::DEFAULT_MAX_DISTANCE,
+        }
+    }
+
+    pub fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+
+    pub fn max_distance(&self) -> usize {
+        self.max_distance
+    }
+
+    /// Find the closest candidate of `s`.
+    ///
+    /// A candidate is accepted only if the distance is within `max_distance`
+    /// and less than half of the candidate's length, so that a short key cannot be
+    /// replaced entirely by a misread
+    pub fn find(&self, s: &str) -> FuzzyMatchResult<'a, T> {
+        if let Some((key, value)) = self.candidates.iter().find(|(key, _)| *key == s) {
+            return FuzzyMatchResult::Matched { key, value: value.clone(), distance: 0 };
+        }
+
+        let mut best_distance = usize::MAX;
+        let mut best: Vec<(&'a str, T)> = Vec::new();
+
+        for (key, value) in self.candidates.iter() {
+            let distance = edit_distance(s, key);
+            if distance > self.max_distance || distance * 2 >= key.chars().count() {
+                continue;
+            }
+
+            if distance < best_distance {
+                best_distance = distance;
+                best.clear();
+            }
+            if distance == best_distance && !best.iter().any(|(_, v)| v == value) {
+                best.push((key, value.clone()));
+            }
+        }
+
+        match best.len() {
+            0 => FuzzyMatchResult::NotFound,
+            1 => {
+                let (key, value) = best.pop().unwrap();
+                FuzzyMatchResult::Matched { key, value, distance: best_distance }
+            },
+            _ => FuzzyMatchResult::Ambiguous { distance: best_distance, candidates: best },
+        }
+    }
+}
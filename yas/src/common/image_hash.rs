@@ -0,0 +1,58 @@
+use image::imageops::{self, FilterType};
+use image::RgbImage;
+
+/// A 64 bit difference hash of an image.
+///
+/// The image is shrunk to 9x8 in gray scale, and each bit tells whether a pixel is brighter
+/// than its right neighbour, so that the hash survives scaling and small color shifts
+pub fn difference_hash(image: &RgbImage) -> u64 {
+    let gray = imageops::grayscale(image);
+    let small = imageops::resize(&gray, 9, 8, FilterType::Triangle);
+
+    let mut hash = 0_u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+/// The number of differing bits of two hashes, 0 to 64
+pub fn hash_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+#[cfg(test)]
+mod tests {
+    use image::Rgb;
+
+    use super::*;
+
+    fn gradient(width: u32, height: u32, reversed: bool) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, _| {
+            let v = (x * 255 / (width - 1)) as u8;
+            let v = if reversed { 255 - v } else { v };
+            Rgb([v, v, v])
+        })
+    }
+
+    #[test]
+    fn test_difference_hash() {
+        // every pixel is darker than its right neighbour
+        assert_eq!(difference_hash(&gradient(90, 80, false)), 0);
+        assert_eq!(difference_hash(&gradient(90, 80, true)), u64::MAX);
+        // the hash does not depend on the size
+        assert_eq!(difference_hash(&gradient(45, 40, true)), difference_hash(&gradient(180, 160, true)));
+    }
+
+    #[test]
+    fn test_hash_distance() {
+        assert_eq!(hash_distance(0, 0), 0);
+        assert_eq!(hash_distance(0, u64::MAX), 64);
+        assert_eq!(hash_distance(0b1011, 0b0110), 3);
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt::{Display, Formatter};
+
+use prettytable::{row, Table};
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
+
+/// An item that can be matched across two scans of the same inventory
+pub trait InventoryItem {
+    /// Whether `newer` can be this item in a later scan, after being levelled up or re-equipped
+    fn is_same_item(&self, newer: &Self) -> bool;
+
+    fn level(&self) -> i32;
+
+    fn equip(&self) -> Option<&str>;
+
+    /// A one line description, for the diff table
+    fn describe(&self) -> String;
+
+    /// The sub stats that are new or changed in `newer`, e.g. "暴击率 3.9% → 7.8%"
+    fn sub_stat_changes(&self, newer: &Self) -> Vec<String>;
+}
+
+fn is_unchanged<T: InventoryItem>(old: &T, new: &T) -> bool {
+    old.level() == new.level() && old.equip() == new.equip()
+}
+
+/// Match the items of two scans, returning the index of the old item for every new item.
+///
+/// Items that are the same in both scans are matched first, so that an upgraded item is
+/// not matched to an untouched copy of itself
+pub fn match_items<T: InventoryItem>(old: &[T], new: &[T]) -> Vec<Option<usize>> {
+    let mut old_matched = vec![false; old.len()];
+    let mut new_match: Vec<Option<usize>> = vec![None; new.len()];
+
+    for require_unchanged in [true, false] {
+        for (new_index, new_item) in new.iter().enumerate() {
+            if new_match[new_index].is_some() {
+                continue;
+            }
+
+            // the closest level is the most likely to be the same item
+            let candidate = old.iter()
+                .enumerate()
+                .filter(|(i, old_item)| !old_matched[*i] && old_item.is_same_item(new_item) && (!require_unchanged || is_unchanged(*old_item, new_item)))
+                .max_by_key(|(_, old_item)| old_item.level());
+            if let Some((old_index, _)) = candidate {
+                old_matched[old_index] = true;
+                new_match[new_index] = Some(old_index);
+            }
+        }
+    }
+
+    new_match
+}
+
+/// An item that is in both scans, but not the same
+pub struct ItemChange<'a, T> {
+    pub old: &'a T,
+    pub new: &'a T,
+}
+
+impl<'a, T: InventoryItem> ItemChange<'a, T> {
+    pub fn is_levelled_up(&self) -> bool {
+        self.new.level() > self.old.level()
+    }
+
+    pub fn is_reequipped(&self) -> bool {
+        self.new.equip() != self.old.equip()
+    }
+}
+
+pub struct InventoryDiff<'a, T> {
+    pub added: Vec<&'a T>,
+    pub removed: Vec<&'a T>,
+    pub changed: Vec<ItemChange<'a, T>>,
+    pub unchanged: usize,
+}
+
+impl<'a, T: InventoryItem> InventoryDiff<'a, T> {
+    pub fn new(old: &'a [T], new: &'a [T]) -> Self {
+        let new_match = match_items(old, new);
+        let mut old_matched = vec![false; old.len()];
+        for i in new_match.iter().flatten() {
+            old_matched[*i] = true;
+        }
+
+        let mut diff = InventoryDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+            unchanged: 0,
+        };
+        for (new_item, old_index) in new.iter().zip(new_match) {
+            match old_index {
+                None => diff.added.push(new_item),
+                Some(i) if is_unchanged(&old[i], new_item) => diff.unchanged += 1,
+                Some(i) => diff.changed.push(ItemChange { old: &old[i], new: new_item }),
+            }
+        }
+        for (old_item, matched) in old.iter().zip(old_matched) {
+            if !matched {
+                diff.removed.push(old_item);
+            }
+        }
+
+        diff
+    }
+
+    pub fn levelled_up(&self) -> impl Iterator<Item = &ItemChange<'a, T>> {
+        self.changed.iter().filter(|x| x.is_levelled_up())
+    }
+
+    pub fn reequipped(&self) -> impl Iterator<Item = &ItemChange<'a, T>> {
+        self.changed.iter().filter(|x| x.is_reequipped())
+    }
+
+    pub fn get_table(&self) -> Table {
+        let mut table = Table::new();
+
+        table.add_row(row!["Change", "Item", "Detail"]);
+        for item in self.added.iter() {
+            table.add_row(row!["新增", item.describe(), ""]);
+        }
+        for item in self.removed.iter() {
+            table.add_row(row!["移除", item.describe(), ""]);
+        }
+        for change in self.levelled_up() {
+            let detail = format!("+{} → +{}\n{}", change.old.level(), change.new.level(), change.old.sub_stat_changes(change.new).join("\n"));
+            table.add_row(row!["升级", change.new.describe(), detail]);
+        }
+        for change in self.reequipped() {
+            let detail = format!("{} → {}", change.old.equip().unwrap_or("无"), change.new.equip().unwrap_or("无"));
+            table.add_row(row!["换装", change.new.describe(), detail]);
+        }
+
+        table
+    }
+}
+
+impl<'a, T: InventoryItem> Display for InventoryDiff<'a, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "新增 {} 件，移除 {} 件，升级 {} 件，换装 {} 件，未变化 {} 件",
+            self.added.len(),
+            self.removed.len(),
+            self.levelled_up().count(),
+            self.reequipped().count(),
+            self.unchanged
+        )
+    }
+}
+
+struct LevelUpEntry<'a, 'b, T>(&'b ItemChange<'a, T>);
+
+impl<'a, 'b, T: InventoryItem + Serialize> Serialize for LevelUpEntry<'a, 'b, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut root = serializer.serialize_map(Some(3))?;
+        root.serialize_entry("old", self.0.old)?;
+        root.serialize_entry("new", self.0.new)?;
+        root.serialize_entry("subStatChanges", &self.0.old.sub_stat_changes(self.0.new))?;
+        root.end()
+    }
+}
+
+struct ReequipEntry<'a, 'b, T>(&'b ItemChange<'a, T>);
+
+impl<'a, 'b, T: InventoryItem + Serialize> Serialize for ReequipEntry<'a, 'b, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut root = serializer.serialize_map(Some(4))?;
+        root.serialize_entry("old", self.0.old)?;
+        root.serialize_entry("new", self.0.new)?;
+        root.serialize_entry("from", &self.0.old.equip())?;
+        root.serialize_entry("to", &self.0.new.equip())?;
+        root.end()
+    }
+}
+
+impl<'a, T: InventoryItem + Serialize> Serialize for InventoryDiff<'a, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let levelled_up: Vec<_> = self.levelled_up().map(LevelUpEntry).collect();
+        let reequipped: Vec<_> = self.reequipped().map(ReequipEntry).collect();
+
+        let mut root = serializer.serialize_map(Some(5))?;
+        root.serialize_entry("added", &self.added)?;
+        root.serialize_entry("removed", &self.removed)?;
+        root.serialize_entry("levelledUp", &levelled_up)?;
+        root.serialize_entry("reequipped", &reequipped)?;
+        root.serialize_entry("unchanged", &self.unchanged)?;
+        root.end()
+    }
+}
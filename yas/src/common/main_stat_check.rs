@@ -0,0 +1,130 @@
+use std::fmt::{Display, Formatter};
+
+/// The outcome of cross-checking an OCR'd main stat value against its level
+#[derive(Debug, Clone, PartialEq)]
+pub enum MainStatCheck {
+    Consistent,
+    /// There is no curve for this item, so it is not checked
+    Unchecked,
+    /// The value is on the curve, but at another level than the OCR'd one
+    LevelCorrected { read: i32, inferred: i32 },
+    /// The value is slightly off the curve, and is replaced by the value at the OCR'd level
+    ValueCorrected { read: f64, expected: f64 },
+    /// Neither the value nor the level can be trusted, or the value is too far off the curve
+    /// to be a misread digit of the right stat
+    Rejected,
+}
+
+/// How far, relative to the value on the curve, an OCR'd value may be off and still be corrected.
+/// Anything further is more likely a misread stat name than a misread value
+const MAX_CORRECTION_RATIO: f64 = 0.1;
+
+impl MainStatCheck {
+    /// Cross-check `value` and `level` against `curve`, which holds the main stat value
+    /// of every level from 0, in the same unit as `value`.
+    ///
+    /// A value off the curve is only corrected when it is close to the value at the OCR'd level
+    pub fn check(curve: &[f64], value: f64, level: i32, tolerance: f64) -> MainStatCheck {
+        let level_valid = level >= 0 && (level as usize) < curve.len();
+        if level_valid && (curve[level as usize] - value).abs() <= tolerance {
+            return MainStatCheck::Consistent;
+        }
+
+        // an OCR'd value which is exactly on the curve is more likely right than the level,
+        // since a misread digit almost never lands on another level's value
+        let mut levels = curve.iter()
+            .enumerate()
+            .filter(|(_, v)| (**v - value).abs() <= tolerance)
+            .map(|(l, _)| l as i32);
+        if let (Some(inferred), None) = (levels.next(), levels.next()) {
+            return MainStatCheck::LevelCorrected { read: level, inferred };
+        }
+
+        if level_valid {
+            let expected = curve[level as usize];
+            if (expected - value).abs() <= tolerance.max(expected * MAX_CORRECTION_RATIO) {
+                return MainStatCheck::ValueCorrected { read: value, expected };
+            }
+        }
+
+        MainStatCheck::Rejected
+    }
+
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, MainStatCheck::Rejected)
+    }
+
+    /// Whether the item was changed by the check
+    pub fn is_corrected(&self) -> bool {
+        matches!(self, MainStatCheck::LevelCorrected { .. } | MainStatCheck::ValueCorrected { .. })
+    }
+}
+
+/// Counts of main stat discrepancies found in a scan, for the scan summary
+#[derive(Debug, Clone, Default)]
+pub struct MainStatCheckSummary {
+    pub unchecked: usize,
+    pub level_corrected: usize,
+    pub value_corrected: usize,
+    pub rejected: usize,
+}
+
+impl MainStatCheckSummary {
+    pub fn add(&mut self, check: &MainStatCheck) {
+        match check {
+            MainStatCheck::Consistent => {},
+            MainStatCheck::Unchecked => self.unchecked += 1,
+            MainStatCheck::LevelCorrected { .. } => self.level_corrected += 1,
+            MainStatCheck::ValueCorrected { .. } => self.value_corrected += 1,
+            MainStatCheck::Rejected => self.rejected += 1,
+        }
+    }
+
+    pub fn has_discrepancy(&self) -> bool {
+        self.level_corrected + self.value_corrected + self.rejected > 0
+    }
+}
+
+impl Display for MainStatCheckSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "主词条校验：修正等级 {} 件，修正主词条数值 {} 件，丢弃 {} 件，未校验 {} 件",
+            self.level_corrected, self.value_corrected, self.rejected, self.unchecked
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVE: [f64; 5] = [7.0, 9.0, 11.0, 12.9, 14.9];
+
+    #[test]
+    fn test_consistent() {
+        assert_eq!(MainStatCheck::check(&CURVE, 11.0, 2, 0.15), MainStatCheck::Consistent);
+    }
+
+    #[test]
+    fn test_level_corrected() {
+        assert_eq!(
+            MainStatCheck::check(&CURVE, 12.9, 1, 0.15),
+            MainStatCheck::LevelCorrected { read: 1, inferred: 3 }
+        );
+    }
+
+    #[test]
+    fn test_value_corrected_within_tolerance() {
+        assert_eq!(
+            MainStatCheck::check(&CURVE, 14.2, 4, 0.15),
+            MainStatCheck::ValueCorrected { read: 14.2, expected: 14.9 }
+        );
+    }
+
+    #[test]
+    fn test_far_off_value_rejected() {
+        assert_eq!(MainStatCheck::check(&CURVE, 46.6, 4, 0.15), MainStatCheck::Rejected);
+        assert_eq!(MainStatCheck::check(&CURVE, 1.5, 4, 0.15), MainStatCheck::Rejected);
+    }
+}
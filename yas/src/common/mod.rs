@@ -1,3 +1,8 @@
 pub mod cancel;
 pub mod color;
+pub mod fuzzy_match;
 pub mod image_ext;
+pub mod image_hash;
+pub mod inventory_diff;
+pub mod main_stat_check;
+pub mod sub_stat_roll;
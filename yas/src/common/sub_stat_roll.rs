@@ -0,0 +1,407 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use log::warn;
+
+/// The values a single sub stat roll can take, in displayed unit (e.g. `3.89` for 3.89%)
+#[derive(Debug, Clone, Copy)]
+pub struct RollTable {
+    pub tiers: &'static [f64],
+    /// How far each tier value in the table may be from the real one, because of rounding
+    pub error: f64,
+}
+
+impl RollTable {
+    pub const fn new(tiers: &'static [f64], error: f64) -> Self {
+        RollTable { tiers, error }
+    }
+
+    /// Smallest distance between `value` and any sum of `n` rolls
+    pub fn distance(&self, value: f64, n: usize) -> f64 {
+        fn search(tiers: &[f64], n: usize, sum: f64, value: f64, best: &mut f64) {
+            if n == 0 {
+                *best = best.min((sum - value).abs());
+                return;
+            }
+            // rolls are unordered, so only visit each multiset once
+            for (i, tier) in tiers.iter().enumerate() {
+                search(&tiers[i..], n - 1, sum + tier, value, best);
+            }
+        }
+
+        let mut best = f64::MAX;
+        search(self.tiers, n, 0.0, value, &mut best);
+        best
+    }
+
+    /// All roll counts within `1..=max_rolls` that can sum up to a value in `value ± tolerance`
+    pub fn possible_roll_counts(&self, value: f64, tolerance: f64, max_rolls: usize) -> Vec<usize> {
+        (1..=max_rolls)
+            .filter(|&n| self.distance(value, n) <= tolerance + self.error * n as f64 + 1e-6)
+            .collect()
+    }
+}
+
+/// How the sub stats of an item were rolled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RollInference {
+    /// Number of rolls of each present sub stat, in display order
+    pub roll_counts: Vec<usize>,
+    /// Number of sub stats the item dropped with
+    pub initial_count: usize,
+}
+
+impl RollInference {
+    pub fn total_rolls(&self) -> usize {
+        self.roll_counts.iter().sum()
+    }
+
+    pub fn upgrade_count(&self) -> usize {
+        self.total_rolls() - self.initial_count
+    }
+
+    /// Number of upgrades that went into each sub stat.
+    ///
+    /// A sub stat that is not there when the item drops is added by an upgrade,
+    /// so its first roll counts as an upgrade too
+    pub fn upgrades(&self) -> Vec<usize> {
+        self.roll_counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| if i < self.initial_count { count - 1 } else { count })
+            .collect()
+    }
+}
+
+/// Choose a roll count for every sub stat from `candidates`, such that the rolls add up
+/// to an item that dropped with `initial_counts` sub stats and was upgraded `upgrades` times.
+///
+/// Returns `None` if no combination is consistent
+pub fn infer_rolls(
+    candidates: &[Vec<usize>],
+    initial_counts: RangeInclusive<usize>,
+    upgrades: usize,
+) -> Option<RollInference> {
+    fn search(candidates: &[Vec<usize>], remaining: usize, chosen: &mut Vec<usize>) -> bool {
+        if candidates.is_empty() {
+            return remaining == 0;
+        }
+        for &count in candidates[0].iter() {
+            if count > remaining {
+                break;
+            }
+            chosen.push(count);
+            if search(&candidates[1..], remaining - count, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+        false
+    }
+
+    let sub_stat_count = candidates.len();
+    for initial_count in initial_counts {
+        if initial_count > sub_stat_count {
+            break;
+        }
+        // a sub stat is added instead of rolled until there are four of them
+        if sub_stat_count < 4 && initial_count + upgrades != sub_stat_count {
+            continue;
+        }
+
+        let mut chosen = Vec::new();
+        if search(candidates, initial_count + upgrades, &mut chosen) {
+            return Some(RollInference {
+                roll_counts: chosen,
+                initial_count,
+            });
+        }
+    }
+
+    None
+}
+
+/// A sub stat that can be checked against a [`RollTable`]
+pub trait RollStat: Clone {
+    /// The roll table of this sub stat on an item of `star`.
+    /// Returns `None` if it cannot be a sub stat there
+    fn roll_table(&self, star: i32) -> Option<RollTable>;
+
+    /// The displayed value in table unit and how far the real value may be from it
+    fn displayed_value(&self) -> (f64, f64);
+
+    fn value(&self) -> f64;
+
+    fn set_value(&mut self, value: f64);
+
+    fn set_roll_count(&mut self, roll_count: Option<usize>);
+
+    /// Mark the value as one that cannot be made up of legal rolls
+    fn set_impossible(&mut self, impossible: bool);
+
+    /// Roll counts within `1..=max_rolls` that can make up this sub stat
+    fn possible_roll_counts(&self, table: &RollTable, max_rolls: usize) -> Vec<usize> {
+        let (value, tolerance) = self.displayed_value();
+        table.possible_roll_counts(value, tolerance, max_rolls)
+    }
+}
+
+/// An item with up to four sub stats rolled from a [`RollTable`]
+pub trait RollItem {
+    type Stat: RollStat + fmt::Display;
+
+    /// What the item is called in log messages
+    const ITEM_NAME: &'static str;
+
+    /// Levels between two upgrades
+    const LEVELS_PER_UPGRADE: i32;
+
+    /// Stars that have a roll table. Items of other stars are not checked
+    const ROLL_TABLE_STARS: RangeInclusive<i32>;
+
+    fn star(&self) -> i32;
+
+    fn level(&self) -> i32;
+
+    fn sub_stats_mut(&mut self) -> Vec<&mut Self::Stat>;
+
+    /// Check every sub stat against the roll table and fill in the inferred roll counts.
+    ///
+    /// A value that cannot be made up of legal rolls but can after dividing it by 10 is
+    /// corrected, since that is what a decimal point missed by OCR looks like. Other impossible
+    /// values are kept as they are, but marked so that exports can flag them
+    fn check_sub_stat_rolls(&mut self) -> Option<RollInference> {
+        let star = self.star();
+        let level = self.level();
+        let upgrades = (level / Self::LEVELS_PER_UPGRADE).max(0) as usize;
+        let max_rolls = upgrades + 1;
+
+        if !Self::ROLL_TABLE_STARS.contains(&star) {
+            for stat in self.sub_stats_mut() {
+                stat.set_roll_count(None);
+                stat.set_impossible(false);
+            }
+            return None;
+        }
+
+        let mut valid = true;
+        let mut stats = Vec::new();
+        let mut candidates = Vec::new();
+        for stat in self.sub_stats_mut() {
+            stat.set_roll_count(None);
+            stat.set_impossible(false);
+
+            let table = match stat.roll_table(star) {
+                Some(v) => v,
+                None => {
+                    warn!("副词条 {} 不可能出现在 {} 星{}上", stat, star, Self::ITEM_NAME);
+                    stat.set_impossible(true);
+                    valid = false;
+                    continue;
+                },
+            };
+
+            let mut counts = stat.possible_roll_counts(&table, max_rolls);
+            if counts.is_empty() {
+                let mut corrected = stat.clone();
+                corrected.set_value(stat.value() / 10.0);
+                counts = corrected.possible_roll_counts(&table, max_rolls);
+                if counts.is_empty() {
+                    warn!("副词条 {} 不可能出现在 {} 星 {} 级{}上", stat, star, level, Self::ITEM_NAME);
+                    stat.set_impossible(true);
+                    valid = false;
+                    continue;
+                }
+
+                warn!("副词条 {} 不合法，修正为 {}", stat, corrected.value());
+                *stat = corrected;
+            }
+
+            stats.push(stat);
+            candidates.push(counts);
+        }
+
+        let inference = if valid {
+            infer_rolls(&candidates, initial_sub_stat_counts(star), upgrades)
+        } else {
+            None
+        };
+
+        match &inference {
+            Some(inference) => {
+                for (stat, &count) in stats.into_iter().zip(inference.roll_counts.iter()) {
+                    stat.set_roll_count(Some(count));
+                }
+            },
+            None => {
+                if valid {
+                    warn!("{} 星 {} 级{}的副词条强化次数不一致", star, level, Self::ITEM_NAME);
+                }
+                // still fill in the sub stats whose roll count is certain
+                for (stat, counts) in stats.into_iter().zip(candidates.iter()) {
+                    if counts.len() == 1 {
+                        stat.set_roll_count(Some(counts[0]));
+                    }
+                }
+            },
+        }
+
+        inference
+    }
+}
+
+/// Number of sub stats an item of `star` can drop with
+pub fn initial_sub_stat_counts(star: i32) -> RangeInclusive<usize> {
+    match star {
+        5 => 3..=4,
+        4 => 2..=3,
+        3 => 1..=2,
+        2 => 0..=1,
+        _ => 0..=0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CRIT_RATE: RollTable = RollTable::new(&[2.72, 3.11, 3.50, 3.89], 0.005);
+
+    #[derive(Debug, Clone)]
+    struct Stat {
+        value: f64,
+        roll_count: Option<usize>,
+        impossible: bool,
+    }
+
+    impl fmt::Display for Stat {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.value)
+        }
+    }
+
+    impl RollStat for Stat {
+        fn roll_table(&self, star: i32) -> Option<RollTable> {
+            (star == 5).then_some(CRIT_RATE)
+        }
+
+        fn displayed_value(&self) -> (f64, f64) {
+            (self.value, 0.05)
+        }
+
+        fn value(&self) -> f64 {
+            self.value
+        }
+
+        fn set_value(&mut self, value: f64) {
+            self.value = value;
+        }
+
+        fn set_roll_count(&mut self, roll_count: Option<usize>) {
+            self.roll_count = roll_count;
+        }
+
+        fn set_impossible(&mut self, impossible: bool) {
+            self.impossible = impossible;
+        }
+    }
+
+    struct Item {
+        star: i32,
+        level: i32,
+        sub_stats: Vec<Stat>,
+    }
+
+    impl Item {
+        fn new(star: i32, level: i32, values: &[f64]) -> Self {
+            let sub_stats = values
+                .iter()
+                .map(|&value| Stat {
+                    value,
+                    roll_count: None,
+                    impossible: false,
+                })
+                .collect();
+            Item { star, level, sub_stats }
+        }
+    }
+
+    impl RollItem for Item {
+        type Stat = Stat;
+
+        const ITEM_NAME: &'static str = "item";
+        const LEVELS_PER_UPGRADE: i32 = 4;
+        const ROLL_TABLE_STARS: RangeInclusive<i32> = 4..=5;
+
+        fn star(&self) -> i32 {
+            self.star
+        }
+
+        fn level(&self) -> i32 {
+            self.level
+        }
+
+        fn sub_stats_mut(&mut self) -> Vec<&mut Stat> {
+            self.sub_stats.iter_mut().collect()
+        }
+    }
+
+    #[test]
+    fn test_distance() {
+        assert!(CRIT_RATE.distance(3.89, 1) < 1e-9);
+        // 3.11 + 3.89 or 3.50 + 3.50
+        assert!(CRIT_RATE.distance(7.0, 2) < 1e-9);
+        assert!((CRIT_RATE.distance(2.0, 1) - 0.72).abs() < 1e-9);
+
+        assert_eq!(CRIT_RATE.possible_roll_counts(3.9, 0.05, 6), vec![1]);
+        assert_eq!(CRIT_RATE.possible_roll_counts(7.0, 0.05, 6), vec![2]);
+        assert!(CRIT_RATE.possible_roll_counts(1.0, 0.05, 6).is_empty());
+    }
+
+    #[test]
+    fn test_infer_rolls() {
+        // dropped with 4 sub stats and upgraded once
+        let inference = infer_rolls(&[vec![1], vec![1], vec![1, 2], vec![2]], 3..=4, 1).unwrap();
+        assert_eq!(inference.roll_counts, vec![1, 1, 1, 2]);
+        assert_eq!(inference.initial_count, 4);
+        assert_eq!(inference.upgrades(), vec![0, 0, 0, 1]);
+
+        // dropped with 3 sub stats, the fourth one was added by the only upgrade
+        let inference = infer_rolls(&[vec![1], vec![1], vec![1], vec![1]], 3..=4, 1).unwrap();
+        assert_eq!(inference.initial_count, 3);
+        assert_eq!(inference.upgrades(), vec![0, 0, 0, 1]);
+
+        // too many rolls for an item that was never upgraded
+        assert_eq!(infer_rolls(&[vec![1], vec![1], vec![2], vec![1]], 3..=4, 0), None);
+    }
+
+    #[test]
+    fn test_missed_decimal_point() {
+        let mut item = Item::new(5, 0, &[39.0, 3.1, 7.0]);
+        item.check_sub_stat_rolls();
+
+        let stat = &item.sub_stats[0];
+        assert!((stat.value - 3.9).abs() < 1e-9);
+        assert_eq!(stat.roll_count, Some(1));
+        assert!(!stat.impossible);
+    }
+
+    #[test]
+    fn test_impossible_value() {
+        // neither 1.0 nor 0.1 can be made up of crit rate rolls
+        let mut item = Item::new(5, 20, &[1.0, 3.1, 3.5, 3.9]);
+        assert_eq!(item.check_sub_stat_rolls(), None);
+
+        let stat = &item.sub_stats[0];
+        assert!((stat.value - 1.0).abs() < 1e-9);
+        assert!(stat.impossible);
+        assert_eq!(stat.roll_count, None);
+    }
+
+    #[test]
+    fn test_star_without_roll_table() {
+        let mut item = Item::new(2, 4, &[1.0, 100.0]);
+        assert_eq!(item.check_sub_stat_rolls(), None);
+        assert!(item.sub_stats.iter().all(|stat| !stat.impossible && stat.roll_count.is_none()));
+    }
+}
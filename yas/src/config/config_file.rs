@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+use clap::{Arg, ArgMatches, Command};
+use prettytable::{row, Table};
+use serde_json::{Map, Value};
+
+/// The options of every scanner in one file, `yas.yaml` or `yas.toml`.
+///
+/// ```yaml
+/// common:            # every game
+///   output-dir: exports
+/// genshin:
+///   format: good
+///   min-star: 5
+/// wutheringwaves:
+///   min-star: 5
+/// profiles:
+///   fast:
+///     genshin:
+///       max-wait-switch-item: 200
+/// ```
+///
+/// Options are named as on the command line. A profile is applied over the sections above it.
+/// An option in `common` is only used by the games that have it
+pub struct ConfigFile {
+    path: PathBuf,
+    root: Map<String, Value>,
+}
+
+impl ConfigFile {
+    pub const DEFAULT_FILENAMES: [&'static str; 3] = ["yas.yaml", "yas.yml", "yas.toml"];
+
+    pub const COMMON_SECTION: &'static str = "common";
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<ConfigFile> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)?;
+        let root: Value = if path.extension().is_some_and(|x| x == "toml") {
+            toml::from_str(&contents)?
+        } else {
+            serde_yaml::from_str(&contents)?
+        };
+
+        match root {
+            Value::Object(root) => Ok(ConfigFile { path: path.to_path_buf(), root }),
+            // an empty yaml file
+            Value::Null => Ok(ConfigFile { path: path.to_path_buf(), root: Map::new() }),
+            _ => Err(anyhow!("配置文件 {:?} 的顶层必须是表", path)),
+        }
+    }
+
+    /// The first of `DEFAULT_FILENAMES` in the working directory
+    pub fn find_default() -> Result<Option<ConfigFile>> {
+        for name in Self::DEFAULT_FILENAMES {
+            if Path::new(name).exists() {
+                return Ok(Some(Self::load(name)?));
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn profile_names(&self) -> Vec<String> {
+        match self.root.get("profiles") {
+            Some(Value::Object(profiles)) => profiles.keys().cloned().collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn section<'a>(parent: &'a Map<String, Value>, name: &str) -> Result<Option<&'a Map<String, Value>>> {
+        match parent.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::Object(v)) => Ok(Some(v)),
+            Some(_) => Err(anyhow!("配置中的 {} 必须是表", name)),
+        }
+    }
+
+    /// The options of `game`, with `profile` applied
+    pub fn options(&self, game: &str, profile: Option<&str>) -> Result<BTreeMap<String, ConfigOption>> {
+        // each section with whether it is a `common` one
+        let mut sections = vec![
+            (Self::section(&self.root, Self::COMMON_SECTION)?, true),
+            (Self::section(&self.root, game)?, false),
+        ];
+        if let Some(name) = profile {
+            let profile = Self::section(&self.root, "profiles")?
+                .and_then(|profiles| profiles.get(name))
+                .ok_or_else(|| anyhow!("配置文件中没有 {} 配置方案，可用的方案：{:?}", name, self.profile_names()))?;
+            let profile = profile.as_object().ok_or_else(|| anyhow!("配置方案 {} 必须是表", name))?;
+            sections.push((Self::section(profile, Self::COMMON_SECTION)?, true));
+            sections.push((Self::section(profile, game)?, false));
+        }
+
+        let mut result = BTreeMap::<String, ConfigOption>::new();
+        for (section, common) in sections {
+            for (key, value) in section.into_iter().flatten() {
+                let in_game_section = !common || result.get(key).is_some_and(|x| x.in_game_section);
+                result.insert(key.clone(), ConfigOption { value: value.clone(), in_game_section });
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// An option of a game in the config file
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigOption {
+    pub value: Value,
+    /// Whether the section of the game sets it, and not only a `common` section
+    pub in_game_section: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OptionSource {
+    File,
+    Env,
+}
+
+impl OptionSource {
+    fn to_zh_cn(self) -> &'static str {
+        match self {
+            OptionSource::File => "配置文件",
+            OptionSource::Env => "环境变量",
+        }
+    }
+}
+
+fn value_to_strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::String(s) => vec![s.clone()],
+        Value::Array(values) => values.iter().flat_map(value_to_strings).collect(),
+        v => vec![v.to_string()],
+    }
+}
+
+/// `YAS_GENSHIN_MIN_STAR` for `--min-star` of `genshin`
+pub fn env_name(game: &str, long: &str) -> String {
+    format!("YAS_{}_{}", game, long).to_uppercase().replace('-', "_")
+}
+
+/// The options of a game from the config file and the environment, which are used as the
+/// defaults of its command, so that the command line still overrides both
+pub struct ResolvedOptions {
+    values: BTreeMap<String, (Vec<String>, OptionSource)>,
+}
+
+fn find_arg<'a>(cmd: &'a Command, long: &str) -> Option<&'a Arg> {
+    cmd.get_arguments().find(|x| x.get_long() == Some(long))
+}
+
+impl ResolvedOptions {
+    /// Merge the config file, then environment variables such as `YAS_GENSHIN_MIN_STAR`
+    pub fn resolve(cmd: &Command, game: &str, config: Option<&ConfigFile>, profile: Option<&str>) -> Result<ResolvedOptions> {
+        let mut values = BTreeMap::new();
+
+        if let Some(config) = config {
+            for (key, option) in config.options(game, profile)? {
+                if find_arg(cmd, &key).is_none() {
+                    // `common` is shared by every game, and not every game has every option
+                    if !option.in_game_section {
+                        continue;
+                    }
+                    return Err(anyhow!("配置文件 {:?} 中有 {} 不支持的选项 {}", config.path(), game, key));
+                }
+                values.insert(key, (value_to_strings(&option.value), OptionSource::File));
+            }
+        }
+
+        for arg in cmd.get_arguments() {
+            let long = match arg.get_long() {
+                Some(v) => v,
+                None => continue,
+            };
+            if let Ok(value) = std::env::var(env_name(game, long)) {
+                values.insert(String::from(long), (vec![value], OptionSource::Env));
+            }
+        }
+
+        Ok(ResolvedOptions { values })
+    }
+
+    pub fn apply(&self, mut cmd: Command) -> Command {
+        for (long, (values, _)) in self.values.iter() {
+            let id = match find_arg(&cmd, long) {
+                Some(arg) => arg.get_id().clone(),
+                None => continue,
+            };
+            let values = values.clone();
+            cmd = cmd.mut_arg(id, move |arg| arg.default_values(values));
+        }
+        cmd
+    }
+
+    /// Every option of `cmd` with its effective value in `matches`, and where it came from
+    pub fn get_table(&self, cmd: &Command, matches: &ArgMatches) -> Table {
+        let mut table = Table::new();
+        table.add_row(row!["Option", "Value", "Source"]);
+
+        for arg in cmd.get_arguments() {
+            let long = match arg.get_long() {
+                Some(v) if v != "help" && v != "version" => v,
+                _ => continue,
+            };
+            let value = matches.get_raw(arg.get_id().as_str())
+                .map(|values| values.map(|x| x.to_string_lossy()).collect::<Vec<_>>().join(", "))
+                .unwrap_or_default();
+            let source = match (matches.value_source(arg.get_id().as_str()), self.values.get(long)) {
+                (Some(clap::parser::ValueSource::CommandLine), _) => "命令行",
+                (_, Some((_, source))) => source.to_zh_cn(),
+                (Some(_), None) => "默认值",
+                (None, None) => "",
+            };
+            table.add_row(row![format!("--{}", long), value, source]);
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::arg;
+
+    use super::*;
+
+    fn load(name: &str, contents: &str) -> ConfigFile {
+        let path = std::env::temp_dir().join(format!("yas_config_{}_{}.yaml", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let config = ConfigFile::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        config
+    }
+
+    #[test]
+    fn test_common_option_of_some_games() {
+        let config = load("common", "common:\n  min-star: 5\nprofiles:\n  fast:\n    common:\n      scroll-delay: 50\n");
+        let artifact = Command::new("genshin")
+            .arg(arg!(--"min-star" <STAR>))
+            .arg(arg!(--"scroll-delay" <MS>));
+        let character = Command::new("starrail-character").arg(arg!(--number <NUMBER>));
+
+        let options = ResolvedOptions::resolve(&artifact, "genshin", Some(&config), Some("fast")).unwrap();
+        let matches = options.apply(artifact).try_get_matches_from(["genshin"]).unwrap();
+        assert_eq!(matches.get_one::<String>("min-star").unwrap(), "5");
+        assert_eq!(matches.get_one::<String>("scroll-delay").unwrap(), "50");
+
+        // the character scanner has neither option, so `common` is skipped for it
+        assert!(ResolvedOptions::resolve(&character, "starrail-character", Some(&config), Some("fast")).is_ok());
+    }
+
+    #[test]
+    fn test_unknown_game_option() {
+        let character = Command::new("starrail-character").arg(arg!(--number <NUMBER>));
+
+        let config = load("game", "starrail-character:\n  min-star: 5\n");
+        assert!(ResolvedOptions::resolve(&character, "starrail-character", Some(&config), None).is_err());
+
+        let config = load("profile", "profiles:\n  fast:\n    starrail-character:\n      min-star: 5\n");
+        assert!(ResolvedOptions::resolve(&character, "starrail-character", Some(&config), None).is_ok());
+        assert!(ResolvedOptions::resolve(&character, "starrail-character", Some(&config), Some("fast")).is_err());
+
+        // also set in `common`, but a game section names it too
+        let config = load("both", "common:\n  min-star: 5\nstarrail-character:\n  min-star: 4\n");
+        assert!(ResolvedOptions::resolve(&character, "starrail-character", Some(&config), None).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub use config_file::{env_name, ConfigFile, ConfigOption, OptionSource, ResolvedOptions};
+
+mod config_file;
@@ -0,0 +1,96 @@
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use anyhow::Result;
+use clap::builder::PossibleValuesParser;
+use log::error;
+
+use crate::export::ExportAssets;
+
+/// A file format that scanned items of type `T` can be exported to
+pub trait ExportFormat<T>: Send + Sync {
+    /// The value of `--format` that selects this format
+    fn name(&self) -> &'static str;
+
+    fn default_filename(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    fn serialize(&self, items: &[T]) -> Result<Vec<u8>>;
+
+    /// Add `items` to `export_assets`, under the default filename in `output_dir`
+    fn emit(&self, items: &[T], output_dir: &Path, export_assets: &mut ExportAssets) {
+        match self.serialize(items) {
+            Ok(contents) => export_assets.add_asset(
+                Some(String::from(self.name())),
+                output_dir.join(self.default_filename()),
+                contents,
+                Some(String::from(self.description()))
+            ),
+            Err(e) => error!("无法导出 {} 格式: {}", self.name(), e),
+        }
+    }
+}
+
+/// The export formats of an item type.
+///
+/// Formats registered before the command line is built can be selected with `--format`,
+/// and are all exported with `--format all`
+pub struct ExportFormatRegistry<T> {
+    formats: RwLock<Vec<Arc<dyn ExportFormat<T>>>>,
+}
+
+impl<T> ExportFormatRegistry<T> {
+    /// The `--format` value that exports every registered format
+    pub const ALL: &'static str = "all";
+
+    pub fn new() -> Self {
+        ExportFormatRegistry {
+            formats: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Register a format, replacing any format of the same name
+    pub fn register<F: ExportFormat<T> + 'static>(&self, format: F) {
+        let mut formats = self.formats.write().unwrap();
+        formats.retain(|x| x.name() != format.name());
+        formats.push(Arc::new(format));
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn ExportFormat<T>>> {
+        let formats = self.formats.read().unwrap();
+        formats.iter().find(|x| x.name() == name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        let formats = self.formats.read().unwrap();
+        formats.iter().map(|x| x.name()).collect()
+    }
+
+    /// Whether `name` is a registered format or `all`
+    pub fn contains(&self, name: &str) -> bool {
+        name == Self::ALL || self.get(name).is_some()
+    }
+
+    /// The formats selected by a `--format` value
+    pub fn select(&self, name: &str) -> Vec<Arc<dyn ExportFormat<T>>> {
+        if name == Self::ALL {
+            self.formats.read().unwrap().clone()
+        } else {
+            self.get(name).into_iter().collect()
+        }
+    }
+
+    /// Parser for `--format`, accepting the registered formats and `all`
+    pub fn value_parser(&self) -> PossibleValuesParser {
+        let mut names = self.names();
+        names.push(Self::ALL);
+        PossibleValuesParser::new(names)
+    }
+}
+
+impl<T> Default for ExportFormatRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
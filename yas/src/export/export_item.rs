@@ -7,6 +7,9 @@ pub struct ExportItem {
     pub filename: PathBuf,
     pub name: Option<String>,
     pub description: Option<String>,
+    /// Written to exactly `filename` even if it exists, e.g. a database that is merged into.
+    /// Renaming and no-clobber do not apply
+    pub in_place: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -15,6 +18,8 @@ pub struct StatisticItem {
     pub filename: PathBuf,
     pub name: Option<String>,
     pub description: Option<String>,
+    /// Why the item could not be saved
+    pub error: Option<String>,
 }
 
 impl StatisticItem {
@@ -23,7 +28,15 @@ impl StatisticItem {
             size_in_bytes: export_item.contents.len(),
             filename: export_item.filename.clone(),
             name: export_item.name.clone(),
-            description: export_item.description.clone()
+            description: export_item.description.clone(),
+            error: None,
+        }
+    }
+
+    pub fn with_error(self, error: String) -> Self {
+        StatisticItem {
+            error: Some(error),
+            ..self
         }
     }
 }
@@ -26,12 +26,32 @@ impl ExportStatistics {
 
         table
     }
+
+    pub fn get_failed_table(&self) -> Table {
+        let mut table = Table::new();
+
+        table.add_row(row!["Name", "File", "Error"]);
+        for item in self.failed_items.iter() {
+            table.add_row(Row::new(vec![
+                prettytable::Cell::new(item.name.as_ref().unwrap_or(&Default::default())),
+                prettytable::Cell::new(&format!("{:?}", item.filename)),
+                prettytable::Cell::new(item.error.as_ref().unwrap_or(&Default::default())),
+            ]));
+        }
+
+        table
+    }
 }
 
 impl fmt::Display for ExportStatistics {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let table = self.get_table();
-        write!(f, "{}", table)
+        write!(f, "{}", table)?;
+        if !self.failed_items.is_empty() {
+            writeln!(f, "{} 个文件导出失败：", self.failed_items.len())?;
+            write!(f, "{}", self.get_failed_table())?;
+        }
+        Ok(())
     }
 }
 
@@ -1,11 +1,120 @@
-use std::fs::File;
+use std::ffi::OsString;
+use std::fs::OpenOptions;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use anyhow::{anyhow, Result};
 use log::error;
+use zip::write::FileOptions;
+use zip::ZipWriter;
 
 use crate::export::{ExportItem, ExportStatistics, StatisticItem};
 
+/// How `ExportAssets::save` writes files
+#[derive(clap::Args, Default, Clone)]
+pub struct ExportSaveConfig {
+    #[arg(id = "timestamp-filenames", long = "timestamp-filenames", help = "在输出文件名后加上时间，如 mona_20240101-120000.json")]
+    pub timestamp_filenames: bool,
+
+    #[arg(id = "filename-suffix", long = "filename-suffix", help = "在输出文件名后加上后缀，如 UID", value_name = "SUFFIX")]
+    pub filename_suffix: Option<String>,
+
+    #[arg(id = "no-clobber", long = "no-clobber", help = "不覆盖已存在的文件")]
+    pub no_clobber: bool,
+
+    #[arg(id = "zip", long = "zip", help = "同时将所有输出文件打包到一个 zip 文件", value_name = "FILE")]
+    pub zip: Option<String>,
+}
+
+impl ExportSaveConfig {
+    /// `mona.json` becomes `mona_<timestamp>_<suffix>.json`
+    fn rename(&self, filename: &Path, timestamp: &str) -> PathBuf {
+        let mut stem = filename.file_stem().unwrap_or_default().to_os_string();
+        if self.timestamp_filenames {
+            stem.push("_");
+            stem.push(timestamp);
+        }
+        if let Some(suffix) = self.filename_suffix.as_ref() {
+            stem.push("_");
+            stem.push(suffix);
+        }
+        if let Some(extension) = filename.extension() {
+            stem.push(".");
+            stem.push(extension);
+        }
+        filename.with_file_name(stem)
+    }
+}
+
+/// Write to a temporary file next to `path` and rename it over `path`,
+/// so that a failed write never leaves a half written file behind.
+///
+/// With `no_clobber` the temporary file is hard linked to `path` instead, which fails if `path`
+/// exists, so that checking and publishing the file is a single step. File systems without
+/// hard links, such as FAT32 and exFAT, create `path` exclusively and write it in place instead
+fn write_atomic(path: &Path, contents: &[u8], no_clobber: bool) -> Result<()> {
+    static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    // unique per process and call, so concurrent saves never share a temporary file
+    let mut temp_name = OsString::from(".");
+    temp_name.push(path.file_name().ok_or_else(|| anyhow!("不是文件路径"))?);
+    temp_name.push(format!(".{}.{}.tmp", std::process::id(), TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)));
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut result = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| if no_clobber {
+            std::fs::hard_link(&temp_path, path)
+        } else {
+            std::fs::rename(&temp_path, path)
+        });
+    if result.is_err() || no_clobber {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+
+    if no_clobber && matches!(&result, Err(e) if e.kind() == std::io::ErrorKind::Unsupported) {
+        result = write_new(path, contents);
+    }
+    match result {
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Err(anyhow!("文件已存在")),
+        result => Ok(result?),
+    }
+}
+
+/// Create `path` and write `contents` to it, failing if it exists
+fn write_new(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    let result = file.write_all(contents).and_then(|_| file.sync_all());
+    if result.is_err() {
+        drop(file);
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+fn zip_assets(assets: &[ExportItem]) -> Result<Vec<u8>> {
+    let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    for item in assets.iter() {
+        let name = item.filename.file_name().unwrap_or_default().to_string_lossy();
+        writer.start_file(name, FileOptions::default())?;
+        writer.write_all(&item.contents)?;
+    }
+    Ok(writer.finish()?.into_inner())
+}
+
 pub struct ExportAssets {
     pub assets: Vec<ExportItem>
 }
@@ -21,36 +130,115 @@ impl ExportAssets {
             filename,
             name,
             description,
+            in_place: false,
+        })
+    }
+
+    /// Add an asset that updates an existing file, so it keeps its path and always overwrites it
+    pub fn add_in_place_asset(&mut self, name: Option<String>, filename: PathBuf, contents: Vec<u8>, description: Option<String>) {
+        self.assets.push(ExportItem {
+            contents,
+            filename,
+            name,
+            description,
+            in_place: true,
         })
     }
 
     pub fn save(&self) -> ExportStatistics {
+        self.save_with(&ExportSaveConfig::default())
+    }
+
+    pub fn save_with(&self, config: &ExportSaveConfig) -> ExportStatistics {
         let mut stat = ExportStatistics::new();
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
 
+        let mut saved = Vec::new();
         for item in self.assets.iter() {
-            let mut file = match File::create(&item.filename) {
+            let (filename, no_clobber) = if item.in_place {
+                (item.filename.clone(), false)
+            } else {
+                (config.rename(&item.filename, &timestamp), config.no_clobber)
+            };
+            let item = ExportItem {
+                filename,
+                ..item.clone()
+            };
+
+            match write_atomic(&item.filename, &item.contents, no_clobber) {
                 Err(why) => {
-                    stat.failed_items.push(StatisticItem::from_export_item(item));
-                    error!("无法创建文件 {:?}: {}", &item.filename, why);
-                    continue;
+                    error!("无法写入文件 {:?}: {}", &item.filename, why);
+                    stat.failed_items.push(StatisticItem::from_export_item(&item).with_error(why.to_string()));
                 },
-                Ok(file) => {
-                    file
+                Ok(_) => {
+                    stat.exported_assets.push(StatisticItem::from_export_item(&item));
+                    saved.push(item);
                 },
-            };
+            }
+        }
 
-            match file.write_all(&item.contents) {
+        if let Some(zip) = config.zip.as_ref() {
+            let path = config.rename(Path::new(zip), &timestamp);
+            let result = zip_assets(&saved)
+                .and_then(|contents| {
+                    write_atomic(&path, &contents, config.no_clobber)?;
+                    Ok(contents.len())
+                });
+            let item = StatisticItem {
+                size_in_bytes: *result.as_ref().unwrap_or(&0),
+                filename: path,
+                name: Some(String::from("zip")),
+                description: Some(String::from("所有输出文件的压缩包")),
+                error: None,
+            };
+            match result {
                 Err(why) => {
-                    stat.failed_items.push(StatisticItem::from_export_item(item));
                     error!("无法写入文件 {:?}: {}", &item.filename, why);
-                    continue;
+                    stat.failed_items.push(item.with_error(why.to_string()));
                 },
-                Ok(_) => (),
+                Ok(_) => stat.exported_assets.push(item),
             }
-
-            stat.exported_assets.push(StatisticItem::from_export_item(item));
         }
 
         stat
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_no_clobber() {
+        let dir = std::env::temp_dir().join(format!("yas_write_atomic_{}", std::process::id()));
+        let path = dir.join("nested").join("mona.json");
+
+        write_atomic(&path, b"first", true).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+
+        assert!(write_atomic(&path, b"second", true).is_err());
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+
+        write_atomic(&path, b"third", false).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"third");
+
+        // no temporary file is left behind
+        assert_eq!(std::fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_new() {
+        let dir = std::env::temp_dir().join(format!("yas_write_new_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mona.json");
+
+        write_new(&path, b"first").unwrap();
+        let e = write_new(&path, b"second").unwrap_err();
+        assert_eq!(e.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read(&path).unwrap(), b"first");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static::lazy_static! {
+    static ref SUBSCRIBERS: Mutex<Vec<Sender<Value>>> = Mutex::new(Vec::new());
+}
+
+/// Stream events to stdout as JSON Lines, for GUIs and scripts wrapping yas.
+///
+/// Every line is an object with a `type`, one of `item`, `removed`, `progress`, `status` and `error`.
+/// An `item` with the `index` of an earlier one replaces it, and `removed` withdraws it, once
+/// the checks after recognition correct or reject the item.
+/// The same events are sent to `subscribe_events`, e.g. for `yas serve`
+#[derive(clap::Args)]
+pub struct JsonLinesConfig {
+    #[arg(id = "json-lines", long = "json-lines", help = "将识别到的物品和进度以 JSON Lines 实时输出到标准输出，日志输出到标准错误")]
+    pub json_lines: bool,
+}
+
+impl JsonLinesConfig {
+    pub fn apply(&self) {
+        set_json_lines_enabled(self.json_lines);
+    }
+}
+
+pub fn set_json_lines_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_json_lines_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Receive every event from now on, until the receiver is dropped
+pub fn subscribe_events() -> Receiver<Value> {
+    let (tx, rx) = mpsc::channel();
+    SUBSCRIBERS.lock().unwrap().push(tx);
+    rx
+}
+
+/// Whether events are written anywhere, so that items need to be converted for them
+pub fn is_event_stream_active() -> bool {
+    is_json_lines_enabled() || !SUBSCRIBERS.lock().unwrap().is_empty()
+}
+
+fn emit_event(value: Value) {
+    if is_json_lines_enabled() {
+        // one locked write per line, so that lines from different threads never interleave
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", value);
+        let _ = stdout.flush();
+    }
+
+    let mut subscribers = SUBSCRIBERS.lock().unwrap();
+    subscribers.retain(|tx| tx.send(value.clone()).is_ok());
+}
+
+/// An item as soon as it is recognized, `index` is its position in the inventory
+pub fn emit_item<T: Serialize>(index: usize, item: &T) {
+    if !is_event_stream_active() {
+        return;
+    }
+    match serde_json::to_value(item) {
+        Ok(item) => emit_event(json!({ "type": "item", "index": index, "item": item })),
+        Err(e) => emit_error(&e.to_string()),
+    }
+}
+
+/// An item emitted at `index` before turned out to be invalid
+pub fn emit_removed(index: usize) {
+    emit_event(json!({ "type": "removed", "index": index }));
+}
+
+/// `current` of `total` items have been captured
+pub fn emit_progress(current: usize, total: usize) {
+    emit_event(json!({ "type": "progress", "current": current, "total": total }));
+}
+
+pub fn emit_status(message: &str) {
+    emit_event(json!({ "type": "status", "message": message }));
+}
+
+pub fn emit_error(message: &str) {
+    emit_event(json!({ "type": "error", "message": message }));
+}
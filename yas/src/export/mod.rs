@@ -1,10 +1,21 @@
 pub use asset_emitter::AssetEmitter;
+pub use export_format::{ExportFormat, ExportFormatRegistry};
 pub use export_item::{ExportItem, StatisticItem};
 pub use export_statistics::ExportStatistics;
-pub use exporter::ExportAssets;
+pub use exporter::{ExportAssets, ExportSaveConfig};
+pub use json_lines::{
+    emit_error, emit_item, emit_progress, emit_removed, emit_status, is_event_stream_active, is_json_lines_enabled,
+    set_json_lines_enabled, subscribe_events, JsonLinesConfig,
+};
+pub use scan_history::{HistoryEntry, HistoryItem, HistoryRun, HistorySighting, ScanHistory, ScanHistoryConfig};
+pub use template::{split_indexed_field, ItemTemplate, TemplateExport, TemplateExportConfig, TemplateItem};
 
 mod exporter;
+mod export_format;
 mod export_item;
 mod export_statistics;
+mod json_lines;
 mod asset_emitter;
+mod scan_history;
+mod template;
 
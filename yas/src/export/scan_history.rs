@@ -0,0 +1,279 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use prettytable::{row, Table};
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::common::inventory_diff::{match_items, InventoryItem};
+
+/// An item that can be stored in the scan history database
+pub trait HistoryItem: InventoryItem + Sized {
+    /// The game the item belongs to, e.g. `genshin`
+    const GAME: &'static str;
+
+    /// The item as stored in the `data` column of a sighting
+    fn to_history_json(&self) -> Result<String>;
+
+    fn from_history_json(json: &str) -> Result<Self>;
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    timestamp INTEGER NOT NULL,
+    game TEXT NOT NULL,
+    uid TEXT,
+    tool_version TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS items (
+    id INTEGER PRIMARY KEY,
+    game TEXT NOT NULL,
+    uid TEXT,
+    description TEXT NOT NULL,
+    first_run INTEGER NOT NULL REFERENCES runs(id),
+    last_run INTEGER NOT NULL REFERENCES runs(id)
+);
+CREATE TABLE IF NOT EXISTS sightings (
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    item_id INTEGER NOT NULL REFERENCES items(id),
+    level INTEGER NOT NULL,
+    equip TEXT,
+    changes TEXT NOT NULL,
+    data TEXT NOT NULL,
+    PRIMARY KEY (run_id, item_id)
+);
+CREATE INDEX IF NOT EXISTS sightings_item ON sightings(item_id);
+";
+
+pub struct HistoryRun {
+    pub id: i64,
+    pub time: String,
+    pub game: String,
+    pub uid: Option<String>,
+    pub tool_version: String,
+    pub item_count: usize,
+}
+
+pub struct HistoryEntry {
+    pub item_id: i64,
+    pub description: String,
+    pub first_seen: String,
+    pub last_seen: String,
+}
+
+pub struct HistorySighting {
+    pub run_id: i64,
+    pub time: String,
+    pub level: i32,
+    pub equip: Option<String>,
+    /// The sub stats changed since the previous sighting, one per line
+    pub changes: String,
+}
+
+/// A SQLite database that every scan is appended to.
+///
+/// An item is matched to the same item in the previous run of the same game and UID,
+/// so that it can be followed across runs as it is levelled up or re-equipped
+pub struct ScanHistory {
+    connection: Connection,
+}
+
+impl ScanHistory {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<ScanHistory> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(ScanHistory { connection })
+    }
+
+    fn last_run(&self, game: &str, uid: Option<&str>) -> Result<Option<i64>> {
+        let run = self.connection.query_row(
+            "SELECT id FROM runs WHERE game = ?1 AND uid IS ?2 ORDER BY id DESC LIMIT 1",
+            params![game, uid],
+            |row| row.get(0)
+        ).optional()?;
+        Ok(run)
+    }
+
+    /// Append a run of `items`, returning the id of the run
+    pub fn record_run<T: HistoryItem>(&mut self, uid: Option<&str>, tool_version: &str, items: &[T]) -> Result<i64> {
+        let previous_run = self.last_run(T::GAME, uid)?;
+
+        let mut previous_ids = Vec::new();
+        let mut previous_items = Vec::new();
+        if let Some(run_id) = previous_run {
+            let mut statement = self.connection.prepare("SELECT item_id, data FROM sightings WHERE run_id = ?1")?;
+            let rows = statement.query_map(params![run_id], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            for row in rows {
+                let (item_id, data) = row?;
+                previous_ids.push(item_id);
+                previous_items.push(T::from_history_json(&data)?);
+            }
+        }
+        let matches = match_items(&previous_items, items);
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64;
+        let transaction = self.connection.transaction()?;
+        transaction.execute(
+            "INSERT INTO runs (timestamp, game, uid, tool_version) VALUES (?1, ?2, ?3, ?4)",
+            params![timestamp, T::GAME, uid, tool_version]
+        )?;
+        let run_id = transaction.last_insert_rowid();
+
+        for (item, previous) in items.iter().zip(matches) {
+            let (item_id, changes) = match previous {
+                Some(index) => {
+                    transaction.execute(
+                        "UPDATE items SET description = ?1, last_run = ?2 WHERE id = ?3",
+                        params![item.describe(), run_id, previous_ids[index]]
+                    )?;
+                    (previous_ids[index], previous_items[index].sub_stat_changes(item).join("\n"))
+                },
+                None => {
+                    transaction.execute(
+                        "INSERT INTO items (game, uid, description, first_run, last_run) VALUES (?1, ?2, ?3, ?4, ?4)",
+                        params![T::GAME, uid, item.describe(), run_id]
+                    )?;
+                    (transaction.last_insert_rowid(), String::new())
+                },
+            };
+
+            transaction.execute(
+                "INSERT INTO sightings (run_id, item_id, level, equip, changes, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![run_id, item_id, item.level(), item.equip(), changes, item.to_history_json()?]
+            )?;
+        }
+        transaction.commit()?;
+
+        Ok(run_id)
+    }
+
+    pub fn runs(&self) -> Result<Vec<HistoryRun>> {
+        let mut statement = self.connection.prepare(
+            "SELECT runs.id, datetime(runs.timestamp, 'unixepoch', 'localtime'), runs.game, runs.uid, runs.tool_version,
+                    (SELECT COUNT(*) FROM sightings WHERE sightings.run_id = runs.id)
+             FROM runs ORDER BY runs.id"
+        )?;
+        let rows = statement.query_map([], |row| {
+            Ok(HistoryRun {
+                id: row.get(0)?,
+                time: row.get(1)?,
+                game: row.get(2)?,
+                uid: row.get(3)?,
+                tool_version: row.get(4)?,
+                item_count: row.get(5)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    fn query_entries(&self, condition: &str, game: &str, uid: Option<&str>) -> Result<Vec<HistoryEntry>> {
+        let sql = format!(
+            "SELECT items.id, items.description,
+                    datetime(first.timestamp, 'unixepoch', 'localtime'), datetime(last.timestamp, 'unixepoch', 'localtime')
+             FROM items
+             JOIN runs AS first ON first.id = items.first_run
+             JOIN runs AS last ON last.id = items.last_run
+             WHERE items.game = ?1 AND items.uid IS ?2 AND {}
+             ORDER BY items.id",
+            condition
+        );
+        let mut statement = self.connection.prepare(&sql)?;
+        let rows = statement.query_map(params![game, uid], |row| {
+            Ok(HistoryEntry {
+                item_id: row.get(0)?,
+                description: row.get(1)?,
+                first_seen: row.get(2)?,
+                last_seen: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Every item ever seen of a game and UID
+    pub fn items(&self, game: &str, uid: Option<&str>) -> Result<Vec<HistoryEntry>> {
+        self.query_entries("1", game, uid)
+    }
+
+    /// The items that were not seen in the latest run of a game and UID
+    pub fn removed_items(&self, game: &str, uid: Option<&str>) -> Result<Vec<HistoryEntry>> {
+        let latest_run = self.last_run(game, uid)?.unwrap_or(0);
+        self.query_entries(&format!("items.last_run < {}", latest_run), game, uid)
+    }
+
+    /// Every sighting of an item, oldest first
+    pub fn sightings(&self, item_id: i64) -> Result<Vec<HistorySighting>> {
+        let mut statement = self.connection.prepare(
+            "SELECT sightings.run_id, datetime(runs.timestamp, 'unixepoch', 'localtime'), sightings.level, sightings.equip, sightings.changes
+             FROM sightings JOIN runs ON runs.id = sightings.run_id
+             WHERE sightings.item_id = ?1 ORDER BY sightings.run_id"
+        )?;
+        let rows = statement.query_map(params![item_id], |row| {
+            Ok(HistorySighting {
+                run_id: row.get(0)?,
+                time: row.get(1)?,
+                level: row.get(2)?,
+                equip: row.get(3)?,
+                changes: row.get(4)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    pub fn get_runs_table(&self) -> Result<Table> {
+        let mut table = Table::new();
+        table.add_row(row!["Run", "Time", "Game", "UID", "Version", "Items"]);
+        for run in self.runs()? {
+            table.add_row(row![run.id, run.time, run.game, run.uid.unwrap_or_default(), run.tool_version, run.item_count]);
+        }
+        Ok(table)
+    }
+
+    pub fn get_entries_table(entries: &[HistoryEntry]) -> Table {
+        let mut table = Table::new();
+        table.add_row(row!["Item", "Description", "First seen", "Last seen"]);
+        for entry in entries.iter() {
+            table.add_row(row![entry.item_id, entry.description, entry.first_seen, entry.last_seen]);
+        }
+        table
+    }
+
+    pub fn get_sightings_table(&self, item_id: i64) -> Result<Table> {
+        let mut table = Table::new();
+        table.add_row(row!["Run", "Time", "Level", "Equip", "Changes"]);
+        for sighting in self.sightings(item_id)? {
+            table.add_row(row![
+                sighting.run_id,
+                sighting.time,
+                format!("+{}", sighting.level),
+                sighting.equip.unwrap_or_else(|| String::from("无")),
+                sighting.changes
+            ]);
+        }
+        Ok(table)
+    }
+}
+
+#[derive(clap::Args)]
+pub struct ScanHistoryConfig {
+    #[arg(id = "history-db", long = "history-db", help = "将本次扫描追加到 SQLite 历史数据库", value_name = "PATH")]
+    pub history_db: Option<String>,
+
+    #[arg(id = "uid", long = "uid", help = "记录到历史数据库的 UID，用于区分不同账号", value_name = "UID")]
+    pub uid: Option<String>,
+}
+
+impl ScanHistoryConfig {
+    /// Append `items` to the history database, if `--history-db` is given
+    pub fn record<T: HistoryItem>(&self, tool_version: &str, items: &[T]) -> Result<Option<i64>> {
+        let path = match self.history_db.as_ref() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let mut history = ScanHistory::open(path)?;
+        let run_id = history.record_run(self.uid.as_deref(), tool_version, items)?;
+        Ok(Some(run_id))
+    }
+}
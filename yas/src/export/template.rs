@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+
+use crate::export::ExportAssets;
+
+/// An item whose fields can be used in an export template
+pub trait TemplateItem {
+    /// Every placeholder of the item, with indexed ones written as `sub[].name`
+    const FIELDS: &'static [&'static str];
+
+    /// The value of a placeholder such as `set` or `sub[0].name`.
+    /// Returns `None` if the item has no such field, e.g. a missing 4th sub stat
+    fn field(&self, name: &str) -> Option<String>;
+}
+
+/// Split a placeholder such as `sub[1].value` into the index and the field name after it
+pub fn split_indexed_field<'a>(name: &'a str, prefix: &str) -> Option<(usize, &'a str)> {
+    let rest = name.strip_prefix(prefix)?.strip_prefix('[')?;
+    let (index, rest) = rest.split_once(']')?;
+    let field = rest.strip_prefix('.').unwrap_or(rest);
+    Some((index.parse().ok()?, field))
+}
+
+/// Whether `name` is one of `T::FIELDS`, with any index such as the `1` in `sub[1].value`
+fn is_known_field<T: TemplateItem>(name: &str) -> bool {
+    let pattern = match name.split_once('[') {
+        Some((prefix, rest)) => match rest.split_once(']') {
+            Some((index, rest)) if index.parse::<usize>().is_ok() => format!("{}[]{}", prefix, rest),
+            _ => return false,
+        },
+        None => String::from(name),
+    };
+    T::FIELDS.contains(&pattern.as_str())
+}
+
+enum Segment {
+    Text(String),
+    Field(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FilterOp {
+    Eq,
+    Ne,
+    Ge,
+    Le,
+    Gt,
+    Lt,
+}
+
+struct Filter {
+    field: String,
+    op: FilterOp,
+    value: String,
+}
+
+struct SortKey {
+    field: String,
+    descending: bool,
+}
+
+/// Compare as numbers when both sides are numbers, and as text otherwise
+fn compare_values(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+impl Filter {
+    // e.g. "star=5", "level>=16", "set!=GladiatorFinale"
+    fn parse(s: &str) -> Result<Filter> {
+        // two character operators first, so that ">=" is not read as ">"
+        const OPS: [(&str, FilterOp); 6] = [
+            ("!=", FilterOp::Ne),
+            (">=", FilterOp::Ge),
+            ("<=", FilterOp::Le),
+            ("=", FilterOp::Eq),
+            (">", FilterOp::Gt),
+            ("<", FilterOp::Lt),
+        ];
+
+        for (token, op) in OPS {
+            if let Some((field, value)) = s.split_once(token) {
+                return Ok(Filter {
+                    field: String::from(field.trim()),
+                    op,
+                    value: String::from(value.trim()),
+                });
+            }
+        }
+        Err(anyhow!("无法解析过滤条件：{}", s))
+    }
+
+    fn matches<T: TemplateItem>(&self, item: &T) -> bool {
+        let value = item.field(&self.field).unwrap_or_default();
+        let ordering = compare_values(&value, &self.value);
+        match self.op {
+            FilterOp::Eq => ordering == Ordering::Equal,
+            FilterOp::Ne => ordering != Ordering::Equal,
+            FilterOp::Ge => ordering != Ordering::Less,
+            FilterOp::Le => ordering != Ordering::Greater,
+            FilterOp::Gt => ordering == Ordering::Greater,
+            FilterOp::Lt => ordering == Ordering::Less,
+        }
+    }
+}
+
+/// A user defined text export, one line per item.
+///
+/// `{field}` is replaced with the field of the item, `{{` and `}}` are literal braces
+pub struct ItemTemplate {
+    header: Option<String>,
+    segments: Vec<Segment>,
+    filters: Vec<Filter>,
+    sort_keys: Vec<SortKey>,
+}
+
+impl ItemTemplate {
+    pub fn parse(template: &str) -> Result<ItemTemplate> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                },
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                },
+                '{' => {
+                    let mut field = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => field.push(c),
+                            None => return Err(anyhow!("模板中的 {{{} 没有闭合", field)),
+                        }
+                    }
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Field(String::from(field.trim())));
+                },
+                '}' => return Err(anyhow!("模板中有多余的 }}")),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+
+        Ok(ItemTemplate {
+            header: None,
+            segments,
+            filters: Vec::new(),
+            sort_keys: Vec::new(),
+        })
+    }
+
+    /// A line written before the items, e.g. the column names of a CSV
+    pub fn with_header(mut self, header: Option<String>) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Only keep items matching every filter, such as `star=5` or `level>=16`
+    pub fn with_filters<S: AsRef<str>>(mut self, filters: &[S]) -> Result<Self> {
+        for filter in filters.iter() {
+            self.filters.push(Filter::parse(filter.as_ref())?);
+        }
+        Ok(self)
+    }
+
+    /// Sort items by these fields in order, a leading `-` sorts that field descending
+    pub fn with_sort_keys<S: AsRef<str>>(mut self, sort_keys: &[S]) -> Self {
+        for key in sort_keys.iter() {
+            let key = key.as_ref().trim();
+            let (field, descending) = match key.strip_prefix('-') {
+                Some(field) => (field, true),
+                None => (key, false),
+            };
+            self.sort_keys.push(SortKey { field: String::from(field), descending });
+        }
+        self
+    }
+
+    /// Fail on a placeholder, filter or sort key that `T` does not have, e.g. a misspelled `{mian.name}`,
+    /// which would otherwise silently render empty
+    pub fn check_fields<T: TemplateItem>(&self) -> Result<()> {
+        let fields = self.segments.iter()
+            .filter_map(|x| match x {
+                Segment::Field(field) => Some(field),
+                Segment::Text(_) => None,
+            })
+            .chain(self.filters.iter().map(|x| &x.field))
+            .chain(self.sort_keys.iter().map(|x| &x.field));
+        for field in fields {
+            if !is_known_field::<T>(field) {
+                return Err(anyhow!("模板中的字段 {} 不存在，可用的字段有：{}", field, T::FIELDS.join(", ")));
+            }
+        }
+        Ok(())
+    }
+
+    fn render_item<T: TemplateItem>(&self, item: &T) -> String {
+        let mut line = String::new();
+        for segment in self.segments.iter() {
+            match segment {
+                Segment::Text(text) => line.push_str(text),
+                Segment::Field(field) => line.push_str(&item.field(field).unwrap_or_default()),
+            }
+        }
+        line
+    }
+
+    pub fn render<T: TemplateItem>(&self, items: &[T]) -> String {
+        let mut selected: Vec<&T> = items.iter()
+            .filter(|item| self.filters.iter().all(|f| f.matches(*item)))
+            .collect();
+
+        // stable, so items equal on every key keep the scan order
+        selected.sort_by(|a, b| {
+            for key in self.sort_keys.iter() {
+                let x = a.field(&key.field).unwrap_or_default();
+                let y = b.field(&key.field).unwrap_or_default();
+                let ordering = compare_values(&x, &y);
+                let ordering = if key.descending { ordering.reverse() } else { ordering };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+
+        let mut result = String::new();
+        if let Some(header) = self.header.as_ref() {
+            result.push_str(header);
+            result.push('\n');
+        }
+        for item in selected {
+            result.push_str(&self.render_item(item));
+            result.push('\n');
+        }
+        result
+    }
+}
+
+/// A template and the file it is exported to
+pub struct TemplateExport {
+    pub template: ItemTemplate,
+    pub filename: String,
+}
+
+impl TemplateExport {
+    pub fn emit<T: TemplateItem>(&self, items: &[T], output_dir: &Path, export_assets: &mut ExportAssets) {
+        export_assets.add_asset(
+            Some(String::from("template")),
+            output_dir.join(&self.filename),
+            self.template.render(items).into_bytes(),
+            Some(String::from("自定义模板"))
+        );
+    }
+}
+
+#[derive(clap::Args)]
+pub struct TemplateExportConfig {
+    #[arg(id = "template", long = "template", help = "自定义导出模板，如 \"{set},{slot},{main.name},{sub[0].name}\"，以 @ 开头时从文件读取", value_name = "TEMPLATE")]
+    pub template: Option<String>,
+
+    #[arg(id = "template-header", long = "template-header", help = "自定义导出的第一行，如 CSV 的列名", value_name = "TEXT")]
+    pub template_header: Option<String>,
+
+    #[arg(id = "template-filter", long = "template-filter", help = "只导出满足条件的物品，如 star=5 或 level>=16，可以重复", value_name = "FILTER")]
+    pub template_filter: Vec<String>,
+
+    #[arg(id = "template-sort", long = "template-sort", value_delimiter = ',', help = "排序字段，以 - 开头时降序，如 -level,set", value_name = "KEYS")]
+    pub template_sort: Vec<String>,
+
+    #[arg(id = "template-output", long = "template-output", default_value_t = String::from("template.csv"), help = "自定义导出的文件名")]
+    pub template_output: String,
+}
+
+impl TemplateExportConfig {
+    /// The template export of `T` described by the options, `None` without `--template`
+    pub fn build<T: TemplateItem>(&self) -> Result<Option<TemplateExport>> {
+        let template = match self.template.as_ref() {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let template = match template.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path)?.trim_end().to_string(),
+            None => template.clone(),
+        };
+
+        let template = ItemTemplate::parse(&template)?
+            .with_header(self.template_header.clone())
+            .with_filters(&self.template_filter)?
+            .with_sort_keys(&self.template_sort);
+        template.check_fields::<T>()?;
+        Ok(Some(TemplateExport {
+            template,
+            filename: self.template_output.clone(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        name: &'static str,
+        level: i32,
+        subs: Vec<i32>,
+    }
+
+    impl TemplateItem for Item {
+        const FIELDS: &'static [&'static str] = &["name", "level", "sub[].value"];
+
+        fn field(&self, name: &str) -> Option<String> {
+            if let Some((index, "value")) = split_indexed_field(name, "sub") {
+                return self.subs.get(index).map(|x| x.to_string());
+            }
+            match name {
+                "name" => Some(String::from(self.name)),
+                "level" => Some(self.level.to_string()),
+                _ => None,
+            }
+        }
+    }
+
+    fn items() -> Vec<Item> {
+        vec![
+            Item { name: "a", level: 4, subs: vec![1, 2] },
+            Item { name: "b", level: 20, subs: vec![3] },
+            Item { name: "c", level: 16, subs: vec![] },
+        ]
+    }
+
+    #[test]
+    fn test_render() {
+        let template = ItemTemplate::parse("{name},{{{level}}},{sub[1].value}").unwrap()
+            .with_header(Some(String::from("name,level,sub")))
+            .with_filters(&["level>=10"]).unwrap()
+            .with_sort_keys(&["-level"]);
+        template.check_fields::<Item>().unwrap();
+        assert_eq!(template.render(&items()), "name,level,sub\nb,{20},\nc,{16},\n");
+    }
+
+    #[test]
+    fn test_unknown_field() {
+        let template = ItemTemplate::parse("{name},{levle}").unwrap();
+        assert!(template.check_fields::<Item>().is_err());
+
+        let template = ItemTemplate::parse("{sub[x].value}").unwrap();
+        assert!(template.check_fields::<Item>().is_err());
+
+        let template = ItemTemplate::parse("{name}").unwrap().with_sort_keys(&["-lvl"]);
+        assert!(template.check_fields::<Item>().is_err());
+    }
+}
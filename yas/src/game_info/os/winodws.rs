@@ -41,9 +41,9 @@ fn get_window(window_names: &[&str]) -> Result<(HWND, bool)> {
         return Err(anyhow!("未找到游戏窗口，请确认{:?}已经开启", window_names));
     }
 
-    println!("找到多个符合名称的窗口，请手动选择窗口：");
+    eprintln!("找到多个符合名称的窗口，请手动选择窗口：");
     for (i, (hwnd, title)) in viable_handles.iter().enumerate() {
-        println!("{}: {}", i, title);
+        eprintln!("{}: {}", i, title);
     }
     let mut index = String::new();
     stdin().read_line(&mut index);
@@ -9,6 +9,7 @@ extern crate log;
 extern crate lazy_static;
 
 pub mod common;
+pub mod config;
 pub mod export;
 pub mod draw_capture_region;
 pub mod capture;
@@ -19,3 +20,4 @@ pub mod system_control;
 pub mod ocr;
 pub mod positioning;
 pub mod profiler;
+pub mod server;
@@ -55,10 +55,10 @@ impl Profiler {
     }
 
     pub fn print(&self) {
-        println!("Profile:");
+        eprintln!("Profile:");
         for (k, v) in self.time_table.iter() {
             let ms = v.1.as_millis() as f64 / (v.0 as f64);
-            println!("{}: avg {}ms, execution count: {}", k, ms, v.0);
+            eprintln!("{}: avg {}ms, execution count: {}", k, ms, v.0);
         }
     }
 }
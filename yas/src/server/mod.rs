@@ -0,0 +1,3 @@
+pub use scan_server::{ScanRunner, ScanServer};
+
+mod scan_server;
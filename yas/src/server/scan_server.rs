@@ -0,0 +1,384 @@
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use log::{error, info};
+use rand::Rng;
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::common::cancel::CancellationToken;
+use crate::export::{emit_error, subscribe_events};
+
+/// Run a scan with the command line arguments of a game, e.g. `["genshin", "--min-star=5"]`
+pub type ScanRunner = Box<dyn Fn(Vec<String>, CancellationToken) -> Result<()> + Send + Sync>;
+
+struct Game {
+    /// The options a scan request may set, none of them may name a file
+    options: Vec<String>,
+    runner: ScanRunner,
+}
+
+struct RunningScan {
+    game: String,
+    token: CancellationToken,
+}
+
+#[derive(Default)]
+struct ServerState {
+    scan_count: usize,
+    running: Option<RunningScan>,
+    /// The zip of the assets of the last finished scan
+    last_export: Option<PathBuf>,
+}
+
+/// A local HTTP server that lets a browser drive the scanner.
+///
+/// Every request must carry the token printed at startup, either as `Authorization: Bearer <token>`
+/// or as `?token=<token>`, and browsers are only let in from the allowed origins.
+///
+/// - `POST /scan/<game>` starts a scan of a game added with `add_game`, the body is a JSON object
+///   of command line options, e.g. `{"format": "good", "min-star": 5}`
+/// - `GET /events` streams the `--json-lines` events as server sent events
+/// - `POST /cancel` cancels the running scan
+/// - `GET /status` tells whether a scan is running
+/// - `GET /export` downloads the assets of the last scan as a zip
+pub struct ScanServer {
+    games: HashMap<String, Game>,
+    output_dir: PathBuf,
+    allowed_origins: Vec<String>,
+    token: String,
+    state: Mutex<ServerState>,
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap()
+}
+
+fn get_header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request.headers().iter()
+        .find(|x| x.field.equiv(name))
+        .map(|x| x.value.as_str())
+}
+
+fn get_query<'a>(url: &'a str, name: &str) -> Option<&'a str> {
+    let (_, query) = url.split_once('?')?;
+    query.split('&')
+        .filter_map(|x| x.split_once('='))
+        .find(|(k, _)| *k == name)
+        .map(|(_, v)| v)
+}
+
+/// Compare without returning early, so that the time taken does not leak the token
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn json_response(status: u16, value: Value) -> Response<Cursor<Vec<u8>>> {
+    Response::from_data(value.to_string().into_bytes())
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+}
+
+fn error_response(status: u16, message: &str) -> Response<Cursor<Vec<u8>>> {
+    json_response(status, json!({ "error": message }))
+}
+
+/// Turn a JSON object of options into command line arguments, rejecting any option not in `allowed`
+fn config_to_args(config: &Value, allowed: &[String]) -> Result<Vec<String>> {
+    let map = match config {
+        Value::Null => return Ok(Vec::new()),
+        Value::Object(v) => v,
+        _ => return Err(anyhow!("扫描配置必须是 JSON 对象")),
+    };
+
+    let to_string = |value: &Value| match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    };
+
+    let mut args = Vec::new();
+    for (key, value) in map.iter() {
+        if !allowed.contains(key) {
+            return Err(anyhow!("不允许的选项：{}", key));
+        }
+        match value {
+            Value::Bool(true) => args.push(format!("--{}", key)),
+            Value::Bool(false) | Value::Null => {},
+            Value::Array(values) => {
+                for v in values.iter() {
+                    args.push(format!("--{}={}", key, to_string(v)));
+                }
+            },
+            v => args.push(format!("--{}={}", key, to_string(v))),
+        }
+    }
+    Ok(args)
+}
+
+impl ScanServer {
+    pub fn new(output_dir: PathBuf) -> ScanServer {
+        let token = rand::thread_rng().gen::<[u8; 16]>()
+            .iter()
+            .map(|x| format!("{:02x}", x))
+            .collect();
+        ScanServer {
+            games: HashMap::new(),
+            output_dir,
+            allowed_origins: Vec::new(),
+            token,
+            state: Mutex::new(ServerState::default()),
+        }
+    }
+
+    /// Add a game, scan requests may only set the given `options`.
+    /// Options that name a file, such as `--merge-into` or `--template`, must not be listed
+    pub fn add_game(mut self, game: &str, options: &[&str], runner: ScanRunner) -> Self {
+        let options = options.iter().map(|x| String::from(*x)).collect();
+        self.games.insert(String::from(game), Game { options, runner });
+        self
+    }
+
+    /// Let browsers on `origin`, e.g. `https://example.com`, call the server
+    pub fn allow_origin(mut self, origin: &str) -> Self {
+        self.allowed_origins.push(String::from(origin.trim_end_matches('/')));
+        self
+    }
+
+    /// Serve on `127.0.0.1:port` until the process exits
+    pub fn serve(mut self, port: u16) -> Result<()> {
+        let server = Server::http(("127.0.0.1", port)).map_err(|e| anyhow!("无法监听端口 {}: {}", port, e))?;
+        self.allowed_origins.push(format!("http://127.0.0.1:{}", port));
+        self.allowed_origins.push(format!("http://localhost:{}", port));
+        info!("服务已启动：http://127.0.0.1:{}", port);
+        info!("访问令牌：{}", self.token);
+        info!("允许的来源：{}", self.allowed_origins.join(", "));
+
+        let this = Arc::new(self);
+        for request in server.incoming_requests() {
+            let this = this.clone();
+            // event streams stay open, so every request gets its own thread
+            std::thread::spawn(move || {
+                if let Err(e) = this.handle(request) {
+                    error!("请求处理失败: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// A page on another site may not reach the server by name, e.g. through DNS rebinding
+    fn is_allowed_host(&self, host: Option<&str>) -> bool {
+        let host = match host {
+            Some(v) => v,
+            None => return false,
+        };
+        let host = host.rsplit_once(':').map(|x| x.0).unwrap_or(host);
+        host == "127.0.0.1" || host == "localhost"
+    }
+
+    fn is_allowed_origin(&self, origin: &str) -> bool {
+        self.allowed_origins.iter().any(|x| x == origin)
+    }
+
+    fn is_authorized(&self, request: &Request) -> bool {
+        let token = get_header(request, "Authorization")
+            .and_then(|x| x.strip_prefix("Bearer "))
+            .or_else(|| get_query(request.url(), "token"));
+        match token {
+            Some(v) => constant_time_eq(v.trim().as_bytes(), self.token.as_bytes()),
+            None => false,
+        }
+    }
+
+    fn handle(self: &Arc<Self>, mut request: Request) -> Result<()> {
+        let url = request.url().split('?').next().unwrap_or_default().to_string();
+        let method = request.method().clone();
+
+        if !self.is_allowed_host(get_header(&request, "Host")) {
+            request.respond(error_response(403, "不允许的 Host"))?;
+            return Ok(());
+        }
+        // requests without an origin do not come from a browser
+        let origin = get_header(&request, "Origin").map(String::from);
+        if let Some(origin) = origin.as_ref() {
+            if !self.is_allowed_origin(origin) {
+                request.respond(error_response(403, &format!("不允许的来源：{}", origin)))?;
+                return Ok(());
+            }
+        }
+        let cors = origin.as_ref().map(|x| header("Access-Control-Allow-Origin", x));
+
+        let respond = |request: Request, response: Response<Cursor<Vec<u8>>>| -> Result<()> {
+            let response = match cors.clone() {
+                Some(v) => response.with_header(v).with_header(header("Vary", "Origin")),
+                None => response,
+            };
+            request.respond(response)?;
+            Ok(())
+        };
+
+        if method == Method::Options {
+            let response = Response::from_data(Vec::new())
+                .with_status_code(204)
+                .with_header(header("Access-Control-Allow-Methods", "GET, POST, OPTIONS"))
+                .with_header(header("Access-Control-Allow-Headers", "Authorization, Content-Type"));
+            return respond(request, response);
+        }
+        if !self.is_authorized(&request) {
+            return respond(request, error_response(401, "缺少或错误的访问令牌"));
+        }
+
+        match (method, url.as_str()) {
+            (Method::Get, "/status") => respond(request, self.status()),
+            (Method::Get, "/events") => self.stream_events(request, origin.as_deref()),
+            (Method::Post, "/cancel") => respond(request, self.cancel()),
+            (Method::Get, "/export") => {
+                let response = self.export();
+                respond(request, response)
+            },
+            (Method::Post, path) if path.starts_with("/scan/") => {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body)?;
+                let response = self.start_scan(&path["/scan/".len()..], &body);
+                respond(request, response)
+            },
+            (_, _) => respond(request, error_response(404, "not found")),
+        }
+    }
+
+    fn status(&self) -> Response<Cursor<Vec<u8>>> {
+        let state = self.state.lock().unwrap();
+        json_response(200, json!({
+            "running": state.running.is_some(),
+            "game": state.running.as_ref().map(|x| x.game.clone()),
+            "export": state.last_export.is_some(),
+            "games": self.games.keys().collect::<Vec<_>>(),
+        }))
+    }
+
+    fn cancel(&self) -> Response<Cursor<Vec<u8>>> {
+        let state = self.state.lock().unwrap();
+        match state.running.as_ref() {
+            Some(scan) => {
+                scan.token.cancel();
+                json_response(200, json!({ "cancelled": true }))
+            },
+            None => json_response(200, json!({ "cancelled": false })),
+        }
+    }
+
+    fn start_scan(self: &Arc<Self>, game: &str, body: &str) -> Response<Cursor<Vec<u8>>> {
+        let options = match self.games.get(game) {
+            Some(v) => &v.options,
+            None => return error_response(404, &format!("不支持的游戏：{}", game)),
+        };
+        let config: Value = if body.trim().is_empty() {
+            Value::Null
+        } else {
+            match serde_json::from_str(body) {
+                Ok(v) => v,
+                Err(e) => return error_response(400, &e.to_string()),
+            }
+        };
+        let config_args = match config_to_args(&config, options) {
+            Ok(v) => v,
+            Err(e) => return error_response(400, &e.to_string()),
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if state.running.is_some() {
+            return error_response(409, "已有扫描正在进行");
+        }
+        state.scan_count += 1;
+        let scan_id = state.scan_count;
+        let token = CancellationToken::new();
+        state.running = Some(RunningScan { game: String::from(game), token: token.clone() });
+
+        let output_dir = self.output_dir.join(format!("scan-{}", scan_id));
+        let zip = output_dir.join("export.zip");
+        let mut args = vec![String::from(game)];
+        args.extend(config_args);
+        args.push(format!("--output-dir={}", output_dir.to_string_lossy()));
+        args.push(format!("--zip={}", zip.to_string_lossy()));
+
+        let this = self.clone();
+        let game = String::from(game);
+        std::thread::spawn(move || {
+            let result = (this.games[&game].runner)(args, token);
+            if let Err(e) = &result {
+                error!("扫描失败: {}", e);
+                emit_error(&e.to_string());
+            }
+
+            let mut state = this.state.lock().unwrap();
+            state.running = None;
+            if result.is_ok() && zip.exists() {
+                state.last_export = Some(zip);
+            }
+        });
+
+        json_response(202, json!({ "scan": scan_id }))
+    }
+
+    fn stream_events(&self, request: Request, origin: Option<&str>) -> Result<()> {
+        let events = subscribe_events();
+        let mut writer = request.into_writer();
+        write!(writer, "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n")?;
+        if let Some(origin) = origin {
+            write!(writer, "Access-Control-Allow-Origin: {}\r\nVary: Origin\r\n", origin)?;
+        }
+        write!(writer, "\r\n")?;
+        writer.flush()?;
+
+        loop {
+            match events.recv_timeout(Duration::from_secs(15)) {
+                Ok(event) => write!(writer, "data: {}\n\n", event)?,
+                // a comment, so that a closed connection is noticed while nothing happens
+                Err(_) => write!(writer, ": keep-alive\n\n")?,
+            }
+            writer.flush()?;
+        }
+    }
+
+    fn export(&self) -> Response<Cursor<Vec<u8>>> {
+        let path = self.state.lock().unwrap().last_export.clone();
+        let path = match path {
+            Some(v) => v,
+            None => return error_response(404, "还没有完成的扫描"),
+        };
+
+        match std::fs::read(&path) {
+            Ok(data) => Response::from_data(data)
+                .with_header(header("Content-Type", "application/zip"))
+                .with_header(header("Content-Disposition", "attachment; filename=\"export.zip\"")),
+            Err(e) => error_response(500, &e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_to_args() {
+        let allowed = vec![String::from("min-star"), String::from("ignore-dup")];
+
+        let args = config_to_args(&json!({ "min-star": 5, "ignore-dup": true }), &allowed).unwrap();
+        assert_eq!(args, vec!["--ignore-dup", "--min-star=5"]);
+
+        assert!(config_to_args(&json!({ "merge-into": "good.json" }), &allowed).is_err());
+        assert!(config_to_args(&json!({ "output-dir": "/" }), &allowed).is_err());
+        assert!(config_to_args(&json!(["--min-star=5"]), &allowed).is_err());
+    }
+
+    #[test]
+    fn test_get_query() {
+        assert_eq!(get_query("/events?a=1&token=abc", "token"), Some("abc"));
+        assert_eq!(get_query("/events", "token"), None);
+    }
+}
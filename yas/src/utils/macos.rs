@@ -53,7 +53,7 @@ pub fn get_titlebar_height() -> f64 {
             )
             .autorelease()
     };
-    println!("{}", unsafe {
+    log::debug!("title bar height: {}", unsafe {
         ns_size.height - ns_window.contentRectForFrameRect_(ns_rect).size.height
     });
     unsafe { ns_size.height - ns_window.contentRectForFrameRect_(ns_rect).size.height }
@@ -8,6 +8,10 @@ pub fn color_distance(c1: &image::Rgb<u8>, c2: &image::Rgb<u8>) -> usize {
     (x * x + y * y + z * z) as usize
 }
 
+/// Does nothing with `--json-lines`, where yas is run by another program
 pub fn press_any_key_to_continue() {
+    if crate::export::is_json_lines_enabled() {
+        return;
+    }
     let _ = std::io::stdin().read(&mut [0u8]).unwrap();
 }
@@ -7,7 +7,7 @@ use std::pin::{Pin, pin};
 use std::ptr::{null, null_mut, slice_from_raw_parts_mut};
 
 use anyhow::{anyhow, Result};
-use log::{info, warn};
+use log::{debug, info, warn};
 use windows_sys::Win32::Foundation::*;
 use windows_sys::Win32::Graphics::Gdi::ClientToScreen;
 use windows_sys::Win32::Security::*;
@@ -138,7 +138,7 @@ pub fn set_dpi_awareness() {
         let utf16 = encode_lpcstr("Shcore.dll");
         LoadLibraryA(utf16.as_ptr())
     };
-    println!("{:?}", h_lib);
+    debug!("Shcore.dll: {:?}", h_lib);
     if h_lib.is_null() {
         unsafe {
             SetProcessDPIAware();
@@ -146,7 +146,7 @@ pub fn set_dpi_awareness() {
     } else {
         unsafe {
             let addr = GetProcAddress(h_lib, encode_lpcstr("SetProcessDpiAwareness").as_ptr());
-            println!("{:?}", addr);
+            debug!("SetProcessDpiAwareness: {:?}", addr);
             if addr.is_none() {
                 warn!("cannot find process `SetProcessDpiAwareness`, but `Shcore.dll` exists");
                 SetProcessDPIAware();